@@ -0,0 +1,149 @@
+//! Accessibility options, picked in the accessibility menu and remembered between sessions:
+//! colour-blind safe palettes laid over the theme, a global UI scale, reduced motion and high
+//! contrast orbit projections. Systems that blink, pulse or wobble check
+//! [`Accessibility::reduced_motion`] and hold still instead.
+
+use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+use crate::persistence;
+use crate::theme::{HudTheme, ThemeColor};
+
+/// Stores the picked options
+const ACCESSIBILITY_KEY: &str = "accessibility.ron";
+/// Smallest and largest UI scale offered in the menu
+const UI_SCALE_RANGE: (f32, f32) = (0.75, 2.0);
+/// Step by which the menu buttons change the UI scale
+pub const UI_SCALE_STEP: f32 = 0.125;
+
+pub(super) fn plugin(app: &mut App) {
+    app.insert_resource(Accessibility::load());
+    app.add_systems(Update, apply_accessibility.run_if(resource_changed::<Accessibility>));
+}
+
+#[derive(Resource, Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Accessibility {
+    pub palette: Palette,
+    pub ui_scale: f32,
+    /// No blinking warnings, pulsing glows or wobbling flames
+    pub reduced_motion: bool,
+    /// Draws orbit projections brighter and bolder
+    pub high_contrast_orbits: bool,
+}
+
+impl Default for Accessibility {
+    fn default() -> Self {
+        Self {
+            palette: Palette::Theme,
+            ui_scale: 1.0,
+            reduced_motion: false,
+            high_contrast_orbits: false,
+        }
+    }
+}
+
+impl Accessibility {
+    /// The options saved in an earlier session, the defaults on the first start
+    fn load() -> Self {
+        persistence::read(ACCESSIBILITY_KEY)
+            .and_then(|contents| ron::from_str(&contents).ok())
+            .unwrap_or_default()
+    }
+
+    pub fn change_ui_scale(&mut self, delta: f32) {
+        self.ui_scale = (self.ui_scale + delta).clamp(UI_SCALE_RANGE.0, UI_SCALE_RANGE.1);
+    }
+
+    /// Remembers the options for the next session
+    pub fn save(&self) {
+        let contents = match ron::ser::to_string(self) {
+            Ok(contents) => contents,
+            Err(e) => {
+                error!("Could not serialize accessibility options: {e}");
+                return;
+            }
+        };
+        if let Err(e) = persistence::write(ACCESSIBILITY_KEY, &contents) {
+            warn!("Could not save accessibility options: {e}");
+        }
+    }
+}
+
+/// Colours laid over the theme so warnings, selections and values stay apart for colour-blind
+/// players. Based on the Okabe-Ito palette.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub enum Palette {
+    /// The colours of the theme as they are
+    #[default]
+    Theme,
+    /// For protanopia and deuteranopia, keeps warnings away from red and green
+    RedGreen,
+    /// For tritanopia, keeps blues and yellows apart
+    BlueYellow,
+}
+
+impl Palette {
+    pub fn next(self) -> Self {
+        match self {
+            Palette::Theme => Palette::RedGreen,
+            Palette::RedGreen => Palette::BlueYellow,
+            Palette::BlueYellow => Palette::Theme,
+        }
+    }
+
+    /// Key of the palette name in the string tables
+    pub fn name_key(self) -> &'static str {
+        match self {
+            Palette::Theme => "accessibility.palette_theme",
+            Palette::RedGreen => "accessibility.palette_red_green",
+            Palette::BlueYellow => "accessibility.palette_blue_yellow",
+        }
+    }
+
+    fn colors(self) -> HashMap<ThemeColor, Color> {
+        let colors = match self {
+            Palette::Theme => Vec::new(),
+            Palette::RedGreen => vec![
+                (ThemeColor::Text, Color::srgb_u8(0xe6, 0x9f, 0x00)),
+                (ThemeColor::Dim, Color::srgb_u8(0x80, 0x58, 0x00)),
+                (ThemeColor::Highlight, Color::srgb_u8(0xf0, 0xe4, 0x42)),
+                (ThemeColor::Accent, Color::srgb_u8(0xcc, 0x79, 0xa7)),
+                (ThemeColor::Warning, Color::srgb_u8(0x56, 0xb4, 0xe9)),
+                (ThemeColor::Earth, Color::srgb_u8(0x00, 0x72, 0xb2)),
+                (ThemeColor::Glow, Color::srgb_u8(0xf0, 0xe4, 0x42)),
+                (ThemeColor::Field, Color::srgb_u8(0xf0, 0xe4, 0x42)),
+                (ThemeColor::Fire, Color::srgb_u8(0xd5, 0x5e, 0x00)),
+                (ThemeColor::Trail, Color::srgb_u8(0x00, 0x9e, 0x73)),
+            ],
+            Palette::BlueYellow => vec![
+                (ThemeColor::Text, Color::srgb_u8(0xff, 0x60, 0x00)),
+                (ThemeColor::Dim, Color::srgb_u8(0x98, 0x39, 0x00)),
+                (ThemeColor::Highlight, Color::srgb_u8(0xff, 0xc0, 0xa0)),
+                (ThemeColor::Accent, Color::srgb_u8(0x00, 0x9e, 0x73)),
+                (ThemeColor::Warning, Color::srgb_u8(0xff, 0x33, 0x99)),
+                (ThemeColor::Earth, Color::srgb_u8(0x00, 0x72, 0xb2)),
+                (ThemeColor::Glow, Color::srgb_u8(0xff, 0xff, 0xff)),
+                (ThemeColor::Field, Color::srgb_u8(0xff, 0xc0, 0xa0)),
+                (ThemeColor::Fire, Color::srgb_u8(0xd5, 0x00, 0x00)),
+                (ThemeColor::Trail, Color::srgb_u8(0xcc, 0x79, 0xa7)),
+            ],
+        };
+        colors.into_iter().collect()
+    }
+}
+
+fn apply_accessibility(
+    accessibility: Res<Accessibility>,
+    mut theme: ResMut<HudTheme>,
+    mut ui_scale: ResMut<UiScale>,
+) {
+    let palette = accessibility.palette.colors();
+    if theme.palette != palette {
+        theme.palette = palette;
+    }
+    if ui_scale.0 != accessibility.ui_scale {
+        ui_scale.0 = accessibility.ui_scale;
+    }
+}
@@ -1,7 +1,10 @@
 use bevy::prelude::*;
-use crate::GameplaySystem;
+use crate::{GameplaySystem, PausableSystems};
+use crate::screens::InGame;
+use crate::theme::{HudTheme, ThemeColor};
 
 pub struct AchievementsPlugin;
+/// A collector has flown once around the sun, see [`crate::sun_system::navigation_instruments::track_flown_orbits`]
 #[derive(Event, Debug, Copy, Clone)]
 pub struct FullOrbitAchieved {
     pub entity: Entity,
@@ -10,15 +13,11 @@ pub struct FullOrbitAchieved {
 impl Plugin for AchievementsPlugin {
     fn build(&self, app: &mut App) {
         app.add_observer(on_full_orbit);
-        app.add_systems(Update, update_neon_circle_fx.in_set(GameplaySystem));
+        app.add_systems(Update, update_neon_circle_fx.in_set(GameplaySystem).in_set(PausableSystems));
     }
 }
 
 
-
-#[derive(Component, Debug, Copy, Clone)]
-pub struct FullOrbitAwarded;
-
 #[derive(Component, Debug, Copy, Clone)]
 struct NeonCircleFx {
     center: Vec2,
@@ -32,7 +31,10 @@ fn on_full_orbit(
     mut commands: Commands,
 ) {
     if let Ok(t) = q_t.get(ev.entity) {
-        commands.spawn(NeonCircleFx { center: t.translation.xy(), elapsed: 0.2, duration: 1.0 });
+        commands.spawn((
+            NeonCircleFx { center: t.translation.xy(), elapsed: 0.2, duration: 1.0 },
+            DespawnOnExit(InGame),
+        ));
     }
 }
 
@@ -41,6 +43,7 @@ fn update_neon_circle_fx(
     mut gizmos: Gizmos,
     mut q: Query<(Entity, &mut NeonCircleFx)>,
     time: Res<Time>,
+    theme: Res<HudTheme>,
 ) {
     for (e, mut fx) in q.iter_mut() {
         fx.elapsed += time.delta_secs();
@@ -48,7 +51,7 @@ fn update_neon_circle_fx(
 
         let radius = 3.0 + 6.0 * t; // expand
         let alpha = (1.0 - t).powf(2.0); // fade out
-        let color = Color::srgb(0.2, 0.8, 1.0).with_alpha(0.75 * alpha);
+        let color = theme.color(ThemeColor::Glow).with_alpha(0.75 * alpha);
 
         let iso = Isometry2d::from_translation(fx.center);
         gizmos.circle_2d(iso, radius, color);
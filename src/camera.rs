@@ -0,0 +1,380 @@
+//! The gameplay camera. It pans with the pan actions, WASD and dragging with the middle mouse
+//! button by default, with the right stick, by moving the cursor to the edge of the window or by
+//! dragging with two fingers. The mouse wheel and pinching zoom smoothly towards the cursor or
+//! the fingers, the zoom actions towards the center. In follow mode the camera stays on the
+//! selected collector, or on earth when none is selected.
+
+use bevy::input::mouse::{MouseScrollUnit, MouseWheel};
+use bevy::input::touch::{TouchInput, TouchPhase};
+use bevy::prelude::*;
+use bevy::window::PrimaryWindow;
+use std::collections::HashMap;
+
+use crate::controls::{Action, ActionInput, STICK_DEADZONE, action_just_pressed};
+use crate::menus::widget;
+use crate::screens::{InitGameResource, Screen};
+use crate::sun_system::SolarSystemAssets;
+use crate::sun_system::earth::Earth;
+use crate::sun_system::navigation_instruments::NavigationInstruments;
+use crate::{GameplaySystem, PausableSystems};
+
+const MIN_SCALE: f32 = 0.1;
+const MAX_SCALE: f32 = 0.75;
+const START_SCALE: f32 = 0.25;
+/// Factor the scale changes by for each line the wheel scrolls
+const WHEEL_ZOOM: f32 = 1.25;
+/// Pixels that count as one line for touchpads
+const PIXELS_PER_LINE: f32 = 100.0;
+/// Factor the scale changes by per second while a zoom action is held
+const ZOOM_RATE: f32 = 3.0;
+/// How quickly the scale catches up with the zoom, higher is snappier
+const ZOOM_SMOOTHING: f32 = 12.0;
+/// How quickly the camera catches up with the followed entity
+const FOLLOW_SMOOTHING: f32 = 8.0;
+/// Screen pixels per second panned with the keys or at the edge of the window
+const PAN_SPEED: f32 = 600.0;
+/// Width of the border of the window that pans the camera, in pixels
+const EDGE_MARGIN: f32 = 8.0;
+/// Distance from the sun the camera can't go beyond
+const CAMERA_BOUNDS: f32 = 700.0;
+/// Directions on screen the pan actions move the camera in
+const PAN_ACTIONS: [(Action, Vec2); 4] = [
+    (Action::PanUp, Vec2::NEG_Y),
+    (Action::PanLeft, Vec2::NEG_X),
+    (Action::PanDown, Vec2::Y),
+    (Action::PanRight, Vec2::X),
+];
+
+pub(super) fn plugin(app: &mut App) {
+    app.init_resource::<PinchZoomState>();
+    app.init_game_resource::<CameraFollow>();
+    app.add_observer(toggle_follow);
+    app.add_systems(OnEnter(Screen::Gameplay), reset_camera);
+    app.add_systems(
+        Update,
+        (
+            request_follow.run_if(action_just_pressed(Action::FollowCamera)),
+            drag_pan,
+            key_pan,
+            edge_pan,
+            stick_pan,
+            camera_zoom,
+            action_zoom,
+            camera_pinch_zoom,
+            apply_zoom,
+            follow_target,
+            clamp_camera,
+        )
+            .chain()
+            .in_set(GameplaySystem)
+            .in_set(PausableSystems),
+    );
+}
+
+#[derive(Component)]
+pub struct CameraZoom {
+    /// Scale the camera is zooming to
+    target: f32,
+    /// Viewport position that stays in place while zooming, the center if `None`
+    anchor: Option<Vec2>,
+}
+
+impl CameraZoom {
+    /// Multiplies the target scale by `factor`, tells whether it changed
+    fn zoom_by(&mut self, factor: f32, anchor: Option<Vec2>) -> bool {
+        let target = (self.target * factor).clamp(MIN_SCALE, MAX_SCALE);
+        self.anchor = anchor;
+        if target == self.target {
+            return false;
+        }
+        self.target = target;
+        true
+    }
+}
+
+/// The player zoomed the camera in or out
+#[derive(Event, Debug)]
+pub struct CameraZoomed;
+
+/// Starts following the selected collector or earth, or stops following
+#[derive(Event, Debug)]
+pub struct ToggleCameraFollow;
+
+/// Entity the camera stays on. Panning by hand or moving the camera from the HUD stops following.
+#[derive(Resource, Default)]
+pub struct CameraFollow(pub Option<Entity>);
+
+#[derive(Resource, Default)]
+struct PinchZoomState {
+    /// Center and distance of the two fingers on the previous frame
+    previous: Option<(Vec2, f32)>,
+    active: HashMap<u64, Vec2>,
+}
+
+fn reset_camera(mut commands: Commands, camera_query: Query<Entity, With<Camera2d>>) {
+    for camera in camera_query.iter() {
+        commands.entity(camera).insert((
+            Transform::from_scale(Vec3::splat(START_SCALE)),
+            CameraZoom {
+                target: START_SCALE,
+                anchor: None,
+            },
+        ));
+    }
+}
+
+/// Moves the camera by `delta` logical pixels on screen and stops following
+fn pan(transform: &mut Transform, follow: &mut CameraFollow, delta: Vec2) {
+    if delta == Vec2::ZERO {
+        return;
+    }
+    follow.0 = None;
+    transform.translation += (Vec2::new(delta.x, -delta.y) * transform.scale.x).extend(0.0);
+}
+
+fn drag_pan(
+    input: ActionInput,
+    window: Query<&Window, With<PrimaryWindow>>,
+    mut last_cursor: Local<Option<Vec2>>,
+    mut camera: Query<&mut Transform, With<CameraZoom>>,
+    mut follow: ResMut<CameraFollow>,
+) {
+    let cursor = window
+        .single()
+        .ok()
+        .and_then(Window::cursor_position)
+        .filter(|_| input.pressed(Action::DragPan));
+    let previous = std::mem::replace(&mut *last_cursor, cursor);
+    let (Some(previous), Some(cursor), Ok(mut transform)) = (previous, cursor, camera.single_mut()) else { return; };
+    // the world sticks to the cursor
+    pan(&mut transform, &mut follow, previous - cursor);
+}
+
+fn key_pan(
+    input: ActionInput,
+    time: Res<Time<Real>>,
+    mut camera: Query<&mut Transform, With<CameraZoom>>,
+    mut follow: ResMut<CameraFollow>,
+) {
+    let direction: Vec2 = PAN_ACTIONS
+        .iter()
+        .filter(|(action, _)| input.pressed(*action))
+        .map(|(_, direction)| *direction)
+        .sum();
+    let Ok(mut transform) = camera.single_mut() else { return; };
+    pan(&mut transform, &mut follow, direction.normalize_or_zero() * PAN_SPEED * time.delta_secs());
+}
+
+fn edge_pan(
+    window: Query<&Window, With<PrimaryWindow>>,
+    buttons: Query<&Interaction>,
+    time: Res<Time<Real>>,
+    mut camera: Query<&mut Transform, With<CameraZoom>>,
+    mut follow: ResMut<CameraFollow>,
+) {
+    let Ok(window) = window.single() else { return; };
+    let Some(cursor) = window.cursor_position().filter(|_| window.focused) else { return; };
+    let size = window.size();
+    let edge = |position: f32, length: f32| {
+        if position < EDGE_MARGIN {
+            -1.0
+        } else if position > length - EDGE_MARGIN {
+            1.0
+        } else {
+            0.0
+        }
+    };
+    let direction = Vec2::new(edge(cursor.x, size.x), edge(cursor.y, size.y));
+    // HUD buttons sit at the edges too
+    if direction == Vec2::ZERO || buttons.iter().any(|interaction| *interaction != Interaction::None) {
+        return;
+    }
+    let Ok(mut transform) = camera.single_mut() else { return; };
+    pan(&mut transform, &mut follow, direction.normalize() * PAN_SPEED * time.delta_secs());
+}
+
+fn stick_pan(
+    gamepads: Query<&Gamepad>,
+    time: Res<Time<Real>>,
+    mut camera: Query<&mut Transform, With<CameraZoom>>,
+    mut follow: ResMut<CameraFollow>,
+) {
+    let Some(stick) = gamepads
+        .iter()
+        .map(Gamepad::right_stick)
+        .find(|stick| stick.length() > STICK_DEADZONE)
+    else {
+        return;
+    };
+    let Ok(mut transform) = camera.single_mut() else { return; };
+    // up on the stick is up on the screen
+    let direction = Vec2::new(stick.x, -stick.y).clamp_length_max(1.0);
+    pan(&mut transform, &mut follow, direction * PAN_SPEED * time.delta_secs());
+}
+
+fn camera_zoom(
+    mut scroll_evr: MessageReader<MouseWheel>,
+    window: Query<&Window, With<PrimaryWindow>>,
+    mut query: Query<&mut CameraZoom>,
+    follow: Res<CameraFollow>,
+    mut commands: Commands,
+    assets: Res<SolarSystemAssets>,
+    scroll_areas: widget::ScrollAreaHover,
+) {
+    // the wheel scrolls lists like the fleet list instead
+    if scroll_areas.is_hovered() {
+        scroll_evr.clear();
+        return;
+    }
+
+    let mut lines = 0.0;
+    let mut notched = false;
+    for ev in scroll_evr.read() {
+        lines += match ev.unit {
+            MouseScrollUnit::Line => {
+                notched = true;
+                ev.y
+            }
+            MouseScrollUnit::Pixel => ev.y / PIXELS_PER_LINE,
+        };
+    }
+    let Ok(mut camera_zoom) = query.single_mut() else { return; };
+    if lines == 0.0 {
+        return;
+    }
+
+    // zoom towards the cursor, unless the camera stays on the followed entity
+    let anchor = window.single().ok().and_then(Window::cursor_position).filter(|_| follow.0.is_none());
+    if camera_zoom.zoom_by(WHEEL_ZOOM.powf(-lines), anchor) {
+        // touchpads scroll every frame, only wheels click
+        if notched {
+            commands.spawn((
+                AudioPlayer::new(assets.warning_sound.clone()),
+                PlaybackSettings::DESPAWN,
+            ));
+        }
+        commands.trigger(CameraZoomed);
+    }
+}
+
+/// Zooms at the center while a zoom action is held
+fn action_zoom(
+    input: ActionInput,
+    time: Res<Time<Real>>,
+    mut query: Query<&mut CameraZoom>,
+    mut commands: Commands,
+) {
+    let direction = match (input.pressed(Action::ZoomIn), input.pressed(Action::ZoomOut)) {
+        (true, false) => -1.0,
+        (false, true) => 1.0,
+        _ => return,
+    };
+    let Ok(mut camera_zoom) = query.single_mut() else { return; };
+    if camera_zoom.zoom_by(ZOOM_RATE.powf(direction * time.delta_secs()), None) {
+        commands.trigger(CameraZoomed);
+    }
+}
+
+/// Two fingers pan and zoom, one finger is left to launching and selecting
+#[allow(deprecated)]
+fn camera_pinch_zoom(
+    mut pinch: ResMut<PinchZoomState>,
+    mut er_touch: EventReader<TouchInput>,
+    mut query: Query<(&mut Transform, &mut CameraZoom)>,
+    mut follow: ResMut<CameraFollow>,
+    mut commands: Commands,
+) {
+    for ev in er_touch.read() {
+        match ev.phase {
+            TouchPhase::Started | TouchPhase::Moved => {
+                pinch.active.insert(ev.id, ev.position);
+            }
+            TouchPhase::Ended | TouchPhase::Canceled => {
+                pinch.active.remove(&ev.id);
+            }
+        }
+    }
+
+    let mut touches = pinch.active.values();
+    let (Some(&p1), Some(&p2)) = (touches.next(), touches.next()) else {
+        pinch.previous = None;
+        return;
+    };
+    let center = p1.midpoint(p2);
+    let distance = p1.distance(p2);
+    let Some((previous_center, previous_distance)) = pinch.previous.replace((center, distance)) else { return; };
+    let Ok((mut transform, mut camera_zoom)) = query.single_mut() else { return; };
+
+    // while following, the fingers only zoom
+    let following = follow.0.is_some();
+    if !following {
+        pan(&mut transform, &mut follow, previous_center - center);
+    }
+    let anchor = (!following).then_some(center);
+    if distance > 0.0 && camera_zoom.zoom_by(previous_distance / distance, anchor) {
+        commands.trigger(CameraZoomed);
+    }
+}
+
+/// Eases the scale towards the zoom, keeping the world under the anchor in place
+fn apply_zoom(time: Res<Time<Real>>, mut query: Query<(&Camera, &mut Transform, &CameraZoom)>) {
+    let Ok((camera, mut transform, camera_zoom)) = query.single_mut() else { return; };
+    let scale = transform.scale.x;
+    if scale == camera_zoom.target {
+        return;
+    }
+    let t = 1.0 - (-ZOOM_SMOOTHING * time.delta_secs()).exp();
+    let mut new_scale = scale * (camera_zoom.target / scale).powf(t);
+    if (new_scale - camera_zoom.target).abs() < camera_zoom.target * 0.001 {
+        new_scale = camera_zoom.target;
+    }
+    if let (Some(anchor), Some(size)) = (camera_zoom.anchor, camera.logical_viewport_size()) {
+        let offset = anchor - size / 2.0;
+        transform.translation += (Vec2::new(offset.x, -offset.y) * (scale - new_scale)).extend(0.0);
+    }
+    transform.scale = Vec3::splat(new_scale);
+}
+
+fn request_follow(mut commands: Commands) {
+    commands.trigger(ToggleCameraFollow);
+}
+
+fn toggle_follow(
+    _: On<ToggleCameraFollow>,
+    mut follow: ResMut<CameraFollow>,
+    selected: Query<Entity, With<NavigationInstruments>>,
+    earth: Query<Entity, With<Earth>>,
+) {
+    let target = selected.iter().next().or_else(|| earth.single().ok());
+    // pressing again stops following, unless something else was selected in the meantime
+    follow.0 = if follow.0 == target { None } else { target };
+}
+
+fn follow_target(
+    time: Res<Time<Real>>,
+    mut follow: ResMut<CameraFollow>,
+    targets: Query<&GlobalTransform>,
+    mut camera: Query<&mut Transform, With<CameraZoom>>,
+) {
+    let Some(target) = follow.0 else { return; };
+    // e.g. the collector crashed
+    let Ok(target) = targets.get(target) else {
+        follow.0 = None;
+        return;
+    };
+    let Ok(mut transform) = camera.single_mut() else { return; };
+    let t = 1.0 - (-FOLLOW_SMOOTHING * time.delta_secs()).exp();
+    let position = transform.translation.xy().lerp(target.translation().xy(), t);
+    transform.translation.x = position.x;
+    transform.translation.y = position.y;
+}
+
+fn clamp_camera(mut camera: Query<&mut Transform, With<CameraZoom>>) {
+    let Ok(mut transform) = camera.single_mut() else { return; };
+    let position = transform.translation.xy();
+    if position.length() > CAMERA_BOUNDS {
+        let clamped = position.clamp_length_max(CAMERA_BOUNDS);
+        transform.translation.x = clamped.x;
+        transform.translation.y = clamped.y;
+    }
+}
@@ -0,0 +1,94 @@
+//! The campaign, a fixed sequence of missions. Missions are scenarios with their own objectives,
+//! winning one unlocks the next. Progress is stored across sessions.
+
+use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+
+use crate::asset_tracking::LoadResource;
+use crate::persistence;
+use crate::scenario::Scenario;
+use crate::screens::Screen;
+use crate::screens::gameover::GameEnd;
+
+const PROGRESS_KEY: &str = "campaign.ron";
+
+/// Missions in the order they are played
+const MISSION_FILES: &[&str] = &[
+    "campaign/01_first_light.scenario.ron",
+    "campaign/02_tight_budget.scenario.ron",
+    "campaign/03_debris_field.scenario.ron",
+    "campaign/04_stable_orbits.scenario.ron",
+    "campaign/05_type_two.scenario.ron",
+];
+
+pub(super) fn plugin(app: &mut App) {
+    app.load_resource::<CampaignAssets>();
+    app.init_resource::<CampaignProgress>();
+    app.init_resource::<ActiveMission>();
+    app.add_systems(OnEnter(Screen::Gameover), record_mission_result);
+}
+
+#[derive(Resource, Asset, Reflect, Clone)]
+#[reflect(Resource)]
+pub struct CampaignAssets {
+    #[dependency]
+    pub missions: Vec<Handle<Scenario>>,
+}
+
+impl FromWorld for CampaignAssets {
+    fn from_world(world: &mut World) -> Self {
+        let assets = world.resource::<AssetServer>();
+        Self {
+            missions: MISSION_FILES.iter().map(|path| assets.load(*path)).collect(),
+        }
+    }
+}
+
+/// How far the player got in the campaign
+#[derive(Resource, Debug, Clone, Serialize, Deserialize)]
+pub struct CampaignProgress {
+    /// Number of missions completed, the mission with this index is the next one to play
+    pub completed: usize,
+}
+
+impl FromWorld for CampaignProgress {
+    fn from_world(_world: &mut World) -> Self {
+        persistence::read(PROGRESS_KEY)
+            .and_then(|contents| ron::from_str(&contents).ok())
+            .unwrap_or(Self { completed: 0 })
+    }
+}
+
+impl CampaignProgress {
+    pub fn is_unlocked(&self, mission: usize) -> bool {
+        mission <= self.completed
+    }
+}
+
+/// Index of the mission the current run plays, `None` outside of the campaign
+#[derive(Resource, Default, Debug, Clone, Copy)]
+pub struct ActiveMission(pub Option<usize>);
+
+fn record_mission_result(
+    active: Res<ActiveMission>,
+    game_end: Res<GameEnd>,
+    mut progress: ResMut<CampaignProgress>,
+) {
+    let Some(mission) = active.0 else { return; };
+    if !game_end.won || progress.completed > mission {
+        return;
+    }
+
+    progress.completed = mission + 1;
+    info!("Mission {} completed, unlocked mission {}", mission + 1, mission + 2);
+    let contents = match ron::ser::to_string(&*progress) {
+        Ok(contents) => contents,
+        Err(e) => {
+            error!("Could not serialize campaign progress: {e}");
+            return;
+        }
+    };
+    if let Err(e) = persistence::write(PROGRESS_KEY, &contents) {
+        error!("Could not write campaign progress: {e}");
+    }
+}
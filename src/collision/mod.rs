@@ -1,22 +1,43 @@
 use crate::dev_tools::is_debug_enabled;
 use crate::physics::calc_gravity::{Attractee, Attractor};
+use crate::screens::InitGameResource;
 use crate::sun_system::{Level, SolarSystemAssets};
-use crate::{AppSystems, GameplaySystem};
+use crate::{AppSystems, GameplaySystem, PausableSystems};
 use bevy::color::palettes::basic::BLUE;
 use bevy::prelude::*;
 use std::collections::HashSet;
 
 pub(super) fn plugin(app: &mut App) {
+    app.add_systems(
+        FixedUpdate,
+        check_for_collisions
+            .run_if(|enabled: Res<CollisionsEnabled>| enabled.0)
+            .in_set(AppSystems::Update)
+            .in_set(GameplaySystem)
+            .in_set(PausableSystems),
+    );
     app.add_systems(
         Update,
-        (check_for_collisions, draw_hitboxes.run_if(is_debug_enabled))
+        draw_hitboxes
+            .run_if(is_debug_enabled)
             .in_set(AppSystems::Update)
             .in_set(GameplaySystem),
     );
+    app.init_game_resource::<CollisionsEnabled>();
     app.add_observer(handle_fatal_collision_event);
     app.add_observer(handle_demote_collision_event);
 }
 
+/// Whether objects collide at all, can be turned off in the sandbox
+#[derive(Resource, Debug)]
+pub struct CollisionsEnabled(pub bool);
+
+impl Default for CollisionsEnabled {
+    fn default() -> Self {
+        Self(true)
+    }
+}
+
 #[derive(Component, Copy, Clone, Debug, PartialEq, Default)]
 pub struct HitBox {
     pub radius: f32,
@@ -28,16 +49,9 @@ pub struct FatalCollisionEvent {
     pub other: Entity,
 }
 
-#[derive(Event)]
-pub struct GeneralCollisionEvent {
-    pub destroyed: Entity,
-    pub other: Entity,
-}
-
 #[derive(Event)]
 pub struct DemoteCollisionEvent {
     pub demoted: Entity,
-    pub other: Entity,
 }
 
 
@@ -59,12 +73,12 @@ pub fn is_colliding(
 
 fn check_for_collisions(
     mut commands: Commands,
-    hitboxes: Query<(Entity, &Transform, &HitBox, Has<Attractor>, Has<Attractee>, &Level)>,
+    hitboxes: Query<(Entity, &Transform, &HitBox, Has<Attractor>, &Level)>,
 ) {
     // Track entities we already decided to destroy this system run to avoid duplicate events
     let mut destroyed_in_this_system: HashSet<Entity> = HashSet::new();
-    for (entity, entity_transform, hitbox1, isAttractor, isAttractee, level1) in hitboxes.iter() {
-        for (entity_check, check_transform, hitbox2, isAttractor2, isAttractee2, level2) in hitboxes.iter()
+    for (entity, entity_transform, hitbox1, is_attractor, level1) in hitboxes.iter() {
+        for (entity_check, check_transform, hitbox2, is_attractor2, level2) in hitboxes.iter()
         {
             if entity == entity_check {
                 // no need to check collisions with self
@@ -80,7 +94,7 @@ fn check_for_collisions(
             if distance < (hitbox1.radius + hitbox2.radius) {
                 info!("crash");
 
-                if isAttractor {
+                if is_attractor {
                     info!("crash sun case");
                     // first sun, sun has level 0
                     if !destroyed_in_this_system.contains(&entity_check) {
@@ -90,7 +104,7 @@ fn check_for_collisions(
                         });
                         destroyed_in_this_system.insert(entity_check);
                     }
-                } else if isAttractor2 {
+                } else if is_attractor2 {
                 } else {
                     info!("crash Satellites");
 
@@ -106,7 +120,6 @@ fn check_for_collisions(
                     }else {
                         commands.trigger(DemoteCollisionEvent {
                             demoted: entity,
-                            other: entity_check,
                         });
                     }
                     // satellite 2
@@ -121,7 +134,6 @@ fn check_for_collisions(
                     }else {
                         commands.trigger(DemoteCollisionEvent {
                             demoted: entity_check,
-                            other: entity,
                         });
 
                     }
@@ -162,11 +174,11 @@ fn handle_fatal_collision_event(event: On<FatalCollisionEvent>, mut commands: Co
 }
 
 
-/**
+/*
 collectors have hp, output, level
 lower hp decreases output
 fornow collectors die when colliding with each other
-**/
+*/
 
 fn draw_hitboxes(mut gizmos: Gizmos, query: Query<(&Transform, &HitBox)>) {
     query.iter().for_each(|(i_trans, i_hitbox)| {
@@ -0,0 +1,347 @@
+//! Input actions. Gameplay systems ask for an [`Action`] instead of a key or mouse button, so
+//! every control can be rebound in the controls menu. Bindings are remembered between sessions.
+//! Actions also have a fixed gamepad button, prompts name it while a gamepad is in use.
+
+use bevy::ecs::system::SystemParam;
+use bevy::input::mouse::AccumulatedMouseMotion;
+use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+use crate::locale::LocaleArg;
+use crate::persistence;
+
+/// Stores the bindings
+const CONTROLS_KEY: &str = "controls.ron";
+/// Sticks tilted less than this are at rest
+pub const STICK_DEADZONE: f32 = 0.2;
+
+pub(super) fn plugin(app: &mut App) {
+    app.insert_resource(InputBindings::load());
+    app.init_resource::<InputDevice>();
+    app.add_systems(Update, detect_input_device);
+}
+
+/// Something the player can do with a key or mouse button
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum Action {
+    /// Hold to charge and release to launch a collector towards the cursor
+    Launch,
+    /// Switch the thrusters when pressed and again when released
+    Thrust,
+    CycleViz,
+    SpeedUp,
+    SlowDown,
+    SeekBack,
+    SeekForward,
+    ZoomIn,
+    ZoomOut,
+    SelectNext,
+    SelectPrevious,
+    ToggleFleet,
+    FollowCamera,
+    PanUp,
+    PanLeft,
+    PanDown,
+    PanRight,
+    /// Hold and move the mouse to pan
+    DragPan,
+    /// Drag to place a collector, only in the sandbox
+    SandboxSpawn,
+    /// Toggle the UI debug overlay, only in dev builds
+    DebugUi,
+}
+
+impl Action {
+    pub const ALL: [Action; 20] = [
+        Action::Launch,
+        Action::Thrust,
+        Action::CycleViz,
+        Action::SpeedUp,
+        Action::SlowDown,
+        Action::SeekBack,
+        Action::SeekForward,
+        Action::ZoomIn,
+        Action::ZoomOut,
+        Action::SelectNext,
+        Action::SelectPrevious,
+        Action::ToggleFleet,
+        Action::FollowCamera,
+        Action::PanUp,
+        Action::PanLeft,
+        Action::PanDown,
+        Action::PanRight,
+        Action::DragPan,
+        Action::SandboxSpawn,
+        Action::DebugUi,
+    ];
+
+    fn default_binding(self) -> Binding {
+        match self {
+            Action::Launch => Binding::Mouse(MouseButton::Left),
+            Action::Thrust => Binding::Key(KeyCode::Space),
+            Action::CycleViz => Binding::Key(KeyCode::KeyU),
+            Action::SpeedUp => Binding::Key(KeyCode::ArrowUp),
+            Action::SlowDown => Binding::Key(KeyCode::ArrowDown),
+            Action::SeekBack => Binding::Key(KeyCode::ArrowLeft),
+            Action::SeekForward => Binding::Key(KeyCode::ArrowRight),
+            Action::ZoomIn => Binding::Key(KeyCode::Equal),
+            Action::ZoomOut => Binding::Key(KeyCode::Minus),
+            Action::SelectNext => Binding::Key(KeyCode::KeyE),
+            Action::SelectPrevious => Binding::Key(KeyCode::KeyQ),
+            Action::ToggleFleet => Binding::Key(KeyCode::KeyF),
+            Action::FollowCamera => Binding::Key(KeyCode::KeyC),
+            Action::PanUp => Binding::Key(KeyCode::KeyW),
+            Action::PanLeft => Binding::Key(KeyCode::KeyA),
+            Action::PanDown => Binding::Key(KeyCode::KeyS),
+            Action::PanRight => Binding::Key(KeyCode::KeyD),
+            Action::DragPan => Binding::Mouse(MouseButton::Middle),
+            Action::SandboxSpawn => Binding::Mouse(MouseButton::Right),
+            Action::DebugUi => Binding::Key(KeyCode::Backquote),
+        }
+    }
+
+    /// Button of the action on gamepads, these can't be rebound. The sticks aim and pan.
+    pub fn gamepad_button(self) -> Option<GamepadButton> {
+        match self {
+            Action::Launch => Some(GamepadButton::RightTrigger2),
+            Action::Thrust => Some(GamepadButton::South),
+            Action::CycleViz => Some(GamepadButton::Select),
+            Action::SpeedUp => Some(GamepadButton::DPadUp),
+            Action::SlowDown => Some(GamepadButton::DPadDown),
+            Action::ZoomIn => Some(GamepadButton::DPadRight),
+            Action::ZoomOut => Some(GamepadButton::DPadLeft),
+            Action::SelectNext => Some(GamepadButton::RightTrigger),
+            Action::SelectPrevious => Some(GamepadButton::LeftTrigger),
+            Action::ToggleFleet => Some(GamepadButton::North),
+            Action::FollowCamera => Some(GamepadButton::West),
+            _ => None,
+        }
+    }
+
+    /// Whether the action does anything in this build
+    pub fn is_available(self) -> bool {
+        self != Action::DebugUi || cfg!(feature = "dev")
+    }
+
+    /// Key of the action name in the string tables
+    pub fn name_key(self) -> &'static str {
+        match self {
+            Action::Launch => "controls.launch",
+            Action::Thrust => "controls.thrust",
+            Action::CycleViz => "controls.cycle_viz",
+            Action::SpeedUp => "controls.speed_up",
+            Action::SlowDown => "controls.slow_down",
+            Action::SeekBack => "controls.seek_back",
+            Action::SeekForward => "controls.seek_forward",
+            Action::ZoomIn => "controls.zoom_in",
+            Action::ZoomOut => "controls.zoom_out",
+            Action::SelectNext => "controls.select_next",
+            Action::SelectPrevious => "controls.select_previous",
+            Action::ToggleFleet => "controls.toggle_fleet",
+            Action::FollowCamera => "controls.follow_camera",
+            Action::PanUp => "controls.pan_up",
+            Action::PanLeft => "controls.pan_left",
+            Action::PanDown => "controls.pan_down",
+            Action::PanRight => "controls.pan_right",
+            Action::DragPan => "controls.drag_pan",
+            Action::SandboxSpawn => "controls.sandbox_spawn",
+            Action::DebugUi => "controls.debug_ui",
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum Binding {
+    Key(KeyCode),
+    Mouse(MouseButton),
+}
+
+impl Binding {
+    /// Name of the key or button for labels and instructions
+    pub fn label(self) -> LocaleArg {
+        match self {
+            Binding::Key(key) => {
+                let name = format!("{key:?}");
+                let name = name.strip_prefix("Key").or_else(|| name.strip_prefix("Digit")).unwrap_or(&name);
+                LocaleArg::from(name.to_uppercase())
+            }
+            Binding::Mouse(MouseButton::Left) => LocaleArg::key("controls.mouse_left"),
+            Binding::Mouse(MouseButton::Right) => LocaleArg::key("controls.mouse_right"),
+            Binding::Mouse(MouseButton::Middle) => LocaleArg::key("controls.mouse_middle"),
+            Binding::Mouse(button) => LocaleArg::from(format!("{button:?}").to_uppercase()),
+        }
+    }
+}
+
+/// Name of a gamepad button in prompts, as printed on common controllers
+fn gamepad_label(button: GamepadButton) -> LocaleArg {
+    let name = match button {
+        GamepadButton::South => "A",
+        GamepadButton::East => "B",
+        GamepadButton::North => "Y",
+        GamepadButton::West => "X",
+        GamepadButton::LeftTrigger => "LB",
+        GamepadButton::RightTrigger => "RB",
+        GamepadButton::LeftTrigger2 => "LT",
+        GamepadButton::RightTrigger2 => "RT",
+        GamepadButton::Select => "VIEW",
+        GamepadButton::Start => "MENU",
+        GamepadButton::DPadUp => "D-PAD UP",
+        GamepadButton::DPadDown => "D-PAD DOWN",
+        GamepadButton::DPadLeft => "D-PAD LEFT",
+        GamepadButton::DPadRight => "D-PAD RIGHT",
+        other => return LocaleArg::from(format!("{other:?}").to_uppercase()),
+    };
+    LocaleArg::from(name)
+}
+
+/// What the player used last
+#[derive(Resource, Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum InputDevice {
+    #[default]
+    KeyboardMouse,
+    Gamepad,
+}
+
+/// The binding of every action
+#[derive(Resource, Debug, Clone, PartialEq)]
+pub struct InputBindings(HashMap<Action, Binding>);
+
+impl Default for InputBindings {
+    fn default() -> Self {
+        Self(Action::ALL.iter().map(|action| (*action, action.default_binding())).collect())
+    }
+}
+
+impl InputBindings {
+    /// The bindings saved in an earlier session, actions added since keep their default
+    fn load() -> Self {
+        let mut bindings = Self::default();
+        let saved = persistence::read(CONTROLS_KEY)
+            .and_then(|contents| ron::from_str::<HashMap<Action, Binding>>(&contents).ok());
+        bindings.0.extend(saved.into_iter().flatten());
+        bindings
+    }
+
+    pub fn get(&self, action: Action) -> Binding {
+        self.0.get(&action).copied().unwrap_or_else(|| action.default_binding())
+    }
+
+    /// Name of what triggers `action` on `device`, for instructions
+    pub fn prompt(&self, action: Action, device: InputDevice) -> LocaleArg {
+        match (device, action.gamepad_button()) {
+            (InputDevice::Gamepad, Some(button)) => gamepad_label(button),
+            _ => self.get(action).label(),
+        }
+    }
+
+    /// Binds `binding` to `action`, an action it was bound to gets the old binding of `action`
+    pub fn rebind(&mut self, action: Action, binding: Binding) {
+        let old = self.get(action);
+        for other in self.0.values_mut() {
+            if *other == binding {
+                *other = old;
+            }
+        }
+        self.0.insert(action, binding);
+    }
+
+    /// Remembers the bindings for the next session
+    pub fn save(&self) {
+        let contents = match ron::ser::to_string(&self.0) {
+            Ok(contents) => contents,
+            Err(e) => {
+                error!("Could not serialize controls: {e}");
+                return;
+            }
+        };
+        if let Err(e) = persistence::write(CONTROLS_KEY, &contents) {
+            warn!("Could not save controls: {e}");
+        }
+    }
+}
+
+/// State of the actions, looked up through their bindings and on every gamepad
+#[derive(SystemParam)]
+pub struct ActionInput<'w, 's> {
+    bindings: Res<'w, InputBindings>,
+    keys: Res<'w, ButtonInput<KeyCode>>,
+    mouse: Res<'w, ButtonInput<MouseButton>>,
+    gamepads: Query<'w, 's, &'static Gamepad>,
+}
+
+impl ActionInput<'_, '_> {
+    pub fn pressed(&self, action: Action) -> bool {
+        let bound = match self.bindings.get(action) {
+            Binding::Key(key) => self.keys.pressed(key),
+            Binding::Mouse(button) => self.mouse.pressed(button),
+        };
+        bound || self.on_gamepad(action, Gamepad::pressed)
+    }
+
+    pub fn just_pressed(&self, action: Action) -> bool {
+        let bound = match self.bindings.get(action) {
+            Binding::Key(key) => self.keys.just_pressed(key),
+            Binding::Mouse(button) => self.mouse.just_pressed(button),
+        };
+        bound || self.on_gamepad(action, Gamepad::just_pressed)
+    }
+
+    pub fn just_released(&self, action: Action) -> bool {
+        let bound = match self.bindings.get(action) {
+            Binding::Key(key) => self.keys.just_released(key),
+            Binding::Mouse(button) => self.mouse.just_released(button),
+        };
+        bound || self.on_gamepad(action, Gamepad::just_released)
+    }
+
+    fn on_gamepad(&self, action: Action, check: impl Fn(&Gamepad, GamepadButton) -> bool) -> bool {
+        let Some(button) = action.gamepad_button() else { return false; };
+        self.gamepads.iter().any(|gamepad| check(gamepad, button))
+    }
+}
+
+/// Run condition like [`input_just_pressed`](bevy::input::common_conditions::input_just_pressed)
+/// for an action
+pub fn action_just_pressed(action: Action) -> impl FnMut(ActionInput) -> bool + Clone {
+    move |input: ActionInput| input.just_pressed(action)
+}
+
+/// Run condition like [`input_just_released`](bevy::input::common_conditions::input_just_released)
+/// for an action
+pub fn action_just_released(action: Action) -> impl FnMut(ActionInput) -> bool + Clone {
+    move |input: ActionInput| input.just_released(action)
+}
+
+/// Run condition for a button on any gamepad, for the few controls that aren't actions
+pub fn gamepad_just_pressed(button: GamepadButton) -> impl FnMut(Query<&Gamepad>) -> bool + Clone {
+    move |gamepads: Query<&Gamepad>| gamepads.iter().any(|gamepad| gamepad.just_pressed(button))
+}
+
+pub fn gamepad_in_use(device: Res<InputDevice>) -> bool {
+    *device == InputDevice::Gamepad
+}
+
+/// Switches prompts to the device that was touched last
+fn detect_input_device(
+    mut device: ResMut<InputDevice>,
+    keys: Res<ButtonInput<KeyCode>>,
+    mouse: Res<ButtonInput<MouseButton>>,
+    motion: Res<AccumulatedMouseMotion>,
+    gamepads: Query<&Gamepad>,
+) {
+    let gamepad_used = gamepads.iter().any(|gamepad| {
+        gamepad.get_just_pressed().next().is_some()
+            || gamepad.left_stick().length() > STICK_DEADZONE
+            || gamepad.right_stick().length() > STICK_DEADZONE
+    });
+    let keyboard_mouse_used = keys.get_just_pressed().next().is_some()
+        || mouse.get_just_pressed().next().is_some()
+        || motion.delta != Vec2::ZERO;
+    if gamepad_used {
+        device.set_if_neq(InputDevice::Gamepad);
+    } else if keyboard_mouse_used {
+        device.set_if_neq(InputDevice::KeyboardMouse);
+    }
+}
@@ -1,14 +1,12 @@
 //! Development tools for the game. This plugin is only enabled in dev builds.
 
-use crate::Pause;
+use crate::controls::{Action, action_just_pressed};
 use crate::screens::Screen;
 use bevy::{
-    dev_tools::states::log_transitions, input::common_conditions::input_just_pressed, prelude::*, 
+    dev_tools::states::log_transitions, prelude::*, 
 };
 //use crate::screens::Screen;
 
-const TOGGLE_KEY: KeyCode = KeyCode::KeyD;
-
 pub(super) fn plugin(app: &mut App) {
     app.add_systems(Update, log_transitions::<Screen>);
 
@@ -17,27 +15,14 @@ pub(super) fn plugin(app: &mut App) {
         Update,
         toggle_debug_ui
             .run_if(in_state(Screen::Gameplay))
-            .run_if(input_just_pressed(TOGGLE_KEY)),
+            .run_if(action_just_pressed(Action::DebugUi)),
     );
 }
 
 pub fn is_debug_enabled(options: Res<UiDebugOptions>) -> bool {
-    return options.enabled
+    options.enabled
 }
 
 fn toggle_debug_ui(mut options: ResMut<UiDebugOptions>) {
     options.toggle();
 }
-
-fn toggle_pause(current_pause: Res<State<Pause>>, mut next_pause: ResMut<NextState<Pause>>) {
-    match current_pause.0 {
-        true => {
-            debug!("Pausing game");
-            next_pause.set(Pause(false))
-        }
-        false => {
-            debug!("Unpausing game");
-            next_pause.set(Pause(true))
-        }
-    }
-}
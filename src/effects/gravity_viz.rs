@@ -1,14 +1,18 @@
-use bevy::input::common_conditions::input_just_pressed;
 use bevy::prelude::*;
 use bevy::window::PrimaryWindow;
-use crate::GameplaySystem;
+use crate::{GameplaySystem, PausableSystems};
+use crate::controls::{Action, action_just_pressed};
 use crate::sun_system::Sun;
+use crate::theme::{HudTheme, ThemeColor};
 
 pub(super) fn plugin(app: &mut App) {
     app.init_resource::<GravityViz>();
     app.add_systems(
         Update,
-        cycle_mode.run_if(input_just_pressed(KeyCode::KeyU)).in_set(GameplaySystem),
+        cycle_mode
+            .run_if(action_just_pressed(Action::CycleViz))
+            .in_set(GameplaySystem)
+            .in_set(PausableSystems),
     );
     app.add_systems(Update, draw_viz.in_set(GameplaySystem));
 }
@@ -71,8 +75,10 @@ fn draw_viz(
     sun_q: Query<&GlobalTransform, With<Sun>>,
     cam_q: Query<(&Camera, &GlobalTransform, &Transform)>,
     window_q: Query<&Window, With<PrimaryWindow>>,
+    theme: Res<HudTheme>,
 ) {
     if viz.mode == VizMode::Off { return; }
+    let field = theme.color(ThemeColor::Field);
     let Some(gt) = sun_q.iter().next() else { return; };
     let center = gt.translation().xy();
 
@@ -87,41 +93,41 @@ fn draw_viz(
     let end = view_center + Vec2::new(half_w, half_h);
 
     match viz.mode {
-        VizMode::Equipotential => draw_iso_potentials(&mut gizmos, center, end),
-        VizMode::VectorField => draw_vector_field(&mut gizmos, center, start, end),
-        VizMode::GravityWell => draw_well_grid(&mut gizmos, center, end),
-        VizMode::SlingshotCues => draw_slingshot_cues(&mut gizmos, center),
-        VizMode::Streamlines => draw_streamlines(&mut gizmos, center, start, end),
-        VizMode::ParticleFlow => draw_particle_flow(&mut gizmos, center, start, end),
-        VizMode::LensingGrid => draw_lensing_grid(&mut gizmos, center, start, end),
-        VizMode::Heatmap => draw_heatmap(&mut gizmos, center, end),
-        VizMode::RadialTicks => draw_radial_ticks(&mut gizmos, center, end),
-        VizMode::Scanlines => draw_scanlines(&mut gizmos, start, end),
-        VizMode::Hatches => draw_hatches(&mut gizmos, start, end),
-        VizMode::Spirals => draw_spirals(&mut gizmos, center, end),
-        VizMode::PolarRulers => draw_polar_rulers(&mut gizmos, center, end),
-        VizMode::Isochrones => draw_isochrones(&mut gizmos, center, end),
-        VizMode::LeifDotted => draw_dotted_iso_potentials(&mut gizmos, center, end),
+        VizMode::Equipotential => draw_iso_potentials(&mut gizmos, field, center, end),
+        VizMode::VectorField => draw_vector_field(&mut gizmos, field, center, start, end),
+        VizMode::GravityWell => draw_well_grid(&mut gizmos, field, center, end),
+        VizMode::SlingshotCues => draw_slingshot_cues(&mut gizmos, field, center),
+        VizMode::Streamlines => draw_streamlines(&mut gizmos, field, center, start, end),
+        VizMode::ParticleFlow => draw_particle_flow(&mut gizmos, field, center, start, end),
+        VizMode::LensingGrid => draw_lensing_grid(&mut gizmos, field, center, start, end),
+        VizMode::Heatmap => draw_heatmap(&mut gizmos, theme.color(ThemeColor::Highlight), center, end),
+        VizMode::RadialTicks => draw_radial_ticks(&mut gizmos, field, center, end),
+        VizMode::Scanlines => draw_scanlines(&mut gizmos, field, start, end),
+        VizMode::Hatches => draw_hatches(&mut gizmos, field, start, end),
+        VizMode::Spirals => draw_spirals(&mut gizmos, field, center, end),
+        VizMode::PolarRulers => draw_polar_rulers(&mut gizmos, field, center, end),
+        VizMode::Isochrones => draw_isochrones(&mut gizmos, field, center, end),
+        VizMode::LeifDotted => draw_dotted_iso_potentials(&mut gizmos, field, center, end),
         VizMode::Off => {}
     }
 }
 
-fn draw_iso_potentials(gizmos: &mut Gizmos, center: Vec2, end: Vec2) {
+fn draw_iso_potentials(gizmos: &mut Gizmos, field: Color, center: Vec2, end: Vec2) {
     let iso = Isometry2d::from_translation(center);
     // Expand circles to fill current view
     let max_r = center.distance(end) * 1.1;
     let mut r = 16.0;
     while r <= max_r {
         let alpha = (1.0 / (1.0 + r * 0.04)).min(0.6);
-        let color = Color::srgb(0.9, 0.8, 0.4).with_alpha(alpha * 0.4);
+        let color = field.with_alpha(alpha * 0.4);
         gizmos.circle_2d(iso, r, color);
         r += 24.0;
     }
 }
 
-fn draw_vector_field(gizmos: &mut Gizmos, center: Vec2, start: Vec2, end: Vec2) {
+fn draw_vector_field(gizmos: &mut Gizmos, field: Color, center: Vec2, start: Vec2, end: Vec2) {
     let step = 64.0;
-    let color = Color::srgb(0.6, 0.9, 0.6).with_alpha(0.55);
+    let color = field.with_alpha(0.55);
     let mut y = start.y;
     while y <= end.y {
         let mut x = start.x;
@@ -138,7 +144,7 @@ fn draw_vector_field(gizmos: &mut Gizmos, center: Vec2, start: Vec2, end: Vec2)
     }
 }
 
-fn draw_well_grid(gizmos: &mut Gizmos, center: Vec2, end: Vec2) {
+fn draw_well_grid(gizmos: &mut Gizmos, field: Color, center: Vec2, end: Vec2) {
     // Radial lines
     let spokes = 24;
     let radius = center.distance(end) * 1.1;
@@ -147,12 +153,12 @@ fn draw_well_grid(gizmos: &mut Gizmos, center: Vec2, end: Vec2) {
         let dir = Vec2::from_angle(a);
         let p0 = center + dir * 16.0;
         let p1 = center + dir * radius;
-        let color = Color::srgb(0.3, 0.9, 0.9).with_alpha(0.25);
+        let color = field.with_alpha(0.25);
         gizmos.line_2d(p0, p1, color);
     }
     // Circular bands
     let mut r = 24.0;
-    let color = Color::srgb(0.3, 0.9, 0.9).with_alpha(0.18);
+    let color = field.with_alpha(0.18);
     let iso = Isometry2d::from_translation(center);
     while r <= radius {
         gizmos.circle_2d(iso, r, color);
@@ -160,17 +166,17 @@ fn draw_well_grid(gizmos: &mut Gizmos, center: Vec2, end: Vec2) {
     }
 }
 
-fn draw_slingshot_cues(gizmos: &mut Gizmos, center: Vec2) {
+fn draw_slingshot_cues(gizmos: &mut Gizmos, field: Color, center: Vec2) {
     // Minimal: a faint ring at the sun to suggest curvature intensity
     let iso = Isometry2d::from_translation(center);
-    let color = Color::srgb(0.2, 0.8, 1.0).with_alpha(0.2);
+    let color = field.with_alpha(0.2);
     gizmos.circle_2d(iso, 34.0, color);
 }
 
-fn draw_streamlines(gizmos: &mut Gizmos, center: Vec2, start: Vec2, end: Vec2) {
+fn draw_streamlines(gizmos: &mut Gizmos, field: Color, center: Vec2, start: Vec2, end: Vec2) {
     let step = 72.0;
     let h = 10;
-    let color = Color::srgb(0.7, 0.9, 1.0).with_alpha(0.36);
+    let color = field.with_alpha(0.36);
     let mut y = start.y;
     while y <= end.y {
         let mut x = start.x;
@@ -190,8 +196,8 @@ fn draw_streamlines(gizmos: &mut Gizmos, center: Vec2, start: Vec2, end: Vec2) {
     }
 }
 
-fn draw_particle_flow(gizmos: &mut Gizmos, center: Vec2, start: Vec2, end: Vec2) {
-    let color = Color::srgb(0.5, 0.9, 1.0).with_alpha(0.25);
+fn draw_particle_flow(gizmos: &mut Gizmos, field: Color, center: Vec2, start: Vec2, end: Vec2) {
+    let color = field.with_alpha(0.25);
     let mut y = start.y;
     while y <= end.y {
         let mut x = start.x;
@@ -206,8 +212,8 @@ fn draw_particle_flow(gizmos: &mut Gizmos, center: Vec2, start: Vec2, end: Vec2)
     }
 }
 
-fn draw_lensing_grid(gizmos: &mut Gizmos, center: Vec2, start: Vec2, end: Vec2) {
-    let color = Color::srgb(0.6, 0.9, 1.0).with_alpha(0.25);
+fn draw_lensing_grid(gizmos: &mut Gizmos, field: Color, center: Vec2, start: Vec2, end: Vec2) {
+    let color = field.with_alpha(0.25);
     // Vertical curves
     let mut x = start.x;
     while x <= end.x {
@@ -244,19 +250,19 @@ fn draw_lensing_grid(gizmos: &mut Gizmos, center: Vec2, start: Vec2, end: Vec2)
     }
 }
 
-fn draw_heatmap(gizmos: &mut Gizmos, center: Vec2, end: Vec2) {
+fn draw_heatmap(gizmos: &mut Gizmos, warm: Color, center: Vec2, end: Vec2) {
     let iso = Isometry2d::from_translation(center);
     let max_r = center.distance(end) * 1.2;
     let mut r = 12.0;
     while r <= max_r {
         let a = (1.0 / (1.0 + r * 0.02)).clamp(0.05, 0.35);
-        let color = Color::srgb(1.0, 0.5, 0.2).with_alpha(a);
+        let color = warm.with_alpha(a);
         gizmos.circle_2d(iso, r, color);
         r += 8.0;
     }
 }
 
-fn draw_radial_ticks(gizmos: &mut Gizmos, center: Vec2, end: Vec2) {
+fn draw_radial_ticks(gizmos: &mut Gizmos, field: Color, center: Vec2, end: Vec2) {
     let max_r = center.distance(end) * 1.1;
     let spokes = 36;
     for s in 0..spokes {
@@ -266,14 +272,14 @@ fn draw_radial_ticks(gizmos: &mut Gizmos, center: Vec2, end: Vec2) {
         while r <= max_r {
             let p = center + dir * r;
             let t = Vec2::new(-dir.y, dir.x);
-            gizmos.line_2d(p - t * 2.0, p + t * 2.0, Color::srgb(0.9, 0.8, 0.4).with_alpha(0.4));
+            gizmos.line_2d(p - t * 2.0, p + t * 2.0, field.with_alpha(0.4));
             r += 24.0;
         }
     }
 }
 
-fn draw_scanlines(gizmos: &mut Gizmos, start: Vec2, end: Vec2) {
-    let color = Color::srgb(0.2, 0.8, 1.0).with_alpha(0.10);
+fn draw_scanlines(gizmos: &mut Gizmos, field: Color, start: Vec2, end: Vec2) {
+    let color = field.with_alpha(0.10);
     let mut y = start.y;
     while y <= end.y {
         gizmos.line_2d(Vec2::new(start.x, y), Vec2::new(end.x, y), color);
@@ -281,8 +287,8 @@ fn draw_scanlines(gizmos: &mut Gizmos, start: Vec2, end: Vec2) {
     }
 }
 
-fn draw_hatches(gizmos: &mut Gizmos, start: Vec2, end: Vec2) {
-    let color = Color::srgb(0.9, 0.8, 0.4).with_alpha(0.12);
+fn draw_hatches(gizmos: &mut Gizmos, field: Color, start: Vec2, end: Vec2) {
+    let color = field.with_alpha(0.12);
     let diag = (end - start).length();
     let mut t = -diag;
     while t <= diag {
@@ -293,8 +299,8 @@ fn draw_hatches(gizmos: &mut Gizmos, start: Vec2, end: Vec2) {
     }
 }
 
-fn draw_spirals(gizmos: &mut Gizmos, center: Vec2, end: Vec2) {
-    let color = Color::srgb(0.2, 0.8, 1.0).with_alpha(0.35);
+fn draw_spirals(gizmos: &mut Gizmos, field: Color, center: Vec2, end: Vec2) {
+    let color = field.with_alpha(0.35);
     let max_r = center.distance(end) * 1.0;
     let arms = 3;
     for k in 0..arms {
@@ -311,28 +317,28 @@ fn draw_spirals(gizmos: &mut Gizmos, center: Vec2, end: Vec2) {
     }
 }
 
-fn draw_polar_rulers(gizmos: &mut Gizmos, center: Vec2, end: Vec2) {
+fn draw_polar_rulers(gizmos: &mut Gizmos, field: Color, center: Vec2, end: Vec2) {
     let iso = Isometry2d::from_translation(center);
     let max_r = center.distance(end) * 1.1;
     let mut r = 20.0;
     while r <= max_r {
-        gizmos.circle_2d(iso, r, Color::srgb(0.6, 0.9, 1.0).with_alpha(0.18));
+        gizmos.circle_2d(iso, r, field.with_alpha(0.18));
         r += 20.0;
     }
     let spokes = 24;
     for i in 0..spokes {
         let a = i as f32 / spokes as f32 * std::f32::consts::TAU;
         let dir = Vec2::from_angle(a);
-        gizmos.line_2d(center, center + dir * max_r, Color::srgb(0.6, 0.9, 1.0).with_alpha(0.08));
+        gizmos.line_2d(center, center + dir * max_r, field.with_alpha(0.08));
     }
 }
 
-fn draw_isochrones(gizmos: &mut Gizmos, center: Vec2, end: Vec2) {
+fn draw_isochrones(gizmos: &mut Gizmos, field: Color, center: Vec2, end: Vec2) {
     let iso = Isometry2d::from_translation(center);
     let max_r = center.distance(end) * 1.2;
     let mut r = 28.0;
     while r <= max_r {
-        gizmos.circle_2d(iso, r, Color::srgb(0.9, 0.8, 0.4).with_alpha(0.22));
+        gizmos.circle_2d(iso, r, field.with_alpha(0.22));
         r *= 1.12;
     }
 }
@@ -340,7 +346,7 @@ fn draw_isochrones(gizmos: &mut Gizmos, center: Vec2, end: Vec2) {
 
 
 
-fn draw_dotted_iso_potentials(gizmos: &mut Gizmos, center: Vec2, end: Vec2) {
+fn draw_dotted_iso_potentials(gizmos: &mut Gizmos, field: Color, center: Vec2, end: Vec2) {
     // Render equipotential radii as dotted/ticked circumferences and skip the first three
     let max_r = center.distance(end) * 1.1;
     let mut r = 16.0;           // same base as draw_iso_potentials
@@ -351,7 +357,7 @@ fn draw_dotted_iso_potentials(gizmos: &mut Gizmos, center: Vec2, end: Vec2) {
             // Angle sampling around the circle. Increase samples with radius for visual density.
             let samples = ((r * 0.25).clamp(32.0, 96.0)) as i32; // 32..96 ticks per circle depending on radius
             let alpha = (1.0 / (1.0 + r * 0.04)).min(0.6);
-            let color = Color::srgb(0.9, 0.8, 0.4).with_alpha(alpha * 0.45);
+            let color = field.with_alpha(alpha * 0.45);
 
             for s in 0..samples {
                 let a = s as f32 / samples as f32 * std::f32::consts::TAU;
@@ -1,8 +1,11 @@
 use bevy::prelude::*;
-use crate::GameplaySystem;
+use crate::{GameplaySystem, PausableSystems};
+use crate::accessibility::Accessibility;
+use crate::screens::{InGame, InitGameResource};
 use crate::sun_system::{Sun, Satellite, Level};
 use crate::collision::{FatalCollisionEvent, HitBox};
-mod red_star;
+use crate::theme::{HudTheme, ThemeColor};
+pub(crate) mod red_star;
 mod gravity_viz;
 mod selection_glow;
 
@@ -32,10 +35,10 @@ impl Default for SunFlameConfig {
 }
 
 pub(super) fn plugin(app: &mut App) {
-    app.init_resource::<SunFlameConfig>();
+    app.init_game_resource::<SunFlameConfig>();
     app.add_systems(Update, draw_sun_flames.in_set(GameplaySystem));
     app.add_observer(on_fatal_collision_swallow);
-    app.add_systems(Update, (update_and_render_swallow_fx).in_set(GameplaySystem));
+    app.add_systems(Update, (update_and_render_swallow_fx).in_set(GameplaySystem).in_set(PausableSystems));
     red_star::plugin(app);
     gravity_viz::plugin(app);
     selection_glow::plugin(app);
@@ -46,11 +49,13 @@ fn draw_sun_flames(
     sun: Query<(&GlobalTransform, &HitBox), With<Sun>>,
     time: Res<Time>,
     cfg: Res<SunFlameConfig>,
+    accessibility: Res<Accessibility>,
 ) {
     let Ok((gt, hb)) = sun.single() else { return; };
     let center = gt.translation().xy();
 
-    let t = time.elapsed_secs();
+    // frozen flames with reduced motion
+    let t = if accessibility.reduced_motion { 0.0 } else { time.elapsed_secs() };
     let n = cfg.spikes.max(3) as i32;
     let two_pi = std::f32::consts::TAU;
 
@@ -113,6 +118,7 @@ fn on_fatal_collision_swallow(
     commands.spawn((
         SwallowFx { start: t.translation.xy(), elapsed: 0.0, duration: 0.5, style, scale },
         Name::new("SwallowFx"),
+        DespawnOnExit(InGame),
     ));
 }
 
@@ -122,6 +128,7 @@ fn update_and_render_swallow_fx(
     mut q_fx: Query<(Entity, &mut SwallowFx)>,
     q_sun_t: Query<(&GlobalTransform, &HitBox), With<Sun>>,
     time: Res<Time>,
+    theme: Res<HudTheme>,
 ) {
     let Ok((gt, hb)) = q_sun_t.single() else { return; };
     let center = gt.translation().xy();
@@ -132,10 +139,9 @@ fn update_and_render_swallow_fx(
         fx.elapsed += time.delta_secs();
         let t = (fx.elapsed / fx.duration).clamp(0.0, 1.0);
         let ease_out = 1.0 - (1.0 - t).powf(3.0);
-        let ease_in = t.powf(2.0);
 
-        let core = Color::srgb(1.00, 0.65, 0.15).with_alpha(0.9 * (1.0 - t));
-        let glow = Color::srgb(1.00, 0.30, 0.05).with_alpha(0.6 * (1.0 - t));
+        let core = theme.color(ThemeColor::Highlight).with_alpha(0.9 * (1.0 - t));
+        let glow = theme.color(ThemeColor::Fire).with_alpha(0.6 * (1.0 - t));
 
         match fx.style {
             SwallowStyle::LavaSplash => {
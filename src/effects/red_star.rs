@@ -1,45 +1,302 @@
+use bevy::asset::io::Reader;
+use bevy::asset::{AssetLoader, LoadContext};
 use bevy::prelude::*;
-use crate::GameplaySystem;
-use crate::collision::{ HitBox};
+use serde::Deserialize;
+use crate::asset_tracking::LoadResource;
+use crate::{AppSystems, GameplaySystem, PausableSystems};
+use crate::accessibility::Accessibility;
+use crate::collision::HitBox;
+use crate::scenario::{ActiveScenario, SunSettings};
 use crate::score::Score;
-use crate::sun_system::{SolarSystemAssets, Sun};
+use crate::screens::InitGameResource;
+use crate::sun_system::{Luminosity, Satellite, SolarSystemAssets, Sun};
+use crate::theme::{HudTheme, ThemeColor, deserialize_hex_color};
 use super::SunFlameConfig;
 
+/// The lifecycle of the sun, listed explicitly because web builds can't load whole folders
+const LIFECYCLE_FILE: &str = "stars/sun.lifecycle.ron";
+
+/// Which sprite the sun uses while in a stage
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+pub enum StarSprite {
+    Yellow,
+    Red,
+}
+
+/// A single stage in the life of the sun
+#[derive(Debug, Clone, Deserialize)]
+pub struct StarStage {
+    /// Key of the name in the string tables
+    pub name: String,
+    /// Stored energy at which the sun evolves into this stage
+    pub energy_threshold: f32,
+    /// Multiplier on the energy every collector harvests
+    pub luminosity: f32,
+    /// Hitbox radius as a multiple of the scenario's sun, everything inside gets swallowed
+    pub radius_factor: f32,
+    /// Sprite scale as a multiple of the scenario's sun
+    pub scale_factor: f32,
+    pub sprite: StarSprite,
+    #[serde(deserialize_with = "deserialize_hex_color")]
+    pub core: Color,
+    #[serde(deserialize_with = "deserialize_hex_color")]
+    pub glow: Color,
+}
+
+impl StarStage {
+    pub fn radius(&self, sun: &SunSettings) -> f32 {
+        sun.radius * self.radius_factor
+    }
+
+    pub fn sprite_scale(&self, sun: &SunSettings) -> f32 {
+        sun.sprite_scale() * self.scale_factor
+    }
+}
+
+/// The ordered sequence of stages the sun evolves through, loaded from [`LIFECYCLE_FILE`]
+#[derive(Resource, Asset, TypePath, Debug, Clone, Deserialize)]
+pub struct StarLifecycle {
+    pub stages: Vec<StarStage>,
+    /// Seconds it takes the sun to grow into a new stage
+    pub growth_duration: f32,
+    /// Fraction of the next threshold from which on collectors in danger are highlighted
+    pub warning_fraction: f32,
+}
+
+impl Default for StarLifecycle {
+    /// A sun that never evolves, used until the lifecycle is loaded
+    fn default() -> Self {
+        Self {
+            stages: vec![StarStage {
+                name: "star.main_sequence".to_string(),
+                energy_threshold: 0.0,
+                luminosity: 1.0,
+                radius_factor: 1.0,
+                scale_factor: 1.0,
+                sprite: StarSprite::Yellow,
+                core: Color::srgb(1.00, 0.30, 0.05),
+                glow: Color::srgb(1.00, 0.30, 0.05),
+            }],
+            growth_duration: 4.0,
+            warning_fraction: 0.8,
+        }
+    }
+}
+
+#[derive(Resource, Asset, Clone, Reflect)]
+#[reflect(Resource)]
+struct StarLifecycleAssets {
+    #[dependency]
+    lifecycle: Handle<StarLifecycle>,
+}
+
+impl FromWorld for StarLifecycleAssets {
+    fn from_world(world: &mut World) -> Self {
+        let assets = world.resource::<AssetServer>();
+        Self {
+            lifecycle: assets.load(LIFECYCLE_FILE),
+        }
+    }
+}
+
+#[derive(Default)]
+struct StarLifecycleLoader;
+
+impl AssetLoader for StarLifecycleLoader {
+    type Asset = StarLifecycle;
+    type Settings = ();
+    type Error = BevyError;
+
+    async fn load(
+        &self,
+        reader: &mut dyn Reader,
+        _settings: &(),
+        _load_context: &mut LoadContext<'_>,
+    ) -> Result<StarLifecycle, BevyError> {
+        let mut bytes = Vec::new();
+        reader.read_to_end(&mut bytes).await?;
+        Ok(ron::de::from_bytes(&bytes)?)
+    }
+
+    fn extensions(&self) -> &[&str] {
+        &["lifecycle.ron"]
+    }
+}
+
+fn apply_star_lifecycle(
+    mut lifecycle: ResMut<StarLifecycle>,
+    lifecycle_assets: Res<StarLifecycleAssets>,
+    lifecycles: Res<Assets<StarLifecycle>>,
+) {
+    if let Some(loaded) = lifecycles.get(&lifecycle_assets.lifecycle) {
+        *lifecycle = loaded.clone();
+    }
+}
+
 #[derive(Resource, Default)]
 pub struct RedStarState {
-    pub converted: bool,
+    /// Index into [`StarLifecycle::stages`] of the stage the sun is in (or growing into)
+    pub stage: usize,
+    growth: Option<StarGrowth>,
 }
 
+/// An ongoing growth animation from the previous stage to the current one
+struct StarGrowth {
+    from_radius: f32,
+    from_scale: f32,
+    elapsed: f32,
+}
+
+/// The sun started growing into its next stage
+#[derive(Event, Debug)]
+pub struct StarStageReached;
+
+/// Puts the sun into the given stage right away without animating, e.g. when loading a save
+#[derive(Event, Debug)]
+pub struct RestoreStarStage(pub usize);
+
 pub(super) fn plugin(app: &mut App) {
-    app.init_resource::<RedStarState>();
-    app.add_systems(Update, red_star_conversion.in_set(GameplaySystem));
+    app.init_asset::<StarLifecycle>();
+    app.init_asset_loader::<StarLifecycleLoader>();
+    app.init_resource::<StarLifecycle>();
+    app.load_resource::<StarLifecycleAssets>();
+    app.add_systems(Update, apply_star_lifecycle.run_if(resource_added::<StarLifecycleAssets>));
+    app.init_game_resource::<RedStarState>();
+    app.add_observer(restore_star_stage);
+    app.add_systems(
+        FixedUpdate,
+        (advance_star_stage, animate_star_growth)
+            .chain()
+            .in_set(AppSystems::Update)
+            .in_set(GameplaySystem)
+            .in_set(PausableSystems),
+    );
+    app.add_systems(
+        Update,
+        draw_engulfment_warning
+            .in_set(GameplaySystem)
+            .in_set(PausableSystems),
+    );
 }
 
-fn red_star_conversion(
+fn advance_star_stage(
+    mut commands: Commands,
     mut state: ResMut<RedStarState>,
+    lifecycle: Res<StarLifecycle>,
     score: Res<Score>,
     assets: Res<SolarSystemAssets>,
-    mut sun_q: Query<(Entity, &mut Transform, &mut HitBox, &mut Sprite), With<Sun>>, 
+    mut sun_q: Query<(&Transform, &HitBox, &mut Sprite, &mut Luminosity), With<Sun>>,
     mut cfg: ResMut<SunFlameConfig>,
 ) {
-    if state.converted { return; }
-    // Treat reaching 10k energy as reaching level 2
-    if score.energy_stored < 10_000.0 { return; }
+    let Some(next) = lifecycle.stages.get(state.stage + 1) else { return; };
+    if score.energy_stored < next.energy_threshold { return; }
+    let Ok((sun_t, sun_hitbox, mut sun_sprite, mut luminosity)) = sun_q.single_mut() else { return; };
 
-    state.converted = true;
+    info!("Sun evolves into {}", next.name);
+    state.stage += 1;
+    state.growth = Some(StarGrowth {
+        from_radius: sun_hitbox.radius,
+        from_scale: sun_t.scale.x,
+        elapsed: 0.0,
+    });
 
-    if let Ok((_sun_entity, mut sun_t, mut sun_hitbox, mut sun_sprite)) = sun_q.single_mut() {
-        // Visual: make sun slightly bigger and swap sprite to red sun
-        sun_t.scale *= 1.25;
-        sun_hitbox.radius *= 1.70;
-        *sun_sprite = Sprite::from(assets.redsun.clone());
+    // Sprite, colours and output switch right away, only the size is animated
+    *sun_sprite = Sprite::from(match next.sprite {
+        StarSprite::Yellow => assets.sun.clone(),
+        StarSprite::Red => assets.redsun.clone(),
+    });
+    luminosity.0 = next.luminosity;
+    cfg.core = next.core;
+    cfg.glow = next.glow;
+    commands.trigger(StarStageReached);
+}
 
-        // Tint flames to redder hues and slightly expand
-        cfg.core = Color::srgb(1.00, 0.25, 0.10);
-        cfg.glow = Color::srgb(1.00, 0.10, 0.05);
-        cfg.outer_r *= 1.15;
+fn restore_star_stage(
+    ev: On<RestoreStarStage>,
+    mut state: ResMut<RedStarState>,
+    lifecycle: Res<StarLifecycle>,
+    assets: Res<SolarSystemAssets>,
+    mut sun_q: Query<(&mut Transform, &mut HitBox, &mut Sprite, &mut Luminosity), With<Sun>>,
+    mut cfg: ResMut<SunFlameConfig>,
+    scenario: Res<ActiveScenario>,
+) {
+    let Some(stage) = lifecycle.stages.get(ev.0) else { return; };
+    let Ok((mut sun_t, mut sun_hitbox, mut sun_sprite, mut luminosity)) = sun_q.single_mut() else { return; };
 
+    state.stage = ev.0;
+    state.growth = None;
+    sun_t.scale = Vec3::splat(stage.sprite_scale(&scenario.0.sun));
+    sun_hitbox.radius = stage.radius(&scenario.0.sun);
+    *sun_sprite = Sprite::from(match stage.sprite {
+        StarSprite::Yellow => assets.sun.clone(),
+        StarSprite::Red => assets.redsun.clone(),
+    });
+    luminosity.0 = stage.luminosity;
+    cfg.core = stage.core;
+    cfg.glow = stage.glow;
+}
+
+fn animate_star_growth(
+    mut state: ResMut<RedStarState>,
+    lifecycle: Res<StarLifecycle>,
+    mut sun_q: Query<(&mut Transform, &mut HitBox), With<Sun>>,
+    time: Res<Time>,
+    scenario: Res<ActiveScenario>,
+) {
+    let stage_idx = state.stage;
+    let Some(growth) = state.growth.as_mut() else { return; };
+    let Some(stage) = lifecycle.stages.get(stage_idx) else { return; };
+    let Ok((mut sun_t, mut sun_hitbox)) = sun_q.single_mut() else { return; };
+
+    growth.elapsed += time.delta_secs();
+    let t = (growth.elapsed / lifecycle.growth_duration.max(0.001)).clamp(0.0, 1.0);
+    let eased = t * t * (3.0 - 2.0 * t); // smoothstep
+
+    let sun = &scenario.0.sun;
+    sun_hitbox.radius = growth.from_radius.lerp(stage.radius(sun), eased);
+    sun_t.scale = Vec3::splat(growth.from_scale.lerp(stage.sprite_scale(sun), eased));
+
+    if t >= 1.0 {
+        state.growth = None;
     }
 }
 
+fn draw_engulfment_warning(
+    mut gizmos: Gizmos,
+    (state, lifecycle, scenario): (Res<RedStarState>, Res<StarLifecycle>, Res<ActiveScenario>),
+    score: Res<Score>,
+    sun_q: Query<&GlobalTransform, With<Sun>>,
+    sats: Query<(&GlobalTransform, &HitBox), With<Satellite>>,
+    (time, accessibility): (Res<Time>, Res<Accessibility>),
+    theme: Res<HudTheme>,
+) {
+    let Some(next) = lifecycle.stages.get(state.stage + 1) else { return; };
+    if score.energy_stored < next.energy_threshold * lifecycle.warning_fraction { return; }
+    let Ok(sun_gt) = sun_q.single() else { return; };
+    let center = sun_gt.translation().xy();
+    let next_radius = next.radius(&scenario.0.sun);
+
+    let pulse = if accessibility.reduced_motion { 1.0 } else { (time.elapsed_secs() * 4.0).sin() * 0.5 + 0.5 };
+    let warn = theme.color(ThemeColor::Warning);
+
+    // Future surface of the sun as a dotted ring
+    let segments = 48;
+    for i in (0..segments).step_by(2) {
+        let a0 = i as f32 / segments as f32 * std::f32::consts::TAU;
+        let a1 = (i + 1) as f32 / segments as f32 * std::f32::consts::TAU;
+        gizmos.line_2d(
+            center + Vec2::from_angle(a0) * next_radius,
+            center + Vec2::from_angle(a1) * next_radius,
+            warn.with_alpha(0.25 + 0.35 * pulse),
+        );
+    }
 
+    // Mark every collector that will end up inside the grown sun
+    for (sat_gt, sat_hitbox) in sats.iter() {
+        let pos = sat_gt.translation().xy();
+        if pos.distance(center) < next_radius + sat_hitbox.radius {
+            let iso = Isometry2d::from_translation(pos);
+            gizmos.circle_2d(iso, 5.0 + pulse * 2.0, warn.with_alpha(0.6 + 0.4 * pulse));
+        }
+    }
+}
@@ -0,0 +1,69 @@
+//! Gamepad play. The left stick aims a reticle around earth that launches go towards, holding the
+//! launch trigger charges the launch like holding the mouse button. The right stick pans the
+//! camera, buttons are mapped to actions in [`crate::controls`].
+
+use bevy::prelude::*;
+
+use crate::controls::{STICK_DEADZONE, gamepad_in_use};
+use crate::launching::LaunchState;
+use crate::screens::InitGameResource;
+use crate::sun_system::earth::Earth;
+use crate::theme::{HudTheme, ThemeColor};
+use crate::{GameplaySystem, PausableSystems};
+
+/// Distance of the reticle from earth in world units
+const RETICLE_DISTANCE: f32 = 40.0;
+const RETICLE_RADIUS: f32 = 3.0;
+
+pub(super) fn plugin(app: &mut App) {
+    app.init_game_resource::<GamepadAim>();
+    app.add_systems(
+        Update,
+        (aim_with_stick, draw_reticle.run_if(gamepad_in_use))
+            .chain()
+            .in_set(GameplaySystem)
+            .in_set(PausableSystems),
+    );
+}
+
+/// Direction launches go towards while a gamepad is in use, stays put when the stick is let go
+#[derive(Resource)]
+pub struct GamepadAim(pub Vec2);
+
+impl Default for GamepadAim {
+    fn default() -> Self {
+        Self(Vec2::Y)
+    }
+}
+
+fn aim_with_stick(gamepads: Query<&Gamepad>, mut aim: ResMut<GamepadAim>) {
+    let Some(stick) = gamepads
+        .iter()
+        .map(Gamepad::left_stick)
+        .find(|stick| stick.length() > STICK_DEADZONE)
+    else {
+        return;
+    };
+    aim.0 = stick.normalize();
+}
+
+/// The reticle grows with the charge of the launch, like the launch bar
+fn draw_reticle(
+    mut gizmos: Gizmos,
+    aim: Res<GamepadAim>,
+    earth: Query<&Transform, With<Earth>>,
+    launch_state: Res<LaunchState>,
+    time: Res<Time>,
+    theme: Res<HudTheme>,
+) {
+    let Ok(earth) = earth.single() else { return; };
+    let earth_position = earth.translation.xy();
+    let reticle = earth_position + aim.0 * RETICLE_DISTANCE;
+    let color = theme.color(ThemeColor::Highlight);
+    gizmos.line_2d(earth_position + aim.0 * RETICLE_RADIUS * 2.0, reticle - aim.0 * RETICLE_RADIUS, color);
+    gizmos.circle_2d(reticle, RETICLE_RADIUS, color);
+    if let Some(launch_start_time) = launch_state.launched_at_time {
+        let charge = ((time.elapsed_secs_f64() - launch_start_time) as f32).min(1.0);
+        gizmos.circle_2d(reticle, RETICLE_RADIUS * (1.0 + charge), theme.color(ThemeColor::Text));
+    }
+}
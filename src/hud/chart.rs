@@ -0,0 +1,289 @@
+//! Energy charts: a live sparkline of the recent energy rate next to the energy box, and the
+//! chart of the whole run with its launches, crashes, swarms and star stages on the game-over
+//! screen.
+
+use bevy::prelude::*;
+
+use super::PANEL_BORDER;
+use crate::locale::{LocaleArg, LocalizedText};
+use crate::score::history::{EnergyHistory, HistoryEventKind};
+use crate::screens::{InGame, Screen};
+use crate::theme::{self, HudTheme, TextSize, ThemeColor, ThemedBackground, ThemedBorder, ThemedText};
+use crate::{GameplaySystem, PausableSystems};
+
+/// Samples shown by the sparkline, one per second until long runs merge them
+const SPARKLINE_SAMPLES: usize = 60;
+const SPARKLINE_BAR_WIDTH: f32 = 2.0;
+const SPARKLINE_HEIGHT: f32 = 40.0;
+/// Columns of the game-over chart, the run is averaged down to fit
+const CHART_COLUMNS: usize = 90;
+const CHART_WIDTH: f32 = 360.0;
+const CHART_HEIGHT: f32 = 180.0;
+
+pub(super) fn plugin(app: &mut App) {
+    app.add_systems(OnEnter(Screen::Gameplay), spawn_sparkline);
+    app.add_systems(
+        Update,
+        update_sparkline
+            .run_if(sparkline_outdated)
+            .in_set(GameplaySystem)
+            .in_set(PausableSystems),
+    );
+}
+
+/// One bar of the sparkline, the index counts from the oldest shown sample
+#[derive(Component)]
+struct SparklineBar(usize);
+
+fn spawn_sparkline(mut commands: Commands) {
+    // TOP LEFT — right of the energy box
+    commands
+        .spawn((
+            Name::new("Energy Sparkline"),
+            DespawnOnExit(InGame),
+            Node {
+                position_type: PositionType::Absolute,
+                top: Val::Px(15.0),
+                left: Val::Px(355.0),
+                flex_direction: FlexDirection::Column,
+                row_gap: Val::Px(4.0),
+                padding: UiRect::all(Val::Px(8.0)),
+                border: UiRect::all(Val::Px(PANEL_BORDER)),
+                ..default()
+            },
+            theme::panel(2.0),
+            Pickable::IGNORE,
+        ))
+        .with_children(|panel| {
+            panel.spawn((
+                LocalizedText::new("chart.sparkline"),
+                ThemedText::new(ThemeColor::Text, TextSize::Small),
+                Pickable::IGNORE,
+            ));
+            panel
+                .spawn((
+                    Node {
+                        width: Val::Px(SPARKLINE_SAMPLES as f32 * SPARKLINE_BAR_WIDTH),
+                        height: Val::Px(SPARKLINE_HEIGHT),
+                        align_items: AlignItems::FlexEnd,
+                        ..default()
+                    },
+                    Pickable::IGNORE,
+                ))
+                .with_children(|bars| {
+                    for index in 0..SPARKLINE_SAMPLES {
+                        bars.spawn((
+                            SparklineBar(index),
+                            Node {
+                                width: Val::Px(SPARKLINE_BAR_WIDTH),
+                                height: Val::Percent(0.0),
+                                ..default()
+                            },
+                            ThemedBackground(ThemeColor::Text),
+                            Pickable::IGNORE,
+                        ));
+                    }
+                });
+        });
+}
+
+/// Only redraws when a sample was taken or the bars are new, the history changes every tick
+fn sparkline_outdated(
+    history: Res<EnergyHistory>,
+    new_bars: Query<(), Added<SparklineBar>>,
+    mut shown: Local<usize>,
+) -> bool {
+    let outdated = history.samples.len() != *shown || !new_bars.is_empty();
+    *shown = history.samples.len();
+    outdated
+}
+
+fn update_sparkline(history: Res<EnergyHistory>, mut bars: Query<(&SparklineBar, &mut Node)>) {
+    let recent = &history.samples[history.samples.len().saturating_sub(SPARKLINE_SAMPLES)..];
+    let max = recent.iter().map(|sample| sample.rate).fold(f32::EPSILON, f32::max);
+    // new samples come in on the right
+    let offset = SPARKLINE_SAMPLES - recent.len();
+    for (bar, mut node) in bars.iter_mut() {
+        let height = bar
+            .0
+            .checked_sub(offset)
+            .map_or(0.0, |index| recent[index].rate / max * 100.0);
+        if node.height != Val::Percent(height) {
+            node.height = Val::Percent(height);
+        }
+    }
+}
+
+fn event_color(kind: HistoryEventKind) -> ThemeColor {
+    match kind {
+        HistoryEventKind::Launch => ThemeColor::Highlight,
+        HistoryEventKind::Crash => ThemeColor::Warning,
+        HistoryEventKind::Swarm => ThemeColor::Neutral,
+        HistoryEventKind::StarStage => ThemeColor::Selected,
+    }
+}
+
+fn event_name(kind: HistoryEventKind) -> &'static str {
+    match kind {
+        HistoryEventKind::Launch => "chart.launch",
+        HistoryEventKind::Crash => "chart.crash",
+        HistoryEventKind::Swarm => "chart.swarm",
+        HistoryEventKind::StarStage => "chart.star",
+    }
+}
+
+pub(super) fn clock(seconds: f32) -> String {
+    let seconds = seconds as u32;
+    format!("{:02}:{:02}", seconds / 60, seconds % 60)
+}
+
+/// Spawns the chart of the whole run: the energy rate as bars, the stored energy as a line of
+/// dots and a marker for every recorded event
+pub(crate) fn spawn_energy_chart(parent: &mut ChildSpawnerCommands, history: &EnergyHistory, theme: &HudTheme) {
+    let text = |content: LocalizedText, color: ThemeColor| {
+        (
+            content,
+            ThemedText::new(color, TextSize::Small),
+            Pickable::IGNORE,
+        )
+    };
+
+    // average the samples down to one column each
+    let per_column = history.samples.len().div_ceil(CHART_COLUMNS).max(1);
+    let columns: Vec<(f32, f32)> = history
+        .samples
+        .chunks(per_column)
+        .map(|chunk| {
+            let count = chunk.len() as f32;
+            (
+                chunk.iter().map(|sample| sample.rate).sum::<f32>() / count,
+                chunk.iter().map(|sample| sample.stored).sum::<f32>() / count,
+            )
+        })
+        .collect();
+    let max_rate = columns.iter().map(|(rate, _)| *rate).fold(f32::EPSILON, f32::max);
+    let max_stored = columns.iter().map(|(_, stored)| *stored).fold(f32::EPSILON, f32::max);
+    let column_width = CHART_WIDTH / CHART_COLUMNS as f32;
+    let duration = history.duration().max(f32::EPSILON);
+
+    parent
+        .spawn((
+            Name::new("Energy Chart"),
+            Node {
+                flex_direction: FlexDirection::Column,
+                row_gap: Val::Px(6.0),
+                padding: UiRect::all(Val::Px(20.0)),
+                border: UiRect::all(Val::Px(2.0)),
+                ..default()
+            },
+            theme::panel(3.0),
+            Pickable::IGNORE,
+        ))
+        .with_children(|panel| {
+            panel.spawn(text(LocalizedText::new("chart.title"), ThemeColor::Text));
+            panel.spawn((
+                Node {
+                    width: Val::Px(CHART_WIDTH),
+                    justify_content: JustifyContent::SpaceBetween,
+                    ..default()
+                },
+                Pickable::IGNORE,
+                children![
+                    text(
+                        LocalizedText::new("chart.max_rate").with("rate", LocaleArg::number(max_rate, 0)),
+                        ThemeColor::Text,
+                    ),
+                    text(
+                        LocalizedText::new("chart.max_stored").with("stored", LocaleArg::number(max_stored, 0)),
+                        ThemeColor::Accent,
+                    ),
+                ],
+            ));
+            panel
+                .spawn((
+                    Node {
+                        width: Val::Px(CHART_WIDTH),
+                        height: Val::Px(CHART_HEIGHT),
+                        align_items: AlignItems::FlexEnd,
+                        border: UiRect::bottom(Val::Px(1.0)).with_left(Val::Px(1.0)),
+                        ..default()
+                    },
+                    ThemedBorder(ThemeColor::Text),
+                    Pickable::IGNORE,
+                ))
+                .with_children(|plot| {
+                    for (index, (rate, stored)) in columns.iter().enumerate() {
+                        plot.spawn((
+                            Node {
+                                width: Val::Px(column_width),
+                                height: Val::Percent(rate / max_rate * 100.0),
+                                ..default()
+                            },
+                            BackgroundColor(theme.color(ThemeColor::Text).with_alpha(0.6)),
+                            Pickable::IGNORE,
+                        ));
+                        plot.spawn((
+                            Node {
+                                position_type: PositionType::Absolute,
+                                left: Val::Px(index as f32 * column_width),
+                                bottom: Val::Percent(stored / max_stored * 100.0),
+                                width: Val::Px(column_width),
+                                height: Val::Px(2.0),
+                                ..default()
+                            },
+                            ThemedBackground(ThemeColor::Accent),
+                            Pickable::IGNORE,
+                        ));
+                    }
+                    for event in &history.events {
+                        plot.spawn((
+                            Node {
+                                position_type: PositionType::Absolute,
+                                left: Val::Percent(event.time / duration * 100.0),
+                                top: Val::Px(0.0),
+                                width: Val::Px(1.0),
+                                height: Val::Percent(100.0),
+                                ..default()
+                            },
+                            BackgroundColor(theme.color(event_color(event.kind)).with_alpha(0.7)),
+                            Pickable::IGNORE,
+                        ));
+                    }
+                });
+            panel.spawn((
+                Node {
+                    width: Val::Px(CHART_WIDTH),
+                    justify_content: JustifyContent::SpaceBetween,
+                    ..default()
+                },
+                Pickable::IGNORE,
+                children![
+                    text(clock(0.0).into(), ThemeColor::Text),
+                    text(clock(history.duration()).into(), ThemeColor::Text),
+                ],
+            ));
+            // legend with the number of each kind of event
+            panel
+                .spawn((
+                    Node {
+                        column_gap: Val::Px(12.0),
+                        ..default()
+                    },
+                    Pickable::IGNORE,
+                ))
+                .with_children(|legend| {
+                    for kind in [
+                        HistoryEventKind::Launch,
+                        HistoryEventKind::Crash,
+                        HistoryEventKind::Swarm,
+                        HistoryEventKind::StarStage,
+                    ] {
+                        let count = history.events.iter().filter(|event| event.kind == kind).count();
+                        let label = LocalizedText::new("chart.legend")
+                            .with("event", LocaleArg::key(event_name(kind)))
+                            .with("count", count);
+                        legend.spawn(text(label, event_color(kind)));
+                    }
+                });
+        });
+}
@@ -0,0 +1,338 @@
+//! Notification feed: crashes, demotions, full orbits, asteroid swarms, the sun changing stage and
+//! Kardashev milestones are logged with the time of the run and listed in the HUD, newest first.
+//! Clicking an entry centers the camera on the collector involved, or where it happened if there is
+//! none or it is gone. The full log is saved with the run and listed on the game-over screen.
+
+use bevy::ecs::system::SystemParam;
+use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+
+use super::PANEL_BORDER;
+use super::chart::clock;
+use crate::achievements::FullOrbitAchieved;
+use crate::camera::CameraFollow;
+use crate::collision::{DemoteCollisionEvent, FatalCollisionEvent};
+use crate::effects::red_star::{RedStarState, StarLifecycle, StarStageReached};
+use crate::launching::CollectorId;
+use crate::locale::{LocaleArg, LocalizedText};
+use crate::menus::widget;
+use crate::score::history::EnergyHistory;
+use crate::score::kardashev::MilestoneReached;
+use crate::screens::{InGame, InitGameResource, Screen};
+use crate::sun_system::asteroids::{AsteroidAssets, AsteroidSwarmSpawned};
+use crate::sun_system::{SolarSystemAssets, Sun};
+use crate::theme::{self, TextSize, ThemeColor, ThemedText};
+use crate::{GameplaySystem, PausableSystems};
+
+/// Entries shown in the HUD, the game-over screen lists all of them
+const FEED_ENTRIES: usize = 30;
+const FEED_WIDTH: f32 = 280.0;
+const ICON_SIZE: f32 = 14.0;
+
+pub(super) fn plugin(app: &mut App) {
+    app.init_game_resource::<EventLog>();
+    app.add_observer(log_crash);
+    app.add_observer(log_demotion);
+    app.add_observer(log_full_orbit);
+    app.add_observer(log_swarm);
+    app.add_observer(log_star_stage);
+    app.add_observer(log_milestone);
+    app.add_systems(OnEnter(Screen::Gameplay), spawn_feed);
+    app.add_systems(
+        Update,
+        (
+            focus_feed_entry,
+            refresh_feed.run_if(resource_changed::<EventLog>),
+        )
+            .in_set(GameplaySystem)
+            .in_set(PausableSystems),
+    );
+}
+
+/// Everything noteworthy that happened in the current run, oldest first
+#[derive(Resource, Default, Debug, Clone, Serialize, Deserialize)]
+pub struct EventLog {
+    pub entries: Vec<LogEntry>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LogEntry {
+    /// Seconds into the run
+    pub time: f32,
+    pub kind: LogKind,
+    pub text: LocalizedText,
+    /// The collector involved, it may be gone by now
+    pub collector: Option<CollectorId>,
+    /// Where it happened, the camera goes there if there is no collector to follow
+    pub position: Vec2,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum LogKind {
+    Crash,
+    Demotion,
+    FullOrbit,
+    Swarm,
+    StarStage,
+    Milestone,
+}
+
+impl LogKind {
+    fn color(self) -> ThemeColor {
+        match self {
+            LogKind::Crash => ThemeColor::Warning,
+            LogKind::Demotion => ThemeColor::Highlight,
+            LogKind::FullOrbit => ThemeColor::Glow,
+            LogKind::Swarm => ThemeColor::Neutral,
+            LogKind::StarStage => ThemeColor::Fire,
+            LogKind::Milestone => ThemeColor::Accent,
+        }
+    }
+}
+
+/// Images shown next to the entries
+#[derive(SystemParam)]
+pub(crate) struct LogIcons<'w> {
+    solar_system: Res<'w, SolarSystemAssets>,
+    asteroids: Res<'w, AsteroidAssets>,
+}
+
+impl LogIcons<'_> {
+    fn icon(&self, kind: LogKind) -> Handle<Image> {
+        match kind {
+            LogKind::Crash => self.solar_system.crash.clone(),
+            LogKind::Demotion => self.solar_system.collector.clone(),
+            LogKind::FullOrbit => self.solar_system.collector3.clone(),
+            LogKind::Swarm => self.asteroids.asteroid.clone(),
+            LogKind::StarStage => self.solar_system.redsun.clone(),
+            LogKind::Milestone => self.solar_system.sun.clone(),
+        }
+    }
+}
+
+/// Gives the time of the run, the collector and the position of the subject to new entries
+#[derive(SystemParam)]
+struct LogWriter<'w, 's> {
+    log: ResMut<'w, EventLog>,
+    history: Res<'w, EnergyHistory>,
+    transforms: Query<'w, 's, &'static Transform>,
+    collectors: Query<'w, 's, &'static CollectorId>,
+}
+
+impl LogWriter<'_, '_> {
+    fn write(&mut self, kind: LogKind, text: LocalizedText, subject: Entity) {
+        let position = self.transforms.get(subject).map_or(Vec2::ZERO, |t| t.translation.xy());
+        let time = self.history.duration();
+        self.log.entries.push(LogEntry {
+            time,
+            kind,
+            text,
+            collector: self.collectors.get(subject).ok().copied(),
+            position,
+        });
+    }
+}
+
+/// Container of the feed entries
+#[derive(Component)]
+struct FeedEntries;
+
+/// Clicking it focuses the camera on the subject of the entry with this index
+#[derive(Component)]
+struct FeedEntry(usize);
+
+fn collector_text(key: &str, id: CollectorId) -> LocalizedText {
+    LocalizedText::new(key).with("id", id.0 + 1)
+}
+
+fn log_crash(
+    ev: On<FatalCollisionEvent>,
+    collectors: Query<&CollectorId>,
+    suns: Query<(), With<Sun>>,
+    mut writer: LogWriter,
+) {
+    let Ok(id) = collectors.get(ev.destroyed) else { return; };
+    let key = if suns.contains(ev.other) { "feed.swallowed" } else { "feed.crash" };
+    writer.write(LogKind::Crash, collector_text(key, *id), ev.destroyed);
+}
+
+fn log_demotion(ev: On<DemoteCollisionEvent>, collectors: Query<&CollectorId>, mut writer: LogWriter) {
+    let Ok(id) = collectors.get(ev.demoted) else { return; };
+    writer.write(LogKind::Demotion, collector_text("feed.demoted", *id), ev.demoted);
+}
+
+fn log_full_orbit(ev: On<FullOrbitAchieved>, collectors: Query<&CollectorId>, mut writer: LogWriter) {
+    let Ok(id) = collectors.get(ev.entity) else { return; };
+    writer.write(LogKind::FullOrbit, collector_text("feed.orbit", *id), ev.entity);
+}
+
+fn log_swarm(ev: On<AsteroidSwarmSpawned>, mut writer: LogWriter) {
+    writer.write(LogKind::Swarm, LocalizedText::new("feed.swarm"), ev.swarm);
+}
+
+fn log_star_stage(
+    _ev: On<StarStageReached>,
+    state: Res<RedStarState>,
+    lifecycle: Res<StarLifecycle>,
+    sun: Query<Entity, With<Sun>>,
+    mut writer: LogWriter,
+) {
+    let (Some(stage), Ok(sun)) = (lifecycle.stages.get(state.stage), sun.single()) else { return; };
+    let text = LocalizedText::new("feed.star_stage").with("stage", LocaleArg::key(stage.name.clone()));
+    writer.write(LogKind::StarStage, text, sun);
+}
+
+fn log_milestone(ev: On<MilestoneReached>, sun: Query<Entity, With<Sun>>, mut writer: LogWriter) {
+    let Ok(sun) = sun.single() else { return; };
+    let text = LocalizedText::new("feed.milestone")
+        .with("type", LocaleArg::number(ev.ktype, 2))
+        .with("reward", LocaleArg::number(ev.reward, 0));
+    writer.write(LogKind::Milestone, text, sun);
+}
+
+fn spawn_feed(mut commands: Commands) {
+    // BOTTOM LEFT — above the buttons
+    commands.spawn((
+        Name::new("Event Feed"),
+        DespawnOnExit(InGame),
+        FeedEntries,
+        widget::ScrollArea,
+        Node {
+            position_type: PositionType::Absolute,
+            bottom: Val::Px(75.0),
+            left: Val::Px(15.0),
+            width: Val::Px(FEED_WIDTH),
+            max_height: Val::Px(140.0),
+            flex_direction: FlexDirection::Column,
+            row_gap: Val::Px(2.0),
+            padding: UiRect::all(Val::Px(6.0)),
+            border: UiRect::all(Val::Px(PANEL_BORDER)),
+            overflow: Overflow::scroll_y(),
+            display: Display::None,
+            ..default()
+        },
+        theme::panel(2.0),
+    ));
+}
+
+/// One line of the log: icon, time and text
+fn spawn_log_line(parent: &mut ChildSpawnerCommands, entry: &LogEntry, icons: &LogIcons, extra: impl Bundle) {
+    parent.spawn((
+        extra,
+        Node {
+            column_gap: Val::Px(6.0),
+            align_items: AlignItems::Center,
+            ..default()
+        },
+        children![
+            (
+                ImageNode::new(icons.icon(entry.kind)),
+                Node {
+                    width: Val::Px(ICON_SIZE),
+                    height: Val::Px(ICON_SIZE),
+                    flex_shrink: 0.0,
+                    ..default()
+                },
+                Pickable::IGNORE,
+            ),
+            (
+                Text::new(clock(entry.time)),
+                ThemedText::new(ThemeColor::Dim, TextSize::Caption),
+                Pickable::IGNORE,
+            ),
+            (
+                entry.text.clone(),
+                ThemedText::new(entry.kind.color(), TextSize::Caption),
+                Pickable::IGNORE,
+            ),
+        ],
+    ));
+}
+
+fn refresh_feed(
+    mut commands: Commands,
+    log: Res<EventLog>,
+    icons: LogIcons,
+    mut feed: Query<(Entity, &mut Node), With<FeedEntries>>,
+) {
+    let Ok((feed, mut node)) = feed.single_mut() else { return; };
+    let display = if log.entries.is_empty() { Display::None } else { Display::Flex };
+    if node.display != display {
+        node.display = display;
+    }
+
+    commands.entity(feed).despawn_related::<Children>();
+    commands.entity(feed).with_children(|feed| {
+        for (index, entry) in log.entries.iter().enumerate().rev().take(FEED_ENTRIES) {
+            spawn_log_line(feed, entry, &icons, (Button, FeedEntry(index)));
+        }
+    });
+}
+
+fn focus_feed_entry(
+    entries: Query<(&Interaction, &FeedEntry), Changed<Interaction>>,
+    log: Res<EventLog>,
+    collectors: Query<(&CollectorId, &GlobalTransform)>,
+    mut camera: Query<&mut Transform, With<Camera2d>>,
+    mut follow: ResMut<CameraFollow>,
+) {
+    for (interaction, entry) in entries.iter() {
+        if *interaction != Interaction::Pressed {
+            continue;
+        }
+        let Some(entry) = log.entries.get(entry.0) else { continue; };
+        let target = collectors
+            .iter()
+            .find(|(id, _)| Some(**id) == entry.collector)
+            .map_or(entry.position, |(_, transform)| transform.translation().xy());
+        follow.0 = None;
+        for mut transform in camera.iter_mut() {
+            transform.translation.x = target.x;
+            transform.translation.y = target.y;
+        }
+    }
+}
+
+/// Lists the whole log of the run, for the game-over screen
+pub(crate) fn spawn_event_log(parent: &mut ChildSpawnerCommands, log: &EventLog, icons: &LogIcons) {
+    parent
+        .spawn((
+            Name::new("Event Log"),
+            Node {
+                flex_direction: FlexDirection::Column,
+                row_gap: Val::Px(6.0),
+                padding: UiRect::all(Val::Px(20.0)),
+                border: UiRect::all(Val::Px(2.0)),
+                ..default()
+            },
+            theme::panel(3.0),
+            Pickable::IGNORE,
+        ))
+        .with_children(|panel| {
+            panel.spawn((
+                LocalizedText::new("feed.title"),
+                ThemedText::new(ThemeColor::Text, TextSize::Small),
+                Pickable::IGNORE,
+            ));
+            panel
+                .spawn((
+                    widget::ScrollArea,
+                    Node {
+                        width: Val::Px(FEED_WIDTH),
+                        max_height: Val::Px(360.0),
+                        flex_direction: FlexDirection::Column,
+                        row_gap: Val::Px(2.0),
+                        overflow: Overflow::scroll_y(),
+                        ..default()
+                    },
+                ))
+                .with_children(|lines| {
+                    if log.entries.is_empty() {
+                        lines.spawn(widget::label("feed.empty"));
+                    }
+                    for entry in &log.entries {
+                        spawn_log_line(lines, entry, icons, ());
+                    }
+                });
+        });
+}
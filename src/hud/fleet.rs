@@ -0,0 +1,399 @@
+//! Fleet list with one row per collector. The list can be sorted by each column, clicking a row
+//! selects the collector and centers the camera on it. Toggled with the FLEET button or
+//! [`Action::ToggleFleet`].
+
+use bevy::prelude::*;
+
+use super::PANEL_BORDER;
+use crate::camera::CameraFollow;
+use crate::collision::HitBox;
+use crate::controls::{Action, action_just_pressed};
+use crate::launching::{CollectorId, CollectorStats, Fuel};
+use crate::locale::{LocaleArg, LocalizedText};
+use crate::menus::widget;
+use crate::physics::calc_gravity::calc_orbit;
+use crate::physics::directional_forces::Mass;
+use crate::physics::velocity::Velocity;
+use crate::replay::{GameplayInput, InputQueue};
+use crate::sandbox::is_sandbox;
+use crate::scenario::ActiveScenario;
+use crate::screens::{InGame, InitGameResource, Screen};
+use crate::sun_system::navigation_instruments::NavigationInstruments;
+use crate::sun_system::{Level, Satellite, Sun};
+use crate::theme::{self, TextSize, ThemeColor, ThemedText};
+use crate::{AppSystems, GameplaySystem, PausableSystems};
+
+/// Seconds between two rebuilds of the rows
+const REFRESH_INTERVAL: f32 = 0.5;
+/// Width of each column in pixels, in the order of [`FleetColumn::ALL`]
+const COLUMN_WIDTHS: [f32; 5] = [50.0, 40.0, 80.0, 50.0, 90.0];
+
+pub(super) fn plugin(app: &mut App) {
+    app.init_game_resource::<FleetList>();
+    app.add_systems(OnEnter(Screen::Gameplay), spawn_fleet_list);
+    app.add_systems(
+        Update,
+        (
+            toggle_fleet_list.run_if(action_just_pressed(Action::ToggleFleet)),
+            handle_fleet_action,
+            select_fleet_row.in_set(AppSystems::RecordInput),
+            update_fleet_visibility,
+            refresh_fleet_list,
+        )
+            .chain()
+            .in_set(GameplaySystem)
+            .in_set(PausableSystems),
+    );
+}
+
+#[derive(Resource)]
+struct FleetList {
+    visible: bool,
+    sort: FleetColumn,
+    descending: bool,
+    refresh: Timer,
+}
+
+impl Default for FleetList {
+    fn default() -> Self {
+        Self {
+            visible: false,
+            sort: FleetColumn::Name,
+            descending: false,
+            refresh: Timer::from_seconds(REFRESH_INTERVAL, TimerMode::Repeating),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum FleetColumn {
+    Name,
+    Level,
+    Rate,
+    Fuel,
+    Orbit,
+}
+
+impl FleetColumn {
+    const ALL: [FleetColumn; 5] = [
+        FleetColumn::Name,
+        FleetColumn::Level,
+        FleetColumn::Rate,
+        FleetColumn::Fuel,
+        FleetColumn::Orbit,
+    ];
+
+    fn title(self) -> &'static str {
+        match self {
+            FleetColumn::Name => "fleet.name",
+            FleetColumn::Level => "fleet.level",
+            FleetColumn::Rate => "fleet.rate",
+            FleetColumn::Fuel => "fleet.fuel",
+            FleetColumn::Orbit => "fleet.orbit",
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+enum OrbitStatus {
+    Stable,
+    /// The orbit passes through the sun
+    Sundive,
+    Escaping,
+}
+
+impl OrbitStatus {
+    fn label(self) -> &'static str {
+        match self {
+            OrbitStatus::Stable => "fleet.stable",
+            OrbitStatus::Sundive => "fleet.sundive",
+            OrbitStatus::Escaping => "fleet.escaping",
+        }
+    }
+}
+
+#[derive(Component, Debug, Clone, Copy, PartialEq, Eq)]
+enum FleetAction {
+    Toggle,
+    Sort(FleetColumn),
+    /// Centers the camera on the sun again
+    CenterSun,
+}
+
+/// A row of the list, clicking it selects the collector
+#[derive(Component)]
+struct FleetRow(CollectorId);
+
+#[derive(Component)]
+struct FleetPanel;
+
+/// Container of the rows
+#[derive(Component)]
+struct FleetRows;
+
+struct FleetEntry {
+    id: CollectorId,
+    level: f32,
+    rate: f32,
+    fuel: f32,
+    orbit: OrbitStatus,
+    selected: bool,
+}
+
+type FleetMember = (
+    &'static CollectorId,
+    &'static Transform,
+    &'static Velocity,
+    &'static Level,
+    &'static Fuel,
+    &'static CollectorStats,
+    &'static HitBox,
+    Has<NavigationInstruments>,
+);
+
+fn spawn_fleet_list(mut commands: Commands, scenario: Res<ActiveScenario>) {
+    let text = |key: &str, width: f32| {
+        (
+            LocalizedText::new(key),
+            Node {
+                width: Val::Px(width),
+                ..default()
+            },
+            ThemedText::new(ThemeColor::Text, TextSize::Small),
+            Pickable::IGNORE,
+        )
+    };
+
+    // BOTTOM LEFT — Fleet toggle button (to the right of the orbit button)
+    commands.spawn((
+        DespawnOnExit(InGame),
+        Node {
+            position_type: PositionType::Absolute,
+            bottom: Val::Px(15.0),
+            left: Val::Px(290.0),
+            width: Val::Px(80.0),
+            height: Val::Px(50.0),
+            border: UiRect::all(Val::Px(PANEL_BORDER)),
+            justify_content: JustifyContent::Center,
+            align_items: AlignItems::Center,
+            ..default()
+        },
+        theme::panel(2.0),
+        Button,
+        FleetAction::Toggle,
+        children![(
+            LocalizedText::new("fleet.title"),
+            ThemedText::new(ThemeColor::Text, TextSize::Button),
+        )],
+    ));
+
+    // RIGHT — the list itself, left of the sandbox panel in the sandbox
+    let right = if is_sandbox(scenario) { 270.0 } else { 15.0 };
+    commands
+        .spawn((
+            Name::new("Fleet List"),
+            FleetPanel,
+            DespawnOnExit(InGame),
+            Node {
+                position_type: PositionType::Absolute,
+                top: Val::Px(150.0),
+                right: Val::Px(right),
+                flex_direction: FlexDirection::Column,
+                row_gap: Val::Px(6.0),
+                padding: UiRect::all(Val::Px(10.0)),
+                display: Display::None,
+                ..default()
+            },
+            theme::panel(2.0),
+        ))
+        .with_children(|panel| {
+            panel
+                .spawn(Node {
+                    column_gap: Val::Px(4.0),
+                    ..default()
+                })
+                .with_children(|header| {
+                    for (column, width) in FleetColumn::ALL.into_iter().zip(COLUMN_WIDTHS) {
+                        header.spawn((
+                            Node {
+                                width: Val::Px(width),
+                                ..default()
+                            },
+                            Button,
+                            FleetAction::Sort(column),
+                            children![text(column.title(), width)],
+                        ));
+                    }
+                });
+            panel.spawn((
+                Name::new("Fleet Rows"),
+                FleetRows,
+                widget::ScrollArea,
+                Node {
+                    flex_direction: FlexDirection::Column,
+                    row_gap: Val::Px(2.0),
+                    max_height: Val::Px(220.0),
+                    overflow: Overflow::scroll_y(),
+                    ..default()
+                },
+            ));
+            panel.spawn(widget::small_button("fleet.sun", FleetAction::CenterSun));
+        });
+}
+
+fn toggle_fleet_list(mut fleet: ResMut<FleetList>) {
+    fleet.visible = !fleet.visible;
+    // show the current state right away
+    let duration = fleet.refresh.duration();
+    fleet.refresh.set_elapsed(duration);
+}
+
+fn handle_fleet_action(
+    query: Query<(&Interaction, &FleetAction), Changed<Interaction>>,
+    mut fleet: ResMut<FleetList>,
+    mut camera: Query<&mut Transform, With<Camera2d>>,
+    mut follow: ResMut<CameraFollow>,
+) {
+    for (interaction, action) in query.iter() {
+        if *interaction != Interaction::Pressed {
+            continue;
+        }
+        match *action {
+            FleetAction::Toggle => fleet.visible = !fleet.visible,
+            FleetAction::Sort(column) => {
+                // clicking the sorted column again flips the order
+                fleet.descending = fleet.sort == column && !fleet.descending;
+                fleet.sort = column;
+            }
+            FleetAction::CenterSun => {
+                follow.0 = None;
+                for mut transform in camera.iter_mut() {
+                    transform.translation.x = 0.0;
+                    transform.translation.y = 0.0;
+                }
+            }
+        }
+        let duration = fleet.refresh.duration();
+        fleet.refresh.set_elapsed(duration);
+    }
+}
+
+fn update_fleet_visibility(fleet: Res<FleetList>, mut panel: Query<&mut Node, With<FleetPanel>>) {
+    let Ok(mut node) = panel.single_mut() else { return; };
+    let display = if fleet.visible { Display::Flex } else { Display::None };
+    if node.display != display {
+        node.display = display;
+    }
+}
+
+fn select_fleet_row(
+    rows: Query<(&Interaction, &FleetRow), Changed<Interaction>>,
+    collectors: Query<(&CollectorId, &Transform)>,
+    mut camera: Query<&mut Transform, (With<Camera2d>, Without<CollectorId>)>,
+    mut follow: ResMut<CameraFollow>,
+    mut inputs: ResMut<InputQueue>,
+) {
+    for (interaction, row) in rows.iter() {
+        if *interaction != Interaction::Pressed {
+            continue;
+        }
+        inputs.push(GameplayInput::Select(row.0));
+        let Some((_, target)) = collectors.iter().find(|(id, _)| **id == row.0) else { continue; };
+        follow.0 = None;
+        for mut transform in camera.iter_mut() {
+            transform.translation.x = target.translation.x;
+            transform.translation.y = target.translation.y;
+        }
+    }
+}
+
+fn refresh_fleet_list(
+    mut commands: Commands,
+    mut fleet: ResMut<FleetList>,
+    time: Res<Time>,
+    collectors: Query<FleetMember, With<Satellite>>,
+    sun: Query<(&Transform, &Mass, &HitBox), With<Sun>>,
+    rows: Query<Entity, With<FleetRows>>,
+) {
+    fleet.refresh.tick(time.delta());
+    if !fleet.visible || !fleet.refresh.just_finished() {
+        return;
+    }
+    let (Ok(rows), Ok((sun_transform, sun_mass, sun_hitbox))) = (rows.single(), sun.single()) else { return; };
+
+    let mut entries: Vec<FleetEntry> = collectors
+        .iter()
+        .map(|(id, transform, velocity, level, fuel, stats, hitbox, selected)| {
+            let offset = transform.translation.xy() - sun_transform.translation.xy();
+            let orbit = calc_orbit(sun_mass.0, offset, velocity.0);
+            let orbit = if orbit.period.is_none() {
+                OrbitStatus::Escaping
+            } else if orbit.hits_attractor(sun_hitbox.radius, hitbox.radius) {
+                OrbitStatus::Sundive
+            } else {
+                OrbitStatus::Stable
+            };
+            FleetEntry {
+                id: *id,
+                level: level.level,
+                rate: stats.output,
+                fuel: fuel.amount,
+                orbit,
+                selected,
+            }
+        })
+        .collect();
+
+    let sort = fleet.sort;
+    entries.sort_by(|a, b| {
+        let ordering = match sort {
+            FleetColumn::Name => a.id.0.cmp(&b.id.0),
+            FleetColumn::Level => a.level.total_cmp(&b.level),
+            FleetColumn::Rate => a.rate.total_cmp(&b.rate),
+            FleetColumn::Fuel => a.fuel.total_cmp(&b.fuel),
+            FleetColumn::Orbit => a.orbit.cmp(&b.orbit),
+        };
+        // ties keep the order of launch
+        let ordering = ordering.then(a.id.0.cmp(&b.id.0));
+        if fleet.descending { ordering.reverse() } else { ordering }
+    });
+
+    commands.entity(rows).despawn_related::<Children>();
+    commands.entity(rows).with_children(|rows| {
+        if entries.is_empty() {
+            rows.spawn(widget::label("fleet.empty"));
+        }
+        for entry in entries {
+            let color = if entry.selected { ThemeColor::Highlight } else { ThemeColor::Text };
+            let cells = [
+                LocalizedText::from(format!("#{}", entry.id.0 + 1)),
+                LocalizedText::from(format!("{}", entry.level)),
+                LocalizedText::new("fleet.rate_value").with("rate", LocaleArg::number(entry.rate, 1)),
+                LocalizedText::new("fleet.fuel_value").with("fuel", LocaleArg::number(entry.fuel, 1)),
+                LocalizedText::new(entry.orbit.label()),
+            ];
+            rows.spawn((
+                Name::new("Fleet Row"),
+                Node {
+                    column_gap: Val::Px(4.0),
+                    ..default()
+                },
+                Button,
+                FleetRow(entry.id),
+            ))
+            .with_children(|row| {
+                for (cell, width) in cells.into_iter().zip(COLUMN_WIDTHS) {
+                    row.spawn((
+                        cell,
+                        Node {
+                            width: Val::Px(width),
+                            ..default()
+                        },
+                        ThemedText::new(color, TextSize::Small),
+                        Pickable::IGNORE,
+                    ));
+                }
+            });
+        }
+    });
+}
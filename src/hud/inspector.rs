@@ -0,0 +1,170 @@
+//! Inspector panel for the selected collector, shows its stats and orbit and lets the player
+//! change its burn direction or decommission it.
+
+use bevy::prelude::*;
+
+use crate::launching::{CollectorId, CollectorStats, Fuel};
+use crate::locale::{LocaleArg, LocalizedText};
+use crate::menus::widget;
+use crate::physics::calc_gravity::calc_orbit;
+use crate::physics::directional_forces::Mass;
+use crate::physics::velocity::Velocity;
+use crate::replay::{GameplayInput, InputQueue};
+use crate::screens::{InGame, Screen};
+use crate::sun_system::navigation_instruments::NavigationInstruments;
+use crate::sun_system::thruster::{Thruster, ThrusterDirection};
+use crate::sun_system::{Level, Satellite, Sun};
+use crate::theme::{self, TextSize, ThemeColor, ThemedText};
+use crate::{AppSystems, GameplaySystem, PausableSystems};
+
+pub(super) fn plugin(app: &mut App) {
+    app.add_systems(OnEnter(Screen::Gameplay), spawn_inspector);
+    app.add_systems(
+        Update,
+        (
+            handle_inspector_action.in_set(AppSystems::RecordInput),
+            update_inspector,
+        )
+            .chain()
+            .in_set(GameplaySystem)
+            .in_set(PausableSystems),
+    );
+}
+
+/// The collector the inspector shows
+type Selected = (With<Satellite>, With<NavigationInstruments>);
+
+type SelectedCollector = (
+    &'static CollectorId,
+    &'static Transform,
+    &'static Velocity,
+    &'static Level,
+    &'static Fuel,
+    &'static Thruster,
+    &'static CollectorStats,
+);
+
+#[derive(Component)]
+struct Inspector;
+
+#[derive(Component)]
+struct InspectorText;
+
+#[derive(Component, Debug, Clone, Copy, PartialEq, Eq)]
+enum InspectorAction {
+    CycleBurnDirection,
+    Decommission,
+}
+
+fn spawn_inspector(mut commands: Commands) {
+    commands.spawn((
+        Name::new("Collector Inspector"),
+        Inspector,
+        DespawnOnExit(InGame),
+        Node {
+            position_type: PositionType::Absolute,
+            top: Val::Px(155.0),
+            left: Val::Px(15.0),
+            flex_direction: FlexDirection::Column,
+            row_gap: Val::Px(8.0),
+            padding: UiRect::all(Val::Px(10.0)),
+            display: Display::None,
+            ..default()
+        },
+        theme::panel(2.0),
+        children![
+            (
+                LocalizedText::new(""),
+                ThemedText::new(ThemeColor::Text, TextSize::Body),
+                InspectorText,
+            ),
+            widget::button("", InspectorAction::CycleBurnDirection),
+            widget::button("inspector.decommission", InspectorAction::Decommission),
+        ],
+    ));
+}
+
+fn handle_inspector_action(
+    query: Query<(&Interaction, &InspectorAction), Changed<Interaction>>,
+    selected: Query<(&CollectorId, &Thruster), Selected>,
+    mut inputs: ResMut<InputQueue>,
+) {
+    let Ok((id, thruster)) = selected.single() else { return; };
+    for (interaction, action) in query.iter() {
+        if *interaction != Interaction::Pressed {
+            continue;
+        }
+        inputs.push(match action {
+            InspectorAction::CycleBurnDirection => {
+                GameplayInput::SetBurnDirection(*id, next_burn_direction(thruster.direction))
+            }
+            InspectorAction::Decommission => GameplayInput::Decommission(*id),
+        });
+    }
+}
+
+fn next_burn_direction(direction: ThrusterDirection) -> ThrusterDirection {
+    match direction {
+        ThrusterDirection::Prograde => ThrusterDirection::Retrograde,
+        ThrusterDirection::Retrograde => ThrusterDirection::RadialIn,
+        ThrusterDirection::RadialIn => ThrusterDirection::RadialOut,
+        ThrusterDirection::RadialOut => ThrusterDirection::Prograde,
+    }
+}
+
+fn burn_direction_name(direction: ThrusterDirection) -> &'static str {
+    match direction {
+        ThrusterDirection::Prograde => "inspector.prograde",
+        ThrusterDirection::Retrograde => "inspector.retrograde",
+        ThrusterDirection::RadialIn => "inspector.radial_in",
+        ThrusterDirection::RadialOut => "inspector.radial_out",
+    }
+}
+
+fn update_inspector(
+    selected: Query<SelectedCollector, Selected>,
+    sun: Query<(&Transform, &Mass), With<Sun>>,
+    mut panel: Query<&mut Node, With<Inspector>>,
+    mut text: Query<&mut LocalizedText, With<InspectorText>>,
+    buttons: Query<(&InspectorAction, &Children)>,
+    mut button_texts: Query<&mut LocalizedText, Without<InspectorText>>,
+) {
+    let Ok(mut node) = panel.single_mut() else { return; };
+    let (Ok((id, transform, velocity, level, fuel, thruster, stats)), Ok((sun_transform, sun_mass))) =
+        (selected.single(), sun.single())
+    else {
+        node.display = Display::None;
+        return;
+    };
+    node.display = Display::Flex;
+
+    let offset = transform.translation.xy() - sun_transform.translation.xy();
+    let orbit = calc_orbit(sun_mass.0, offset, velocity.0);
+    let seconds = |value: Option<f32>| value.map_or("--".to_string(), |s| format!("{s:.0}s"));
+    let content = LocalizedText::new("inspector.stats")
+        .with("id", format!("{}", id.0 + 1))
+        .with("level", format!("{}", level.level))
+        .with("output", LocaleArg::number(stats.output, 2))
+        .with("lifetime", LocaleArg::number(stats.total_collected, 1))
+        .with("fuel", LocaleArg::number(fuel.amount, 1))
+        .with("speed", LocaleArg::number(velocity.0.length(), 1))
+        .with("distance", LocaleArg::number(offset.length(), 0))
+        .with("period", seconds(orbit.period))
+        .with("eccentricity", LocaleArg::number(orbit.eccentricity, 3))
+        .with("periapsis", seconds(orbit.time_to_periapsis));
+    if let Ok(mut text) = text.single_mut() {
+        text.set_if_neq(content);
+    }
+
+    let burn = LocalizedText::new("inspector.burn").with("direction", LocaleArg::key(burn_direction_name(thruster.direction)));
+    for (action, children) in buttons.iter() {
+        if *action != InspectorAction::CycleBurnDirection {
+            continue;
+        }
+        for child in children.iter() {
+            if let Ok(mut text) = button_texts.get_mut(child) {
+                text.set_if_neq(burn.clone());
+            }
+        }
+    }
+}
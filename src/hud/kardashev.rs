@@ -0,0 +1,142 @@
+//! Live Kardashev meter at the bottom of the screen, with a marker for every milestone that
+//! lights up once it is crossed.
+
+use bevy::prelude::*;
+
+use super::PANEL_BORDER;
+use crate::locale::{LocaleArg, LocalizedText};
+use crate::score::Score;
+use crate::score::kardashev::{KardashevMilestones, KardashevProgress, kardashev_type};
+use crate::screens::{InGame, Screen};
+use crate::theme::{self, TextSize, ThemeColor, ThemedBackground, ThemedText};
+use crate::{GameplaySystem, PausableSystems};
+
+/// Types at the left and right end of the meter
+const METER_RANGE: (f32, f32) = (1.95, 2.08);
+const METER_WIDTH: f32 = 240.0;
+const METER_HEIGHT: f32 = 8.0;
+
+pub(super) fn plugin(app: &mut App) {
+    app.add_systems(OnEnter(Screen::Gameplay), spawn_kardashev_meter);
+    app.add_systems(
+        Update,
+        update_kardashev_meter
+            .in_set(GameplaySystem)
+            .in_set(PausableSystems),
+    );
+}
+
+#[derive(Component)]
+struct KardashevLabel;
+
+#[derive(Component)]
+struct KardashevFill;
+
+/// Marker of the milestone with this index
+#[derive(Component)]
+struct MilestoneMarker(usize);
+
+/// Position of `ktype` on the meter in percent
+fn meter_percent(ktype: f32) -> f32 {
+    ((ktype - METER_RANGE.0) / (METER_RANGE.1 - METER_RANGE.0) * 100.0).clamp(0.0, 100.0)
+}
+
+fn spawn_kardashev_meter(mut commands: Commands, milestones: Res<KardashevMilestones>) {
+    // BOTTOM CENTER
+    commands
+        .spawn((
+            Name::new("Kardashev Meter"),
+            DespawnOnExit(InGame),
+            Node {
+                position_type: PositionType::Absolute,
+                bottom: Val::Px(15.0),
+                width: Val::Percent(100.0),
+                justify_content: JustifyContent::Center,
+                ..default()
+            },
+            Pickable::IGNORE,
+        ))
+        .with_children(|root| {
+            root.spawn((
+                Node {
+                    flex_direction: FlexDirection::Column,
+                    row_gap: Val::Px(4.0),
+                    padding: UiRect::all(Val::Px(8.0)),
+                    border: UiRect::all(Val::Px(PANEL_BORDER)),
+                    ..default()
+                },
+                theme::panel(2.0),
+                Pickable::IGNORE,
+            ))
+            .with_children(|panel| {
+                panel.spawn((
+                    KardashevLabel,
+                    LocalizedText::new("kardashev.meter"),
+                    ThemedText::new(ThemeColor::Text, TextSize::Small),
+                    Pickable::IGNORE,
+                ));
+                panel
+                    .spawn((
+                        Node {
+                            width: Val::Px(METER_WIDTH),
+                            height: Val::Px(METER_HEIGHT),
+                            ..default()
+                        },
+                        ThemedBackground(ThemeColor::Dim),
+                        Pickable::IGNORE,
+                    ))
+                    .with_children(|bar| {
+                        bar.spawn((
+                            KardashevFill,
+                            Node {
+                                width: Val::Percent(0.0),
+                                height: Val::Percent(100.0),
+                                ..default()
+                            },
+                            ThemedBackground(ThemeColor::Text),
+                            Pickable::IGNORE,
+                        ));
+                        for (index, milestone) in milestones.0.iter().enumerate() {
+                            bar.spawn((
+                                MilestoneMarker(index),
+                                Node {
+                                    position_type: PositionType::Absolute,
+                                    left: Val::Percent(meter_percent(milestone.ktype)),
+                                    top: Val::Px(-3.0),
+                                    width: Val::Px(2.0),
+                                    height: Val::Px(METER_HEIGHT + 6.0),
+                                    ..default()
+                                },
+                                ThemedBackground(ThemeColor::Neutral),
+                                Pickable::IGNORE,
+                            ));
+                        }
+                    });
+            });
+        });
+}
+
+fn update_kardashev_meter(
+    score: Res<Score>,
+    progress: Res<KardashevProgress>,
+    mut label: Query<&mut LocalizedText, With<KardashevLabel>>,
+    mut fill: Query<&mut Node, With<KardashevFill>>,
+    mut markers: Query<(&MilestoneMarker, &mut ThemedBackground)>,
+) {
+    let ktype = kardashev_type(score.energy_rate);
+    if let Ok(mut label) = label.single_mut() {
+        label.set_if_neq(LocalizedText::new("kardashev.meter").with("type", LocaleArg::number(ktype, 3)));
+    }
+    if let Ok(mut fill) = fill.single_mut() {
+        let width = Val::Percent(meter_percent(ktype));
+        if fill.width != width {
+            fill.width = width;
+        }
+    }
+    for (marker, mut background) in markers.iter_mut() {
+        let color = if marker.0 < progress.reached() { ThemeColor::Highlight } else { ThemeColor::Neutral };
+        if background.0 != color {
+            background.0 = color;
+        }
+    }
+}
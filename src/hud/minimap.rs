@@ -0,0 +1,243 @@
+//! Minimap in the bottom right corner showing the whole system and the part the camera sees.
+//! Clicking or dragging on it moves the camera, incoming asteroid swarms blink for a while.
+
+use bevy::ecs::query::QueryItem;
+use bevy::platform::collections::HashSet;
+use bevy::prelude::*;
+use bevy::ui::RelativeCursorPosition;
+
+use crate::accessibility::Accessibility;
+use crate::camera::CameraFollow;
+use crate::screens::{InGame, Screen};
+use crate::sun_system::asteroids::{AsteroidSwarm, AsteroidSwarmSpawned};
+use crate::sun_system::earth::Earth;
+use crate::sun_system::navigation_instruments::NavigationInstruments;
+use crate::sun_system::{Satellite, Sun};
+use crate::theme::{self, ThemeColor, ThemedBackground, ThemedBorder};
+use crate::{GameplaySystem, PausableSystems};
+
+/// Width and height of the map in pixels
+const MAP_SIZE: f32 = 150.0;
+/// Distance from the sun to the edge of the map in world units
+const MAP_RADIUS: f32 = 350.0;
+/// Seconds a new swarm is highlighted for
+const INCOMING_DURATION: f32 = 10.0;
+/// Blinks per second of a highlighted swarm
+const INCOMING_BLINK_RATE: f32 = 3.0;
+
+pub(super) fn plugin(app: &mut App) {
+    app.add_observer(mark_incoming_swarm);
+    app.add_systems(OnEnter(Screen::Gameplay), spawn_minimap);
+    app.add_systems(
+        Update,
+        (
+            move_camera_from_minimap,
+            tick_incoming_swarms,
+            update_minimap_blips,
+            update_minimap_viewport,
+        )
+            .chain()
+            .in_set(GameplaySystem)
+            .in_set(PausableSystems),
+    );
+}
+
+#[derive(Component)]
+struct Minimap;
+
+/// Outline of the area the camera sees
+#[derive(Component)]
+struct MinimapViewport;
+
+/// Dot on the map following an entity of the system
+#[derive(Component)]
+struct MinimapBlip(Entity);
+
+/// Swarm that spawned recently, highlighted on the map until the timer finishes
+#[derive(Component)]
+struct IncomingSwarm(Timer);
+
+type Tracked = (
+    Entity,
+    &'static Transform,
+    Has<Sun>,
+    Has<Earth>,
+    Has<AsteroidSwarm>,
+    Has<NavigationInstruments>,
+    Option<&'static IncomingSwarm>,
+);
+
+type TrackedFilter = Or<(With<Sun>, With<Earth>, With<Satellite>, With<AsteroidSwarm>)>;
+
+fn spawn_minimap(mut commands: Commands) {
+    // BOTTOM RIGHT — left of the launch pad
+    commands.spawn((
+        Name::new("Minimap"),
+        Minimap,
+        DespawnOnExit(InGame),
+        Node {
+            position_type: PositionType::Absolute,
+            bottom: Val::Px(15.0),
+            right: Val::Px(75.0),
+            width: Val::Px(MAP_SIZE),
+            height: Val::Px(MAP_SIZE),
+            overflow: Overflow::clip(),
+            ..default()
+        },
+        theme::panel(2.0),
+        Button,
+        RelativeCursorPosition::default(),
+        children![(
+            MinimapViewport,
+            Node {
+                position_type: PositionType::Absolute,
+                border: UiRect::all(Val::Px(1.0)),
+                ..default()
+            },
+            ThemedBorder(ThemeColor::Text),
+            Pickable::IGNORE,
+        )],
+    ));
+}
+
+fn mark_incoming_swarm(ev: On<AsteroidSwarmSpawned>, mut commands: Commands) {
+    commands
+        .entity(ev.swarm)
+        .try_insert(IncomingSwarm(Timer::from_seconds(INCOMING_DURATION, TimerMode::Once)));
+}
+
+/// Point on the map in pixels from its top left corner, points outside stick to the edge
+fn map_position(world: Vec2) -> Vec2 {
+    let normalized = (world / (2.0 * MAP_RADIUS)).clamp(Vec2::splat(-0.5), Vec2::splat(0.5));
+    Vec2::new(normalized.x + 0.5, 0.5 - normalized.y) * MAP_SIZE
+}
+
+fn move_camera_from_minimap(
+    map: Query<(&Interaction, &RelativeCursorPosition), With<Minimap>>,
+    mut camera: Query<&mut Transform, With<Camera2d>>,
+    mut follow: ResMut<CameraFollow>,
+) {
+    let Ok((interaction, cursor)) = map.single() else { return; };
+    // keeps following the cursor while the button is held
+    if *interaction != Interaction::Pressed {
+        return;
+    }
+    let Some(normalized) = cursor.normalized else { return; };
+    let target = Vec2::new(normalized.x, -normalized.y) * 2.0 * MAP_RADIUS;
+    follow.0 = None;
+    for mut transform in camera.iter_mut() {
+        transform.translation.x = target.x;
+        transform.translation.y = target.y;
+    }
+}
+
+fn tick_incoming_swarms(
+    mut commands: Commands,
+    time: Res<Time>,
+    mut swarms: Query<(Entity, &mut IncomingSwarm)>,
+) {
+    for (entity, mut incoming) in swarms.iter_mut() {
+        incoming.0.tick(time.delta());
+        if incoming.0.is_finished() {
+            commands.entity(entity).remove::<IncomingSwarm>();
+        }
+    }
+}
+
+/// Size in pixels and color of the blip of a tracked entity, incoming swarms stay highlighted
+/// instead of blinking with reduced motion
+fn blip_style(
+    (_, _, sun, earth, swarm, selected, incoming): QueryItem<'_, '_, Tracked>,
+    reduced_motion: bool,
+) -> (f32, ThemeColor) {
+    if sun {
+        (10.0, ThemeColor::Highlight)
+    } else if earth {
+        (6.0, ThemeColor::Earth)
+    } else if swarm {
+        let blink = incoming.is_some_and(|incoming| {
+            reduced_motion || (incoming.0.elapsed_secs() * INCOMING_BLINK_RATE).fract() < 0.5
+        });
+        if blink {
+            (6.0, ThemeColor::Warning)
+        } else {
+            (4.0, ThemeColor::Neutral)
+        }
+    } else if selected {
+        (5.0, ThemeColor::Selected)
+    } else {
+        (3.0, ThemeColor::Text)
+    }
+}
+
+fn place_blip(node: &mut Node, world: Vec2, size: f32) {
+    let position = map_position(world) - size / 2.0;
+    node.left = Val::Px(position.x);
+    node.top = Val::Px(position.y);
+    node.width = Val::Px(size);
+    node.height = Val::Px(size);
+}
+
+fn update_minimap_blips(
+    mut commands: Commands,
+    map: Query<Entity, With<Minimap>>,
+    tracked: Query<Tracked, TrackedFilter>,
+    mut blips: Query<(Entity, &MinimapBlip, &mut Node, &mut ThemedBackground)>,
+    accessibility: Res<Accessibility>,
+) {
+    let Ok(map) = map.single() else { return; };
+
+    let mut shown = HashSet::new();
+    for (entity, blip, mut node, mut color) in blips.iter_mut() {
+        let Ok(target) = tracked.get(blip.0) else {
+            commands.entity(entity).despawn();
+            continue;
+        };
+        shown.insert(blip.0);
+        let world = target.1.translation.xy();
+        let (size, blip_color) = blip_style(target, accessibility.reduced_motion);
+        place_blip(&mut node, world, size);
+        if color.0 != blip_color {
+            color.0 = blip_color;
+        }
+    }
+
+    for target in tracked.iter() {
+        if shown.contains(&target.0) {
+            continue;
+        }
+        let entity = target.0;
+        let world = target.1.translation.xy();
+        let (size, color) = blip_style(target, accessibility.reduced_motion);
+        let mut node = Node {
+            position_type: PositionType::Absolute,
+            ..default()
+        };
+        place_blip(&mut node, world, size);
+        commands.spawn((
+            Name::new("Minimap Blip"),
+            MinimapBlip(entity),
+            node,
+            BorderRadius::MAX,
+            ThemedBackground(color),
+            Pickable::IGNORE,
+            ChildOf(map),
+        ));
+    }
+}
+
+fn update_minimap_viewport(
+    camera: Query<(&Camera, &Transform), With<Camera2d>>,
+    mut viewport: Query<&mut Node, With<MinimapViewport>>,
+) {
+    let (Ok((camera, transform)), Ok(mut node)) = (camera.single(), viewport.single_mut()) else { return; };
+    let Some(size) = camera.logical_viewport_size() else { return; };
+
+    let size = size * transform.scale.xy() / (2.0 * MAP_RADIUS) * MAP_SIZE;
+    let center = transform.translation.xy() / (2.0 * MAP_RADIUS);
+    let top_left = Vec2::new(center.x + 0.5, 0.5 - center.y) * MAP_SIZE - size / 2.0;
+    node.left = Val::Px(top_left.x);
+    node.top = Val::Px(top_left.y);
+    node.width = Val::Px(size.x);
+    node.height = Val::Px(size.y);
+}
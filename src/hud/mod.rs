@@ -1,17 +1,34 @@
-use crate::GameplaySystem;
+pub(crate) mod chart;
+pub(crate) mod feed;
+mod fleet;
+mod inspector;
+mod kardashev;
+mod minimap;
+mod tutorial;
+
+use crate::{GameplaySystem, PausableSystems};
+use crate::accessibility::Accessibility;
+use crate::camera::{CameraFollow, ToggleCameraFollow};
 use crate::collision::FatalCollisionEvent;
-use crate::launching::{LaunchState, SatellitePriceFactor,CollectorStats};
+use crate::controls::{Action, InputBindings, InputDevice};
+use crate::launching::{launch_cost, LaunchState, SatellitePriceFactor,CollectorStats};
+use crate::locale::{LocaleArg, LocalizedText};
+use crate::replay::GameplayTick;
 use crate::score::Score;
-use crate::screens::{gameover, Screen};
+use crate::seed::RunSeed;
+use crate::screens::{gameover, InGame, InitGameResource, Screen};
 use crate::sun_system::{SolarSystemAssets, Sun, Satellite};
 use crate::sun_system::asteroids::AsteroidSwarmSpawned;
-use crate::sun_system::navigation_instruments::{NavigationInstruments, ShowAllOrbits};
+use crate::sun_system::navigation_instruments::ShowAllOrbits;
 
 // Generated at compile-time by build.rs
 include!(concat!(env!("OUT_DIR"), "/build_info.rs"));
 use bevy::prelude::*;
-use bevy::ui_render::stack_z_offsets::BORDER;
 use crate::sound::Music;
+use crate::theme::{self, TextSize, ThemeColor, ThemedText};
+
+/// Border width of the HUD panels, inside their outline
+const PANEL_BORDER: f32 = 2.0;
 
 pub struct HudPlugin;
 
@@ -25,25 +42,30 @@ impl Plugin for HudPlugin {
                     update_crash_indicators,
                     update_launch_pad_ui,
                     update_zoom_level,
-                    update_explanation_text,
                     update_debris_warning,
                     update_countdown,
+                    update_highest_earner_display,
+                )
+                .in_set(GameplaySystem)
+                .in_set(PausableSystems),
+            )
+            .add_systems(
+                Update,
+                (
                     handle_music_button,
                     update_music_button_visual,
                     handle_orbit_toggle_button,
                     update_orbit_toggle_button_visual,
-                    update_highest_earner_display,
+                    handle_follow_button,
+                    update_follow_button_visual,
+                    update_launch_hint.run_if(resource_changed::<InputBindings>.or(resource_changed::<InputDevice>)),
                 )
                 .in_set(GameplaySystem),
             );
+        app.add_plugins((chart::plugin, inspector::plugin, feed::plugin, fleet::plugin, kardashev::plugin, minimap::plugin, tutorial::plugin));
         app.add_observer(handle_fatal_collision_event_for_hud);
         app.add_observer(handle_asteroid_swarm_spawned);
-        app.insert_resource(HudState {
-            just_destroyed: None,
-            already_pressed_space: false,
-            already_pressed_lmb: false,
-            is_mobile: false,
-        });
+        app.init_game_resource::<HudState>();
     }
 }
 
@@ -64,20 +86,14 @@ struct CrashIndicator {
 struct LaunchBarText;
 
 #[derive(Component)]
-struct ZoomLevelText;
+struct LaunchHintText;
 
 #[derive(Component)]
-struct ExplanationText;
-
-#[derive(Component)]
-struct ExplanationContainer;
+struct ZoomLevelText;
 
-#[derive(Resource)]
+#[derive(Resource, Default)]
 struct HudState {
     just_destroyed: Option<Entity>,
-    already_pressed_space: bool,
-    already_pressed_lmb: bool,
-    is_mobile: bool,
 }
 
 #[derive(Component)]
@@ -101,9 +117,6 @@ struct HighestEarnerText;
 #[derive(Component)]
 struct HighestEarnerDistanceText;
 
-#[derive(Component)]
-struct SpacebarIndicator;
-
 // Orbit button
 #[derive(Component)]
 struct OrbitToggleButton;
@@ -111,27 +124,30 @@ struct OrbitToggleButton;
 #[derive(Component)]
 struct OrbitToggleButtonText;
 
-fn setup_hud(mut commands: Commands, solar_system_assets: Res<SolarSystemAssets>) {
+// Camera follow button
+#[derive(Component)]
+struct FollowButton;
+
+#[derive(Component)]
+struct FollowButtonText;
+
+fn setup_hud(mut commands: Commands, bindings: Res<InputBindings>, device: Res<InputDevice>) {
     // TOP LEFT: Energy Rate and Total Energy Storage
     commands.spawn((
+        DespawnOnExit(InGame),
         Node {
             position_type: PositionType::Absolute,
             top: Val::Px(15.0),
             left: Val::Px(15.0),
             width: Val::Px(330.0),
             height: Val::Px(125.0),
-            border: UiRect::all(Val::Px(BORDER)),
+            border: UiRect::all(Val::Px(PANEL_BORDER)),
             ..default()
         },
-        BackgroundColor(Color::srgb(0.0, 0.0, 0.0)),
-        Outline {
-            width: Val::Px(2.0),
-            offset: Default::default(),
-            color: Color::xyz(0.4811, 0.3064, 0.0253),
-        },
+        theme::panel(2.0),
         children![
             (
-                Text::new("ENERGY RATE\n0"),
+                energy_rate_text(0.0),
                 Node {
                     position_type: PositionType::Relative,
                     top: Val::Px(5.0),
@@ -139,26 +155,18 @@ fn setup_hud(mut commands: Commands, solar_system_assets: Res<SolarSystemAssets>
                     border: UiRect::all(Val::Px(4.0)),
                     ..default()
                 },
-                TextFont {
-                    font: solar_system_assets.font.clone(),
-                    ..default()
-                },
-                TextColor(Color::xyz(0.4811, 0.3064, 0.0253)),
+                ThemedText::new(ThemeColor::Text, TextSize::Title),
                 EnergyRateText
             ),
             (
-                Text::new("TOTAL:\n0"),
+                energy_storage_text(0.0),
                 Node {
                     position_type: PositionType::Absolute,
                     top: Val::Px(65.0),
                     left: Val::Px(15.0),
                     ..default()
                 },
-                TextFont {
-                    font: solar_system_assets.font.clone(),
-                    ..default()
-                },
-                TextColor(Color::xyz(0.4811, 0.3064, 0.0253)),
+                ThemedText::new(ThemeColor::Text, TextSize::Title),
                 EnergyStorageText
             )
         ],
@@ -166,34 +174,25 @@ fn setup_hud(mut commands: Commands, solar_system_assets: Res<SolarSystemAssets>
 
     // BOTTOM LEFT — Music toggle button (to the right of the zoom indicator)
     commands.spawn((
+        DespawnOnExit(InGame),
         Node {
             position_type: PositionType::Absolute,
             bottom: Val::Px(15.0),
             left: Val::Px(110.0),
             width: Val::Px(80.0),
             height: Val::Px(50.0),
-            border: UiRect::all(Val::Px(BORDER)),
+            border: UiRect::all(Val::Px(PANEL_BORDER)),
             justify_content: JustifyContent::Center,
             align_items: AlignItems::Center,
             ..default()
         },
-        BackgroundColor(Color::srgb(0.0, 0.0, 0.0)),
-        Outline {
-            width: Val::Px(2.0),
-            offset: Default::default(),
-            color: Color::xyz(0.4811, 0.3064, 0.0253),
-        },
+        theme::panel(2.0),
         Button,
         MusicButton,
         children![
             (
                 Text::new("[)] )))"),
-                TextFont {
-                    font: solar_system_assets.font.clone(),
-                    font_size: 16.0,
-                    ..default()
-                },
-                TextColor(Color::xyz(0.4811, 0.3064, 0.0253)),
+                ThemedText::new(ThemeColor::Text, TextSize::Button),
                 MusicButtonText,
             )
         ],
@@ -201,72 +200,81 @@ fn setup_hud(mut commands: Commands, solar_system_assets: Res<SolarSystemAssets>
 
     // BOTTOM LEFT — Orbit toggle button (to the right of the music button)
     commands.spawn((
+        DespawnOnExit(InGame),
         Node {
             position_type: PositionType::Absolute,
             bottom: Val::Px(15.0),
             left: Val::Px(200.0),
             width: Val::Px(80.0),
             height: Val::Px(50.0),
-            border: UiRect::all(Val::Px(BORDER)),
+            border: UiRect::all(Val::Px(PANEL_BORDER)),
             justify_content: JustifyContent::Center,
             align_items: AlignItems::Center,
             ..default()
         },
-        BackgroundColor(Color::srgb(0.0, 0.0, 0.0)),
-        Outline {
-            width: Val::Px(2.0),
-            offset: Default::default(),
-            color: Color::xyz(0.4811, 0.3064, 0.0253),
-        },
+        theme::panel(2.0),
         Button,
         OrbitToggleButton,
         children![
             (
-                Text::new("ORBIT\nON"),
-                TextFont {
-                    font: solar_system_assets.font.clone(),
-                    font_size: 16.0,
-                    ..default()
-                },
-                TextColor(Color::xyz(0.4811, 0.3064, 0.0253)),
+                LocalizedText::new("hud.orbit_on"),
+                ThemedText::new(ThemeColor::Text, TextSize::Button),
                 OrbitToggleButtonText,
             )
         ],
     ));
 
+    // BOTTOM RIGHT — Camera follow button (left of the minimap)
+    commands.spawn((
+        DespawnOnExit(InGame),
+        Node {
+            position_type: PositionType::Absolute,
+            bottom: Val::Px(15.0),
+            right: Val::Px(235.0),
+            width: Val::Px(80.0),
+            height: Val::Px(50.0),
+            border: UiRect::all(Val::Px(PANEL_BORDER)),
+            justify_content: JustifyContent::Center,
+            align_items: AlignItems::Center,
+            ..default()
+        },
+        theme::panel(2.0),
+        Button,
+        FollowButton,
+        children![
+            (
+                LocalizedText::new("hud.follow_off"),
+                ThemedText::new(ThemeColor::Text, TextSize::Button),
+                FollowButtonText,
+            )
+        ],
+    ));
+
     let text_center = Justify::Center;
 
     // TOP RIGHT: Countdown to game end
     commands.spawn((
+        DespawnOnExit(InGame),
         Node {
             position_type: PositionType::Absolute,
             top: Val::Px(15.0),
             right: Val::Px(15.0),
             width: Val::Px(150.0),
             height: Val::Px(60.0),
-            border: UiRect::all(Val::Px(BORDER)),
+            border: UiRect::all(Val::Px(PANEL_BORDER)),
             ..default()
         },
-        BackgroundColor(Color::srgb(0.0, 0.0, 0.0)),
-        Outline {
-            width: Val::Px(2.0),
-            offset: Default::default(),
-            color: Color::xyz(0.4811, 0.3064, 0.0253),
-        },
+        theme::panel(2.0),
         children![
             (
-                Text::new("TIME\n10:00"),
+                LocalizedText::new(""),
                 Node {
                     position_type: PositionType::Relative,
                     top: Val::Px(5.0),
                     left: Val::Px(15.0),
                     ..default()
                 },
-                TextFont {
-                    font: solar_system_assets.font.clone(),
-                    ..default()
-                },
-                TextColor(Color::xyz(0.4811, 0.3064, 0.0253)),
+                ThemedText::new(ThemeColor::Text, TextSize::Title),
                 CountdownText
             )
         ],
@@ -274,52 +282,38 @@ fn setup_hud(mut commands: Commands, solar_system_assets: Res<SolarSystemAssets>
 
     // TOP RIGHT (below countdown): Highest Earning Satellite Tracker
     commands.spawn((
+        DespawnOnExit(InGame),
         Node {
             position_type: PositionType::Absolute,
             top: Val::Px(85.0),
             right: Val::Px(15.0),
             width: Val::Px(150.0),
             height: Val::Px(50.0),
-            border: UiRect::all(Val::Px(BORDER)),
+            border: UiRect::all(Val::Px(PANEL_BORDER)),
             ..default()
         },
-        BackgroundColor(Color::srgb(0.0, 0.0, 0.0)),
-        Outline {
-            width: Val::Px(2.0),
-            offset: Default::default(),
-            color: Color::xyz(0.4811, 0.3064, 0.0253),
-        },
+        theme::panel(2.0),
         children![
             (
-                Text::new("TOP SAT\n+0.00 YW"),
+                top_sat_text(0.0),
                 Node {
                     position_type: PositionType::Relative,
                     top: Val::Px(5.0),
                     left: Val::Px(15.0),
                     ..default()
                 },
-                TextFont {
-                    font: solar_system_assets.font.clone(),
-                    font_size: 14.0,
-                    ..default()
-                },
-                TextColor(Color::xyz(0.4811, 0.3064, 0.0253)),
+                ThemedText::new(ThemeColor::Text, TextSize::Body),
                 HighestEarnerText
             ),
             (
-                Text::new("DIST: 0"),
+                top_sat_distance_text(0.0),
                 Node {
                     position_type: PositionType::Absolute,
                     top: Val::Px(40.0),
                     left: Val::Px(20.0),
                     ..default()
                 },
-                TextFont {
-                    font: solar_system_assets.font.clone(),
-                    font_size: 10.0,
-                    ..default()
-                },
-                TextColor(Color::xyz(0.4811, 0.3064, 0.0253)),
+                ThemedText::new(ThemeColor::Text, TextSize::Caption),
                 HighestEarnerDistanceText
             )
         ],
@@ -327,24 +321,21 @@ fn setup_hud(mut commands: Commands, solar_system_assets: Res<SolarSystemAssets>
 
     // BOTTOM RIGHT: Launch Pad UI
     commands.spawn((
+        DespawnOnExit(InGame),
         Node {
             position_type: PositionType::Absolute,
             bottom: Val::Px(15.0),
             right: Val::Px(15.0),
             width: Val::Px(45.0),
             height: Val::Px(550.0),
-            border: UiRect::all(Val::Px(BORDER)),
+            border: UiRect::all(Val::Px(PANEL_BORDER)),
             ..default()
         },
-        BackgroundColor(Color::srgb(0.0, 0.0, 0.0)),
-        Outline {
-            width: Val::Px(2.0),
-            offset: Default::default(),
-            color: Color::xyz(0.4811, 0.3064, 0.0253),
-        },
+        theme::panel(2.0),
         children![
             (
-                Text::new("PRESS\nLMB"),
+                launch_hint_text(&bindings, *device),
+                LaunchHintText,
                 Node {
                     position_type: PositionType::Absolute,
                     bottom: Val::Px(15.0),
@@ -352,12 +343,7 @@ fn setup_hud(mut commands: Commands, solar_system_assets: Res<SolarSystemAssets>
                     ..default()
                 },
                 TextLayout::new_with_justify(text_center),
-                TextColor(Color::xyz(0.4811, 0.3064, 0.0253)),
-                TextFont {
-                    font: solar_system_assets.font.clone(),
-                    font_size: 12.0,
-                    ..default()
-                },
+                ThemedText::new(ThemeColor::Text, TextSize::Small),
             ),
             (
                 LaunchBarText,
@@ -370,37 +356,28 @@ fn setup_hud(mut commands: Commands, solar_system_assets: Res<SolarSystemAssets>
                     right: Val::Px(15.0),
                     ..default()
                 },
-                TextFont {
-                    font: solar_system_assets.font.clone(),
-                    font_size: 25.0,
-                    ..default()
-                },
-                TextColor(Color::xyz(0.4811, 0.3064, 0.0253)),
+                ThemedText::new(ThemeColor::Text, TextSize::Gauge),
             ),
         ],
     ));
 
     //BOTTOM LEFT: ZOOM LEVEL INDICATOR
     commands.spawn((
+        DespawnOnExit(InGame),
         Node {
             position_type: PositionType::Absolute,
             bottom: Val::Px(15.0),
             left: Val::Px(15.0),
             width: Val::Px(80.0),
             height: Val::Px(50.0),
-            border: UiRect::all(Val::Px(BORDER)),
+            border: UiRect::all(Val::Px(PANEL_BORDER)),
 
             ..default()
         },
-        BackgroundColor(Color::srgb(0.0, 0.0, 0.0)),
-        Outline {
-            width: Val::Px(2.0),
-            offset: Default::default(),
-            color: Color::xyz(0.4811, 0.3064, 0.0253),
-        },
+        theme::panel(2.0),
         children![
             (
-                Text::new("1.0x"),
+                zoom_level_text(1.0),
                 ZoomLevelText,
                 Node {
                     position_type: PositionType::Relative,
@@ -409,64 +386,15 @@ fn setup_hud(mut commands: Commands, solar_system_assets: Res<SolarSystemAssets>
                     justify_content: JustifyContent::Center,
                     ..default()
                 },
-                TextFont {
-                    font: solar_system_assets.font.clone(),
-                    ..default()
-                },
-                TextColor(Color::xyz(0.4811, 0.3064, 0.0253)),
+                ThemedText::new(ThemeColor::Text, TextSize::Title),
             )
         ],
     ));
 
 
-    //MIDDLE OF SCREEN: Explanation text
-    commands.spawn((
-        Node {
-            position_type: PositionType::Absolute,
-            width: Val::Percent(100.0),
-            height: Val::Percent(100.0),
-            justify_content: JustifyContent::Center,
-            align_items: AlignItems::Center,
-            ..default()
-        },
-        Pickable::IGNORE,
-        children![
-        (
-            Node {
-                width: Val::Px(300.0),
-                height: Val::Px(45.0),
-                border: UiRect::all(Val::Px(BORDER)),
-                justify_content: JustifyContent::Center,
-                align_items: AlignItems::Center,
-                ..default()
-            },
-            Pickable::IGNORE,
-            BackgroundColor(Color::srgb(0.0, 0.0, 0.0)),
-            Outline {
-                width: Val::Px(2.0),
-                offset: Default::default(),
-                color: Color::xyz(0.4811, 0.3064, 0.0253),
-            },
-            ExplanationContainer,
-            children![
-                (
-                    Text::new("HOLD/RELEASE LMB TO LAUNCH"),
-                    TextFont {
-                        font: solar_system_assets.font.clone(),
-                        font_size: 14.0,
-                        ..default()
-                    },
-                    TextColor(Color::xyz(0.4811, 0.3064, 0.0253)),
-                    ExplanationText,
-                    Pickable::IGNORE,
-                )
-            ],
-        )
-    ],
-    ));
-
     //MIDDLE OF SCREEN: DEBRIS WARNING
     commands.spawn((
+        DespawnOnExit(InGame),
         Node {
             position_type: PositionType::Absolute,
             width: Val::Percent(100.0),
@@ -485,27 +413,17 @@ fn setup_hud(mut commands: Commands, solar_system_assets: Res<SolarSystemAssets>
                 Node {
                     width: Val::Px(300.0),
                     height: Val::Px(60.0),
-                    border: UiRect::all(Val::Px(BORDER)),
+                    border: UiRect::all(Val::Px(PANEL_BORDER)),
                     justify_content: JustifyContent::Center,
                     align_items: AlignItems::Center,
                     ..default()
                 },
                 Pickable::IGNORE,
-                BackgroundColor(Color::srgb(0.0, 0.0, 0.0)),
-                Outline {
-                    width: Val::Px(3.0),
-                    offset: Default::default(),
-                    color: Color::xyz(0.4811, 0.3064, 0.0253),
-                },
+                theme::panel(3.0),
                 children![
                     (
-                        Text::new("DEBRIS WARNING ! !"),
-                        TextFont {
-                            font: solar_system_assets.font.clone(),
-                            font_size: 18.0,
-                            ..default()
-                        },
-                        TextColor(Color::xyz(0.4811, 0.3064, 0.0253)),
+                        LocalizedText::new("hud.debris_warning"),
+                        ThemedText::new(ThemeColor::Text, TextSize::Large),
                         Pickable::IGNORE,
                     )
                 ],
@@ -519,54 +437,79 @@ fn update_hud(
     time: Res<Time>,
     price: Res<SatellitePriceFactor>,
     mut energy_rate_query: Query<
-        (&mut Text, &EnergyRateText),
+        (&mut LocalizedText, &EnergyRateText),
         (With<EnergyRateText>, Without<EnergyStorageText>),
     >,
     mut energy_storage_query: Query<
-        (&mut Text, &mut TextColor, &EnergyStorageText),
+        (&mut LocalizedText, &mut ThemedText, &EnergyStorageText),
         (With<EnergyStorageText>, Without<EnergyRateText>),
     >,
+    accessibility: Res<Accessibility>,
 ) {
-    let percent_rate = player_data.energy_rate /400.;
-    let percent_stored = player_data.energy_stored /100000.;
     if player_data.is_changed() {
         for (mut text, _) in energy_rate_query.iter_mut() {
-            text.0 = format!(
-                "ENERGY RATE\n{} {:.3}YW",
-                get_ascii_bar(percent_rate.clamp(0.0, 1.0)),
-                player_data.energy_rate
-            )
+            *text = energy_rate_text(player_data.energy_rate);
         }
 
-        for (mut text, mut _color, _) in energy_storage_query.iter_mut() {
-            text.0 = format!(
-                "TOTAL:\n{} {:.0}YWh",
-                get_ascii_bar(percent_stored.clamp(0.0, 1.0)),
-                player_data.energy_stored
-            )
+        for (mut text, _, _) in energy_storage_query.iter_mut() {
+            *text = energy_storage_text(player_data.energy_stored);
         }
     }
 
-    // Flash the energy storage text red when there isn't enough energy to launch a satellite
-    let lvl: f32 = if player_data.energy_stored > 20_000.0 {
-        3.0
-    } else if player_data.energy_stored > 10_000.0 {
-        2.0
-    } else {
-        1.0
-    };
-    let required_energy = price.factor * lvl;
+    // Flash the energy storage text red when there isn't enough energy to launch a satellite,
+    // keep it red with reduced motion
+    let (_, required_energy) = launch_cost(player_data.energy_stored, &price);
     let insufficient = player_data.energy_stored < required_energy;
-    let blink_on = (time.elapsed_secs() * 6.0).sin() > 0.0; // ~1 Hz
-    for (_text, mut color, _) in energy_storage_query.iter_mut() {
-        if insufficient && blink_on {
-            color.0 = Color::srgb(1.0, 0.1, 0.1);
-        } else {
-            color.0 = Color::xyz(0.4811, 0.3064, 0.0253);
+    let blink_on = accessibility.reduced_motion || (time.elapsed_secs() * 6.0).sin() > 0.0; // ~1 Hz
+    let color = if insufficient && blink_on { ThemeColor::Warning } else { ThemeColor::Text };
+    for (_text, mut themed, _) in energy_storage_query.iter_mut() {
+        if themed.color != color {
+            themed.color = color;
         }
     }
 }
 
+fn energy_rate_text(rate: f32) -> LocalizedText {
+    let percent_rate = rate / 400.;
+    LocalizedText::new("hud.energy_rate")
+        .with("bar", get_ascii_bar(percent_rate.clamp(0.0, 1.0)))
+        .with("rate", LocaleArg::number(rate, 3))
+}
+
+fn energy_storage_text(stored: f32) -> LocalizedText {
+    let percent_stored = stored / 100000.;
+    LocalizedText::new("hud.energy_stored")
+        .with("bar", get_ascii_bar(percent_stored.clamp(0.0, 1.0)))
+        .with("stored", LocaleArg::number(stored, 0))
+}
+
+fn top_sat_text(rate: f32) -> LocalizedText {
+    LocalizedText::new("hud.top_sat").with("rate", LocaleArg::number(rate, 2))
+}
+
+fn top_sat_distance_text(distance: f32) -> LocalizedText {
+    LocalizedText::new("hud.top_sat_distance").with("distance", LocaleArg::number(distance, 0))
+}
+
+fn launch_hint_text(bindings: &InputBindings, device: InputDevice) -> LocalizedText {
+    LocalizedText::new("hud.press_launch").with("key", bindings.prompt(Action::Launch, device))
+}
+
+/// Picking up the gamepad or rebinding launch in the pause menu changes the hint right away
+fn update_launch_hint(
+    bindings: Res<InputBindings>,
+    device: Res<InputDevice>,
+    mut text: Query<&mut LocalizedText, With<LaunchHintText>>,
+) {
+    for mut text in text.iter_mut() {
+        text.set_if_neq(launch_hint_text(&bindings, *device));
+    }
+}
+
+fn zoom_level_text(zoom: f32) -> LocalizedText {
+    LocalizedText::new("hud.zoom").with("zoom", LocaleArg::number(zoom, 1))
+}
+
 fn get_ascii_bar(percentage: f32) -> String {
     let total_bars = 15;
     let filled_bars = (percentage * total_bars as f32).round() as usize;
@@ -603,6 +546,7 @@ fn handle_fatal_collision_event_for_hud(
 
     commands.spawn((
         Name::new("crash"),
+        DespawnOnExit(InGame),
         Transform::from_translation(entity_transform.translation).with_scale(Vec3::splat(0.01)),
         Sprite::from(solar_system_assets.crash.clone()),
         CrashIndicator {
@@ -621,8 +565,13 @@ fn update_crash_indicators(
     time: Res<Time>,
     mut query: Query<(Entity, &mut CrashIndicator, &mut Visibility)>,
     mut hud_state: ResMut<HudState>,
+    accessibility: Res<Accessibility>,
 ) {
     for (entity, mut crash_indicator, mut visibility) in query.iter_mut() {
+        // shown steadily instead of blinking
+        if accessibility.reduced_motion && crash_indicator.blink_count < 4 {
+            crash_indicator.blink_count = 4;
+        }
         crash_indicator.timer.tick(time.delta());
 
         if crash_indicator.timer.just_finished() {
@@ -685,63 +634,14 @@ fn get_vertical_ascii_bar(percentage: f32) -> String {
 
 fn update_zoom_level (
     camera_query: Query<(&Camera, &Transform)>,
-    mut zoom_level_query: Query<&mut Text, With<ZoomLevelText>>,
+    mut zoom_level_query: Query<&mut LocalizedText, With<ZoomLevelText>>,
 ) {
     let (_, transform) = camera_query.single().unwrap();
-    let mut zoom_level_text = zoom_level_query.single_mut().unwrap();
+    let mut text = zoom_level_query.single_mut().unwrap();
 
     let mut zoom_level = 1.0 / transform.scale.x;
-    zoom_level = zoom_level / 4.0;
-    zoom_level_text.0 = format!("{:.1}x", zoom_level);
-}
-
-fn update_explanation_text(
-    keyboard_input: Res<ButtonInput<KeyCode>>,
-    mouse_input: Res<ButtonInput<MouseButton>>,
-    mut er_touch: MessageReader<bevy::input::touch::TouchInput>,
-    mut explanation_text_query: Query<&mut Text, With<ExplanationText>>,
-    mut explanation_container_query: Query<&mut Visibility, With<ExplanationContainer>>,
-    mut hud_state: ResMut<HudState>,
-) {
-    let mut explanation_text = explanation_text_query.single_mut().unwrap();
-    let mut container_visibility = explanation_container_query.single_mut().unwrap();
-
-    // Mobile detection based on presence of touch events (runtime, no web_sys needed)
-    let mut saw_touch = false;
-    for _ in er_touch.read() { saw_touch = true; break; }
-    if saw_touch { hud_state.is_mobile = true; }
-
-    if hud_state.is_mobile {
-        // Mobile placeholder explanations
-        if !hud_state.already_pressed_space {
-            // First message: how to launch
-            explanation_text.0 = "TAP EARTH AND RELEASE TO LAUNCH".to_string();
-            if saw_touch {
-                hud_state.already_pressed_space = true;
-                explanation_text.0 = "TAP THE SUN TO ACTIVATE THRUSTER".to_string();
-            }
-        } else if !hud_state.already_pressed_lmb {
-            // Second interaction hides the hint box
-            if saw_touch {
-                hud_state.already_pressed_lmb = true;
-                *container_visibility = Visibility::Hidden;
-            }
-        }
-        return;
-    }else {
-        // Desktop (mouse/keyboard) behavior stays unchanged
-        if !hud_state.already_pressed_space {
-            if mouse_input.pressed(MouseButton::Left) {
-                hud_state.already_pressed_space = true;
-                explanation_text.0 = "PRESS SPACE TO SLOW DOWN".to_string();
-            }
-        } else if !hud_state.already_pressed_lmb {
-            if keyboard_input.pressed(KeyCode::Space) {
-                hud_state.already_pressed_lmb = true;
-                *container_visibility = Visibility::Hidden;
-            }
-        }
-    }
+    zoom_level /= 4.0;
+    text.set_if_neq(zoom_level_text(zoom_level));
 }
 
 fn update_debris_warning(
@@ -775,21 +675,35 @@ fn handle_asteroid_swarm_spawned(
 
 
 fn update_countdown(
-    time: Res<Time>,
+    tick: Res<GameplayTick>,
+    fixed: Res<Time<Fixed>>,
     game_end: Option<Res<gameover::GameEnd>>,
-    mut query: Query<&mut Text, With<crate::hud::CountdownText>>,
+    run_seed: Res<RunSeed>,
+    mut query: Query<&mut LocalizedText, With<crate::hud::CountdownText>>,
 ) {
     let Ok(mut text) = query.single_mut() else { return; };
     let Some(game_end) = game_end else { return; };
 
-    if !game_end.enabled {
-        return;
-    }
-
-    let remaining = (game_end.game_end_time - time.elapsed_secs()).max(0.0);
-    let mins = (remaining / 60.0).floor() as i32;
-    let secs = (remaining % 60.0).floor() as i32;
-    text.0 = format!("{}\n{:02}:{:02}s", BUILD_LABEL, mins, secs);
+    let (mode, seed) = match run_seed.date() {
+        Some(date) => ("hud.seed_daily", date),
+        None => ("hud.seed", run_seed.seed.to_string()),
+    };
+    // scenarios without a time limit show no time
+    let remaining = if game_end.enabled {
+        let remaining = (game_end.game_end_time - tick.elapsed_secs(&fixed)).max(0.0);
+        let mins = (remaining / 60.0).floor() as i32;
+        let secs = (remaining % 60.0).floor() as i32;
+        format!("{:02}:{:02}s", mins, secs)
+    } else {
+        "--:--".to_string()
+    };
+    text.set_if_neq(
+        LocalizedText::new("hud.countdown")
+            .with("build", BUILD_LABEL)
+            .with("mode", LocaleArg::key(mode))
+            .with("seed", seed)
+            .with("time", remaining),
+    );
 }
 
 // --- Music toggle HUD systems ---
@@ -823,6 +737,7 @@ fn handle_music_button(
                     AudioPlayer::new(assets.music_loop.clone()),
                     PlaybackSettings::LOOP,
                     Music,
+                    DespawnOnExit(InGame),
                 ));
             }
         }
@@ -843,21 +758,37 @@ fn handle_orbit_toggle_button(
 
 fn update_orbit_toggle_button_visual(
     show_all: Res<ShowAllOrbits>,
-    mut text_q: Query<&mut Text, With<OrbitToggleButtonText>>,
+    mut text_q: Query<&mut LocalizedText, With<OrbitToggleButtonText>>,
 ) {
     let Ok(mut text) = text_q.single_mut() else { return; };
-    
-    if show_all.0 {
-        text.0 = "ORBIT\nON".to_string();
-    } else {
-        text.0 = "ORBIT\nOFF".to_string();
+
+    text.set_if_neq(LocalizedText::new(if show_all.0 { "hud.orbit_on" } else { "hud.orbit_off" }));
+}
+fn handle_follow_button(
+    q: Query<&Interaction, (With<FollowButton>, Changed<Interaction>)>,
+    mut commands: Commands,
+) {
+    for interaction in &q {
+        if *interaction == Interaction::Pressed {
+            commands.trigger(ToggleCameraFollow);
+        }
     }
 }
+
+fn update_follow_button_visual(
+    follow: Res<CameraFollow>,
+    mut text_q: Query<&mut LocalizedText, With<FollowButtonText>>,
+) {
+    let Ok(mut text) = text_q.single_mut() else { return; };
+
+    text.set_if_neq(LocalizedText::new(if follow.0.is_some() { "hud.follow_on" } else { "hud.follow_off" }));
+}
+
 fn update_highest_earner_display(
     satellite_query: Query<(&Transform, &CollectorStats), With<Satellite>>,
     sun_query: Query<&Transform, With<Sun>>,
-    mut earner_text_query: Query<&mut Text, With<HighestEarnerText>>,
-    mut distance_text_query: Query<&mut Text, (With<HighestEarnerDistanceText>, Without<HighestEarnerText>)>,
+    mut earner_text_query: Query<&mut LocalizedText, With<HighestEarnerText>>,
+    mut distance_text_query: Query<&mut LocalizedText, (With<HighestEarnerDistanceText>, Without<HighestEarnerText>)>,
 ) {
     let Ok(sun_transform) = sun_query.single() else { return; };
     let sun_position = sun_transform.translation;
@@ -875,18 +806,11 @@ fn update_highest_earner_display(
 
     // Update the UI text
     if let Ok(mut text) = earner_text_query.single_mut() {
-        if highest_rate > 0.0 {
-            text.0 = format!("TOP SAT\n+{:.2} YW", highest_rate);
-        } else {
-            text.0 = "TOP SAT\n+0.00 YW".to_string();
-        }
+        text.set_if_neq(top_sat_text(highest_rate));
     }
 
     if let Ok(mut text) = distance_text_query.single_mut() {
-        if closest_distance < f32::MAX {
-            text.0 = format!("DISTANCE {:.0}", closest_distance);
-        } else {
-            text.0 = "DISTANCE 0".to_string();
-        }
+        let distance = if closest_distance < f32::MAX { closest_distance } else { 0.0 };
+        text.set_if_neq(top_sat_distance_text(distance));
     }
 }
\ No newline at end of file
@@ -0,0 +1,375 @@
+//! Step-based tutorial shown in the first runs. Every step names what to do, the action that
+//! completes it and what an arrow points at, a world entity or a HUD element. Actions done ahead
+//! of their step count once the step comes up. Finishing or skipping the tutorial is remembered.
+
+use bevy::ecs::system::SystemParam;
+use bevy::input::touch::TouchInput;
+use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+
+use super::{PANEL_BORDER, OrbitToggleButton, ZoomLevelText};
+use crate::achievements::FullOrbitAchieved;
+use crate::camera::CameraZoomed;
+use crate::controls::{Action, InputBindings, InputDevice};
+use crate::launching::CollectorLaunched;
+use crate::locale::LocalizedText;
+use crate::menus::widget;
+use crate::persistence;
+use crate::replay::{GameplayInput, ReplayPlayback};
+use crate::screens::{InGame, InitGameResource, Screen};
+use crate::sun_system::earth::Earth;
+use crate::sun_system::navigation_instruments::NavigationInstruments;
+use crate::theme::{self, HudTheme, TextSize, ThemeColor, ThemedText};
+use crate::{GameplaySystem, PausableSystems};
+
+/// Stores whether the tutorial was finished or skipped
+const TUTORIAL_KEY: &str = "tutorial.ron";
+/// Gap in pixels between the arrow and the box or its target
+const ARROW_GAP: f32 = 12.0;
+/// Length in pixels of the arrow tip
+const ARROW_TIP: f32 = 12.0;
+
+/// The steps in the order they are shown
+const STEPS: &[TutorialStep] = &[
+    TutorialStep {
+        text: "tutorial.launch",
+        touch_text: "tutorial.touch_launch",
+        action: Some(Action::Launch),
+        trigger: TutorialTrigger::Launched,
+        target: TutorialTarget::Earth,
+    },
+    TutorialStep {
+        text: "tutorial.thruster",
+        touch_text: "tutorial.touch_thruster",
+        action: Some(Action::Thrust),
+        trigger: TutorialTrigger::UsedThruster,
+        target: TutorialTarget::SelectedCollector,
+    },
+    TutorialStep {
+        text: "tutorial.orbit",
+        touch_text: "tutorial.orbit",
+        action: None,
+        trigger: TutorialTrigger::CompletedOrbit,
+        target: TutorialTarget::OrbitButton,
+    },
+    TutorialStep {
+        text: "tutorial.zoom",
+        touch_text: "tutorial.touch_zoom",
+        action: None,
+        trigger: TutorialTrigger::Zoomed,
+        target: TutorialTarget::ZoomLevel,
+    },
+];
+
+pub(super) fn plugin(app: &mut App) {
+    app.init_resource::<TutorialProgress>();
+    app.init_game_resource::<Tutorial>();
+    app.add_systems(OnEnter(Screen::Gameplay), spawn_tutorial.run_if(should_show_tutorial));
+    app.add_observer(on_collector_launched);
+    app.add_observer(on_thruster_input);
+    app.add_observer(on_full_orbit);
+    app.add_observer(on_camera_zoomed);
+    app.add_systems(
+        Update,
+        (
+            detect_touch,
+            handle_tutorial_action,
+            update_tutorial_box,
+            draw_tutorial_arrow,
+        )
+            .chain()
+            .in_set(GameplaySystem)
+            .in_set(PausableSystems),
+    );
+}
+
+struct TutorialStep {
+    /// Key of the instruction
+    text: &'static str,
+    /// Key of the instruction on touch screens
+    touch_text: &'static str,
+    /// Action whose binding is named in the instruction as `{key}`
+    action: Option<Action>,
+    trigger: TutorialTrigger,
+    target: TutorialTarget,
+}
+
+/// Action that completes a step
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum TutorialTrigger {
+    Launched,
+    UsedThruster,
+    CompletedOrbit,
+    Zoomed,
+}
+
+/// What the arrow of a step points at
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum TutorialTarget {
+    Earth,
+    SelectedCollector,
+    OrbitButton,
+    ZoomLevel,
+}
+
+/// Whether the tutorial was finished or skipped in an earlier run
+#[derive(Resource, Debug, Clone, Serialize, Deserialize)]
+struct TutorialProgress {
+    completed: bool,
+}
+
+impl FromWorld for TutorialProgress {
+    fn from_world(_world: &mut World) -> Self {
+        persistence::read(TUTORIAL_KEY)
+            .and_then(|contents| ron::from_str(&contents).ok())
+            .unwrap_or(Self { completed: false })
+    }
+}
+
+impl TutorialProgress {
+    fn complete(&mut self) {
+        self.completed = true;
+        let contents = match ron::ser::to_string(self) {
+            Ok(contents) => contents,
+            Err(e) => {
+                error!("Could not serialize tutorial progress: {e}");
+                return;
+            }
+        };
+        if let Err(e) = persistence::write(TUTORIAL_KEY, &contents) {
+            warn!("Could not save tutorial progress: {e}");
+        }
+    }
+}
+
+/// The tutorial of the current run
+#[derive(Resource, Default)]
+struct Tutorial {
+    /// Whether the tutorial is shown in this run
+    running: bool,
+    step: usize,
+    /// Actions done so far, they complete their step once it comes up
+    done: Vec<TutorialTrigger>,
+    /// Switches to the touch instructions once a touch is seen
+    touch: bool,
+}
+
+impl Tutorial {
+    fn record(&mut self, trigger: TutorialTrigger) {
+        if self.running && !self.done.contains(&trigger) {
+            self.done.push(trigger);
+        }
+    }
+
+    fn current(&self) -> Option<&'static TutorialStep> {
+        STEPS.get(self.step)
+    }
+}
+
+/// The box showing the instruction of the current step
+#[derive(Component)]
+struct TutorialBox;
+
+#[derive(Component)]
+struct TutorialText;
+
+#[derive(Component, Debug, Clone, Copy, PartialEq)]
+enum TutorialAction {
+    Skip,
+}
+
+fn should_show_tutorial(progress: Res<TutorialProgress>, playback: Option<Res<ReplayPlayback>>) -> bool {
+    !progress.completed && playback.is_none()
+}
+
+fn spawn_tutorial(mut commands: Commands, mut tutorial: ResMut<Tutorial>) {
+    tutorial.running = true;
+    commands.spawn((
+        Name::new("Tutorial"),
+        DespawnOnExit(InGame),
+        Node {
+            position_type: PositionType::Absolute,
+            width: Val::Percent(100.0),
+            height: Val::Percent(100.0),
+            justify_content: JustifyContent::Center,
+            align_items: AlignItems::Center,
+            ..default()
+        },
+        Pickable::IGNORE,
+        children![(
+            Node {
+                width: Val::Px(300.0),
+                flex_direction: FlexDirection::Column,
+                border: UiRect::all(Val::Px(PANEL_BORDER)),
+                padding: UiRect::all(Val::Px(8.0)),
+                row_gap: Val::Px(8.0),
+                justify_content: JustifyContent::Center,
+                align_items: AlignItems::Center,
+                ..default()
+            },
+            theme::panel(2.0),
+            TutorialBox,
+            children![
+                (
+                    LocalizedText::new(STEPS[0].text),
+                    ThemedText::new(ThemeColor::Text, TextSize::Body),
+                    TextLayout::new_with_justify(Justify::Center),
+                    TutorialText,
+                    Pickable::IGNORE,
+                ),
+                widget::small_button("tutorial.skip", TutorialAction::Skip),
+            ],
+        )],
+    ));
+}
+
+fn on_collector_launched(_: On<CollectorLaunched>, mut tutorial: ResMut<Tutorial>) {
+    tutorial.record(TutorialTrigger::Launched);
+}
+
+fn on_thruster_input(ev: On<GameplayInput>, mut tutorial: ResMut<Tutorial>) {
+    if matches!(*ev, GameplayInput::ToggleThrusters | GameplayInput::SetSelectedThruster(true)) {
+        tutorial.record(TutorialTrigger::UsedThruster);
+    }
+}
+
+fn on_full_orbit(_: On<FullOrbitAchieved>, mut tutorial: ResMut<Tutorial>) {
+    tutorial.record(TutorialTrigger::CompletedOrbit);
+}
+
+fn on_camera_zoomed(_: On<CameraZoomed>, mut tutorial: ResMut<Tutorial>) {
+    tutorial.record(TutorialTrigger::Zoomed);
+}
+
+fn detect_touch(mut touches: MessageReader<TouchInput>, mut tutorial: ResMut<Tutorial>) {
+    if touches.read().next().is_some() && !tutorial.touch {
+        tutorial.touch = true;
+    }
+}
+
+fn handle_tutorial_action(
+    query: Query<(&Interaction, &TutorialAction), Changed<Interaction>>,
+    mut tutorial: ResMut<Tutorial>,
+) {
+    for (interaction, action) in query.iter() {
+        if *interaction == Interaction::Pressed {
+            match action {
+                TutorialAction::Skip => tutorial.step = STEPS.len(),
+            }
+        }
+    }
+}
+
+/// Moves on past every step whose action was done and finishes the tutorial after the last one
+fn update_tutorial_box(
+    mut commands: Commands,
+    mut tutorial: ResMut<Tutorial>,
+    mut progress: ResMut<TutorialProgress>,
+    tutorial_box: Query<Entity, With<TutorialBox>>,
+    mut text: Query<&mut LocalizedText, With<TutorialText>>,
+    bindings: Res<InputBindings>,
+    device: Res<InputDevice>,
+) {
+    if !tutorial.running {
+        return;
+    }
+    while let Some(step) = tutorial.current() {
+        if !tutorial.done.contains(&step.trigger) {
+            break;
+        }
+        tutorial.step += 1;
+    }
+
+    let Some(step) = tutorial.current() else {
+        tutorial.running = false;
+        progress.complete();
+        for entity in tutorial_box.iter() {
+            commands.entity(entity).despawn();
+        }
+        return;
+    };
+    let content = match step.action {
+        Some(action) if !tutorial.touch => {
+            LocalizedText::new(step.text).with("key", bindings.prompt(action, *device))
+        }
+        _ => LocalizedText::new(if tutorial.touch { step.touch_text } else { step.text }),
+    };
+    for mut text in text.iter_mut() {
+        text.set_if_neq(content.clone());
+    }
+}
+
+/// Finds where the targets of the steps are on the screen, in logical pixels
+#[derive(SystemParam)]
+struct TargetPositions<'w, 's> {
+    camera: Query<'w, 's, (&'static Camera, &'static GlobalTransform), With<Camera2d>>,
+    earth: Query<'w, 's, &'static GlobalTransform, With<Earth>>,
+    selected: Query<'w, 's, &'static GlobalTransform, With<NavigationInstruments>>,
+    orbit_button: Query<'w, 's, (&'static ComputedNode, &'static UiGlobalTransform), With<OrbitToggleButton>>,
+    zoom_level: Query<'w, 's, (&'static ComputedNode, &'static UiGlobalTransform), With<ZoomLevelText>>,
+    ui_scale: Res<'w, UiScale>,
+}
+
+impl TargetPositions<'_, '_> {
+    fn viewport_position(&self, target: TutorialTarget) -> Option<Vec2> {
+        let world = match target {
+            TutorialTarget::Earth => self.earth.iter().next(),
+            TutorialTarget::SelectedCollector => self.selected.iter().next(),
+            TutorialTarget::OrbitButton => return self.orbit_button.iter().next().map(|node| self.node_position(node)),
+            TutorialTarget::ZoomLevel => return self.zoom_level.iter().next().map(|node| self.node_position(node)),
+        }?;
+        let (camera, camera_transform) = self.camera.single().ok()?;
+        camera.world_to_viewport(camera_transform, world.translation()).ok()
+    }
+
+    fn world_position(&self, viewport: Vec2) -> Option<Vec2> {
+        let (camera, camera_transform) = self.camera.single().ok()?;
+        camera.viewport_to_world_2d(camera_transform, viewport).ok()
+    }
+
+    /// Center of a UI node in logical pixels. The inverse scale factor of the node includes the
+    /// [`UiScale`], the camera doesn't know about it.
+    fn node_position(&self, (node, transform): (&ComputedNode, &UiGlobalTransform)) -> Vec2 {
+        transform.translation * node.inverse_scale_factor() * self.ui_scale.0
+    }
+
+    /// Size of a UI node in logical pixels
+    fn node_size(&self, node: &ComputedNode) -> Vec2 {
+        node.size() * node.inverse_scale_factor() * self.ui_scale.0
+    }
+}
+
+/// Draws an arrow from the tutorial box to the target of the current step
+fn draw_tutorial_arrow(
+    mut gizmos: Gizmos,
+    tutorial: Res<Tutorial>,
+    tutorial_box: Query<(&ComputedNode, &UiGlobalTransform), With<TutorialBox>>,
+    targets: TargetPositions,
+    theme: Res<HudTheme>,
+) {
+    let Some(step) = tutorial.current().filter(|_| tutorial.running) else { return; };
+    let Ok((box_node, box_transform)) = tutorial_box.single() else { return; };
+    let Some(target) = targets.viewport_position(step.target) else { return; };
+
+    // start at the edge of the box facing the target
+    let center = targets.node_position((box_node, box_transform));
+    let half_size = targets.node_size(box_node) / 2.0;
+    let direction = (target - center).normalize_or_zero();
+    if direction == Vec2::ZERO {
+        return;
+    }
+    let to_edge = (half_size / direction.abs().max(Vec2::splat(f32::EPSILON))).min_element();
+    let start = center + direction * (to_edge + ARROW_GAP);
+    let end = target - direction * ARROW_GAP;
+    let length = (end - start).dot(direction);
+    // the target is under or right next to the box
+    if length <= ARROW_GAP {
+        return;
+    }
+
+    let (Some(world_start), Some(world_end)) = (targets.world_position(start), targets.world_position(end)) else { return; };
+    let world_per_pixel = world_start.distance(world_end) / length;
+    gizmos
+        .arrow_2d(world_start, world_end, theme.color(ThemeColor::Highlight))
+        .with_tip_length(ARROW_TIP * world_per_pixel);
+}
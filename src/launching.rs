@@ -1,27 +1,32 @@
 #![allow(deprecated)]
-use bevy::color::palettes::basic::GREEN;
-use bevy::color::palettes::css::WHITE;
-use crate::GameplaySystem;
+use crate::{AppSystems, GameplaySystem, PausableSystems};
 use crate::collision::HitBox;
+use crate::controls::{Action, InputDevice, action_just_pressed, action_just_released};
+use crate::gamepad::GamepadAim;
 use crate::physics::calc_gravity::Attractee;
 use crate::physics::directional_forces::{GravityForce, Mass};
 use crate::physics::velocity::Velocity;
 use crate::score::{EnergyRateLabel, Score};
-use crate::sun_system::navigation_instruments::NavigationInstruments;
+use crate::sun_system::navigation_instruments::{NavigationInstruments, OrbitSweep};
 use crate::sun_system::thruster::{Thruster, ThrusterDirection};
 use crate::sun_system::{Level, Satellite, SolarSystemAssets, Sun};
 use crate::sun_system::earth::Earth;
-use bevy::input::common_conditions::{input_just_pressed, input_just_released};
+use crate::replay::{GameplayInput, InputQueue, ReplayPlayback};
+use crate::scenario::ActiveScenario;
+use crate::theme::{TextSize, ThemeColor, ThemedText};
+use crate::screens::{InGame, InitGameResource, Screen};
+use bevy::ecs::system::SystemParam;
 use bevy::prelude::*;
 use bevy::window::PrimaryWindow;
 use bevy::input::touch::{TouchInput, TouchPhase};
+use serde::{Deserialize, Serialize};
 
 #[derive(Component)]
 pub struct LaunchPad;
 
 
 
-#[derive(Resource)]
+#[derive(Resource, Default)]
 pub struct LaunchState {
     pub launched_at_time: Option<f64>,
     pub active_touch: Option<u64>,
@@ -30,7 +35,10 @@ pub struct LaunchState {
 #[derive(Component)]
 pub struct CollectorStats {
     pub energy_rate: f32,
-    pub _total_collected: f32 // future feature
+    /// Energy the collector adds to the energy rate, depends on its level
+    pub output: f32,
+    /// Energy collected over the lifetime of the collector
+    pub total_collected: f32,
 }
 
 #[derive(Component)]
@@ -40,44 +48,63 @@ pub struct Fuel {
 
 #[derive(Component)]
 pub struct FuelLabel;
+
+/// Identifies a collector across a run, collectors are numbered in the order they are spawned
+/// so recorded selections refer to the same collector when a run is replayed
+#[derive(Component, Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CollectorId(pub u32);
+
+/// The id the next collector is given, saved with the run so ids stay unique after loading
+#[derive(Resource, Default, Debug, Clone, Copy, Serialize, Deserialize)]
+pub struct NextCollectorId(pub u32);
+
 #[derive(Resource)]
 pub struct SatellitePriceFactor{
     pub factor:f32,
 }
 
-#[derive(Resource)]
-pub struct LaunchArmed(pub bool);
-
-impl Default for LaunchArmed {
-    fn default() -> Self { Self(false) }
-}
-
 #[derive(Resource, Default)]
 pub struct ThrusterTouch {
     pub active_touch_id: Option<u64>,
 }
 
 pub(super) fn plugin(app: &mut App) {
-    app.init_resource::<LaunchArmed>();
-    app.init_resource::<ThrusterTouch>();
+    app.init_game_resource::<ThrusterTouch>();
+    app.init_game_resource::<LaunchState>();
+    app.init_game_resource::<NextCollectorId>();
+    app.add_observer(queue_launch);
+    app.add_observer(apply_collector_input);
+    app.add_observer(assign_collector_id);
     app.add_systems(
         Update,
         (
-            start_new_launch.run_if(input_just_released(MouseButton::Left)),
-            record_launch_time.run_if(input_just_pressed(MouseButton::Left)),
-            deactivate_old_sats.run_if(input_just_pressed(MouseButton::Left)),
-            update_fuel_label,
-            arm_launch_on_earth_tap,
-            record_touch_start,
-            start_launch_from_touch_end,
+            release_launch.run_if(action_just_released(Action::Launch)),
+            charge_launch.run_if(action_just_pressed(Action::Launch).and(not(pointer_over_button))),
+            deactivate_old_sats.run_if(action_just_pressed(Action::Launch).and(not(pointer_over_button))),
+            charge_touch_launch,
+            release_touch_launch,
             select_satellite_on_touch,
             sun_thruster_touch,
+            cycle_selection::<1>.run_if(action_just_pressed(Action::SelectNext)),
+            cycle_selection::<-1>.run_if(action_just_pressed(Action::SelectPrevious)),
         )
             .chain()
-            .in_set(GameplaySystem),
+            .in_set(AppSystems::RecordInput)
+            .in_set(GameplaySystem)
+            .in_set(PausableSystems),
     );
-    app.insert_resource(LaunchState { launched_at_time: None, active_touch: None });
+    app.add_systems(Update, update_fuel_label.in_set(GameplaySystem).in_set(PausableSystems));
     app.insert_resource(SatellitePriceFactor { factor: 500. });
+    app.add_systems(OnEnter(Screen::Gameplay), apply_scenario_price);
+}
+
+/// Clicks on HUD buttons neither launch nor deselect, the gamepad doesn't click
+fn pointer_over_button(buttons: Query<&Interaction>, device: Res<InputDevice>) -> bool {
+    *device == InputDevice::KeyboardMouse && buttons.iter().any(|interaction| *interaction != Interaction::None)
+}
+
+fn apply_scenario_price(mut price: ResMut<SatellitePriceFactor>, scenario: Res<ActiveScenario>) {
+    price.factor = scenario.0.satellite_price;
 }
 
 pub fn make_launchpad() -> impl Bundle {
@@ -88,100 +115,267 @@ pub fn make_launchpad() -> impl Bundle {
     )
 }
 
-fn start_new_launch(
-    mut commands: Commands,
-    launch_pad_query: Query<&Transform, With<LaunchPad>>,
-    window_q: Query<&Window, With<PrimaryWindow>>,
-    camera_query: Query<(&Camera, &GlobalTransform)>,
-    solar_system_assets: Res<SolarSystemAssets>,
-    mut launch_state: ResMut<LaunchState>,
-    time: Res<Time>,
-    mut score: ResMut<Score>,
-    satellite_price_factor: Res<SatellitePriceFactor>,
-    current_marked: Query<Entity, With<NavigationInstruments>>,
-) {
+/// Where launches go: towards the pointer, or along the reticle while a gamepad is in use
+#[derive(SystemParam)]
+struct LaunchAim<'w, 's> {
+    launch_pad: Query<'w, 's, &'static GlobalTransform, With<LaunchPad>>,
+    camera: Query<'w, 's, (&'static Camera, &'static GlobalTransform)>,
+    window: Query<'w, 's, &'static Window, With<PrimaryWindow>>,
+    device: Res<'w, InputDevice>,
+    gamepad: Res<'w, GamepadAim>,
+}
 
-    let Some(launch_pad_transform) = launch_pad_query.iter().next() else { return; };
-    let launch_position = launch_pad_transform.translation;
+impl LaunchAim<'_, '_> {
+    /// Direction from the launch pad towards a point on screen
+    fn towards(&self, screen_pos: Vec2) -> Option<Vec2> {
+        let launch_pad = self.launch_pad.iter().next()?;
+        let (camera, camera_transform) = self.camera.iter().next()?;
+        let world_pos = camera.viewport_to_world_2d(camera_transform, screen_pos).ok()?;
+        Some(world_pos - launch_pad.translation().xy())
+    }
 
-    let Some((camera, camera_transform)) = camera_query.iter().next() else { return; };
+    /// Direction of the reticle or of the mouse cursor
+    fn current(&self) -> Option<Vec2> {
+        if *self.device == InputDevice::Gamepad {
+            return Some(self.gamepad.0);
+        }
+        let cursor = self.window.iter().next()?.cursor_position()?;
+        self.towards(cursor)
+    }
+}
 
-    let Some(window) = window_q.iter().next() else { return; };
-    let launch_direction = if let Some(cursor_pos) = window.cursor_position() {
-        if let Ok(world_pos) = camera.viewport_to_world_2d(camera_transform, cursor_pos) {
-            (world_pos.extend(0.0) - launch_position).normalize()
-        } else {
-            return;
+impl LaunchState {
+    fn begin_charge(&mut self, time: &Time) {
+        if self.launched_at_time.is_none() {
+            self.launched_at_time = Some(time.elapsed_secs_f64());
         }
-    } else {
-        return;
-    };
+    }
 
-    info!("Launching new satellite towards {:?}", launch_direction);
+    /// Ends the charge, the power is how long it was held in seconds
+    fn take_power(&mut self, time: &Time) -> Option<f32> {
+        let launch_start_time = self.launched_at_time.take()?;
+        Some((time.elapsed_secs_f64() - launch_start_time) as f32)
+    }
+}
 
-    //force is dependent on how long the mouse was held down
-    let mut force_multiplier = if let Some(launch_start_time) = launch_state.launched_at_time {
-        let held_duration = time.elapsed_secs_f64() - launch_start_time;
-        held_duration.min(1.0) //cap at 1 secs
-    } else {
-        0.1
-    };
+fn can_afford_launch(score: &Score, price: &SatellitePriceFactor) -> bool {
+    let (_, cost) = launch_cost(score.energy_stored, price);
+    score.energy_stored >= cost
+}
 
-    force_multiplier = force_multiplier * 10.0;
-    let sprite ;
-    let lvl ;
-    if score.energy_stored > 10000. && score.energy_stored <20000. {
-        lvl=2.;
-        sprite = solar_system_assets.collector2.clone();
-    }else if score.energy_stored >20000. {
-        lvl=3.;
-        sprite = solar_system_assets.collector3.clone();
-    }else{
-        lvl=1.;
-        sprite= solar_system_assets.collector.clone();        
+/// Every launch goes through the input queue so replays launch the same collectors, whether it
+/// can be paid for is checked when the input is applied
+fn queue_launch(
+    ev: On<LaunchRequest>,
+    mut inputs: ResMut<InputQueue>,
+    playback: Option<Res<ReplayPlayback>>,
+) {
+    // the recorded launches are used while watching a replay
+    if playback.is_some() { return; }
+    let direction = ev.direction.normalize_or_zero();
+    if direction == Vec2::ZERO { return; }
+    inputs.push(GameplayInput::Launch { direction, held: ev.power });
+}
+
+fn charge_launch(
+    time: Res<Time>,
+    mut launch_state: ResMut<LaunchState>,
+    score: Res<Score>,
+    price: Res<SatellitePriceFactor>,
+) {
+    if can_afford_launch(&score, &price) {
+        launch_state.begin_charge(&time);
     }
-    info!("Pay energy");
-    if score.energy_stored >= satellite_price_factor.factor {
-        score.energy_stored -= satellite_price_factor.factor*lvl;
+}
+
+/// Releasing the launch action launches towards the cursor or the reticle
+fn release_launch(
+    mut commands: Commands,
+    aim: LaunchAim,
+    mut launch_state: ResMut<LaunchState>,
+    time: Res<Time>,
+) {
+    // a touch is charging, it launches when lifted
+    if launch_state.active_touch.is_some() { return; }
+    // only launch if the press was recorded, e.g. not when the button was pressed in a menu
+    let Some(power) = launch_state.take_power(&time) else { return; };
+    let Some(direction) = aim.current() else { return; };
+    commands.trigger(LaunchRequest { direction, power });
+}
+
+/// A collector was launched from earth and paid for
+#[derive(Event, Debug)]
+pub struct CollectorLaunched;
+
+/// Asks for a collector to be launched from earth towards `direction`. Mouse, touch and gamepad
+/// all end up here, as may anything else that wants to launch. `power` is how long the launch
+/// was charged in seconds.
+#[derive(Event, Debug, Clone, Copy)]
+pub struct LaunchRequest {
+    pub direction: Vec2,
+    pub power: f32,
+}
+
+/// The level of the next collector and what it costs, richer players launch better collectors
+pub fn launch_cost(energy_stored: f32, price: &SatellitePriceFactor) -> (f32, f32) {
+    let lvl = if energy_stored > 20000. {
+        3.
+    } else if energy_stored > 10000. {
+        2.
     } else {
+        1.
+    };
+    (lvl, price.factor * lvl)
+}
+
+/// Launches a collector from earth, paid from the stored energy. The launch force depends on
+/// how long the launch was held, capped at one second.
+fn launch_collector(
+    commands: &mut Commands,
+    launch: &LaunchParams,
+    score: &mut Score,
+    direction: Vec2,
+    held: f32,
+) {
+    let Some((earth_transform, earth_velocity)) = launch.earth.iter().next() else { return; };
+    let Some(launch_pad) = launch.launch_pad.iter().next() else { return; };
+    // the pad is placed from earth on the fixed tick the launch is applied, not from its global
+    // transform of the last frame, so replays launch from the same spot
+    let launch_position = earth_transform.transform_point(launch_pad.translation);
+    let force_multiplier = held.min(1.0) * 10.0;
+
+    let (lvl, cost) = launch_cost(score.energy_stored, &launch.price);
+    if score.energy_stored < cost {
         return;
     }
+    info!("Launching new satellite towards {:?}", direction);
+    score.energy_stored -= cost;
+    score.energy_spent += cost;
     // Ensure only the newly launched satellite will be selected
-    for e in current_marked.iter() {
+    deselect_all(commands, &launch.selected);
+    spawn_collector(
+        commands,
+        &launch.assets,
+        lvl,
+        launch_position + direction.extend(0.0),
+        // satellites leave earth with its orbital velocity on top of the launch impulse
+        direction * force_multiplier + earth_velocity.0,
+        None,
+    );
+    commands.trigger(CollectorLaunched);
+}
+
+fn deselect_all(commands: &mut Commands, selected: &Query<Entity, With<NavigationInstruments>>) {
+    for e in selected.iter() {
         commands.entity(e).remove::<NavigationInstruments>();
     }
-let collector_id = commands.spawn((
+}
+
+/// What applying a launch needs besides the score
+#[derive(SystemParam)]
+struct LaunchParams<'w, 's> {
+    assets: Res<'w, SolarSystemAssets>,
+    price: Res<'w, SatellitePriceFactor>,
+    earth: Query<'w, 's, (&'static Transform, &'static Velocity), With<Earth>>,
+    launch_pad: Query<'w, 's, &'static Transform, With<LaunchPad>>,
+    selected: Query<'w, 's, Entity, With<NavigationInstruments>>,
+}
+
+/// Applies the gameplay inputs that launch, place and select collectors
+fn apply_collector_input(
+    ev: On<GameplayInput>,
+    mut commands: Commands,
+    mut score: ResMut<Score>,
+    launch: LaunchParams,
+    mut selected_thrusters: Query<&mut Thruster, With<NavigationInstruments>>,
+    collectors: Query<(Entity, &CollectorId)>,
+) {
+    match *ev {
+        GameplayInput::Launch { direction, held } => {
+            launch_collector(&mut commands, &launch, &mut score, direction, held);
+        }
+        GameplayInput::Place { position, velocity } => {
+            info!("Placing collector at {position} with velocity {velocity}");
+            // the placed collector becomes the selected one
+            deselect_all(&mut commands, &launch.selected);
+            spawn_collector(&mut commands, &launch.assets, 1.0, position.extend(0.0), velocity, None);
+        }
+        GameplayInput::Select(id) => {
+            let Some((target, _)) = collectors.iter().find(|(_, collector)| **collector == id) else { return; };
+            commands.entity(target).insert(NavigationInstruments);
+            // remove selection from others
+            for e in launch.selected.iter() {
+                if e != target {
+                    commands.entity(e).remove::<NavigationInstruments>();
+                }
+            }
+        }
+        GameplayInput::Decommission(id) => {
+            let Some((target, _)) = collectors.iter().find(|(_, collector)| **collector == id) else { return; };
+            info!("Decommissioning collector {}", id.0);
+            commands.entity(target).despawn();
+        }
+        GameplayInput::ReleaseSelection => {
+            for mut thr in selected_thrusters.iter_mut() {
+                // turn off, don't remove the thruster component
+                thr.active = false;
+            }
+            deselect_all(&mut commands, &launch.selected);
+        }
+        _ => {}
+    }
+}
+
+/// Gives every new collector the next [`CollectorId`], collectors spawned with a saved id keep it
+fn assign_collector_id(ev: On<Add, Satellite>, mut commands: Commands, mut next_id: ResMut<NextCollectorId>) {
+    commands.entity(ev.entity).insert_if_new(CollectorId(next_id.0));
+    next_id.0 += 1;
+}
+
+/// Spawns a freshly launched collector of the given level, including its floating labels. Without
+/// an `id` the collector is given the next free one.
+pub fn spawn_collector(
+    commands: &mut Commands,
+    solar_system_assets: &SolarSystemAssets,
+    lvl: f32,
+    translation: Vec3,
+    velocity: Vec2,
+    id: Option<CollectorId>,
+) -> Entity {
+    let mut collector = commands.spawn((
         Fuel { amount: 1.5 },
         Level { level: lvl },
         Attractee,
         GravityForce::default(),
-        Velocity(launch_direction.xy() * Vec2::splat(force_multiplier as f32)),
+        Velocity(velocity),
         Mass(1.0),
-        Transform::from_translation(launch_position + launch_direction)
+        Transform::from_translation(translation)
             .with_scale(Vec3::splat(0.015)),
-        Sprite::from(sprite),
-        TextColor(Color::from(GREEN)),
+        Sprite::from(collector_sprite(solar_system_assets, lvl)),
         Thruster::new(ThrusterDirection::Retrograde, 2.0),
         HitBox { radius: 8.0 },
         NavigationInstruments,
+        OrbitSweep::default(),
         Satellite,
         CollectorStats {
             energy_rate: 0.0,
-            _total_collected: 0.0,
+            output: 0.0,
+            total_collected: 0.0,
         },
         Pickable::default(),
-    ))
+    ));
+    if let Some(id) = id {
+        collector.insert(id);
+    }
+    let collector_id = collector
+        .insert(DespawnOnExit(InGame))
         .observe(on_hover_collector_over)
         .id();
 
     commands.spawn((
         Text2d::new("0"),
         Transform::default().with_translation(Vec3::new(0.0, -600.0, 0.0)).with_scale(Vec3::splat(10.0)),
-        TextFont {
-            font_size: 27.0,
-            ..default()
-        },
-        TextColor(Color::from(GREEN)),
+        ThemedText::new(ThemeColor::Accent, TextSize::Label),
         ChildOf(collector_id),
         EnergyRateLabel,
         Pickable::IGNORE,
@@ -190,212 +384,92 @@ let collector_id = commands.spawn((
     commands.spawn((
         Text2d::new("0"),
         Transform::default().with_translation(Vec3::new(0.0, -1000.0, 0.0)).with_scale(Vec3::splat(10.0)),
-        TextFont {
-            font_size: 27.0,
-            ..default()
-        },
-        TextColor(Color::from(WHITE)),
+        ThemedText::new(ThemeColor::Text, TextSize::Label),
         ChildOf(collector_id),
         FuelLabel,
         Visibility::Visible,
         Pickable::IGNORE,
     ));
 
-    launch_state.launched_at_time = None;
+    collector_id
 }
 
-fn screen_to_world(
-    camera_query: &Query<(&Camera, &GlobalTransform)>,
-    window_q: &Query<&Window, With<PrimaryWindow>>,
-    screen_pos: Vec2,
-) -> Option<Vec2> {
-    let (camera, cam_gt) = camera_query.iter().next()?;
-    let _ = window_q.iter().next()?; // ensure window exists
-    camera.viewport_to_world_2d(cam_gt, screen_pos).ok()
+/// The sprite a collector of the given level is drawn with
+pub fn collector_sprite(solar_system_assets: &SolarSystemAssets, lvl: f32) -> Handle<Image> {
+    if lvl >= 3. {
+        solar_system_assets.collector3.clone()
+    } else if lvl >= 2. {
+        solar_system_assets.collector2.clone()
+    } else {
+        solar_system_assets.collector.clone()
+    }
 }
 
-fn record_touch_start(
+/// Touching down on earth charges a launch, like pressing the launch action
+fn charge_touch_launch(
     mut er_touch: EventReader<TouchInput>,
     time: Res<Time>,
     mut st: ResMut<LaunchState>,
     score: Res<Score>,
-    launch_armed: Res<LaunchArmed>,
     price: Res<SatellitePriceFactor>,
+    camera_query: Query<(&Camera, &GlobalTransform)>,
+    earth_q: Query<(&GlobalTransform, &HitBox), With<Earth>>,
 ) {
-    if !launch_armed.0 { return; }
-    if score.energy_stored < price.factor { return; }
+    if !can_afford_launch(&score, &price) { return; }
     if st.active_touch.is_some() { return; }
+    let Some((camera, cam_gt)) = camera_query.iter().next() else { return; };
+    let Some((earth, hitbox)) = earth_q.iter().next() else { return; };
+
     for t in er_touch.read() {
-        if t.phase == TouchPhase::Started {
-            st.launched_at_time = Some(time.elapsed_secs_f64());
+        if t.phase != TouchPhase::Started { continue; }
+        let Ok(world_pos) = camera.viewport_to_world_2d(cam_gt, t.position) else { continue; };
+        if earth.translation().truncate().distance(world_pos) <= hitbox.radius {
+            st.begin_charge(&time);
             st.active_touch = Some(t.id);
             break;
         }
     }
 }
 
-fn start_launch_from_touch_end(
-    mut er_touch: EventReader<TouchInput>,
+/// Lifting the charging touch launches towards where it was lifted
+fn release_touch_launch(
     mut commands: Commands,
-    launch_pad_query: Query<&Transform, With<LaunchPad>>,
-    window_q: Query<&Window, With<PrimaryWindow>>,
-    camera_query: Query<(&Camera, &GlobalTransform)>,
-    solar_system_assets: Res<SolarSystemAssets>,
+    mut er_touch: EventReader<TouchInput>,
+    aim: LaunchAim,
     mut st: ResMut<LaunchState>,
-    mut launch_armed: ResMut<LaunchArmed>,
-    mut score: ResMut<Score>,
-    current_marked: Query<Entity, With<NavigationInstruments>>,
     time: Res<Time>,
-    price: Res<SatellitePriceFactor>
 ) {
-    let Some(launch_pad_transform) = launch_pad_query.iter().next() else { return; };
-    let launch_position = launch_pad_transform.translation;
-
-    if !launch_armed.0 { return; }
-
-    // Only launch if we have an active touch and we see its matching Ended
     let Some(active_id) = st.active_touch else { return; };
-    let mut screen_pos: Option<Vec2> = None;
-    for t in er_touch.read() {
-        if t.phase == TouchPhase::Ended && t.id == active_id {
-            screen_pos = Some(t.position);
-            break;
-        }
-    }
-    let Some(screen_pos) = screen_pos else { return; };
-
-    let Some(world_pos) = screen_to_world(&camera_query, &window_q, screen_pos) else { return; };
-    let launch_direction = (world_pos.extend(0.0) - launch_position).normalize_or_zero();
-    if launch_direction == Vec3::ZERO { return; }
-
-    //force is dependent on how long the touch was held, same as mouse
-    let mut force_multiplier = if let Some(launch_start_time) = st.launched_at_time {
-        let held_duration = time.elapsed_secs_f64() - launch_start_time;
-        held_duration.min(1.0) // cap at 1 sec
-    } else {
-        0.1
-    };
-    force_multiplier *= 10.0;
-
-    let sprite;
-    let lvl;
-    if score.energy_stored > 10000. && score.energy_stored <20000. {
-        lvl=2.;
-        sprite = solar_system_assets.collector2.clone();
-    }else if score.energy_stored >20000. {
-        lvl=3.;
-        sprite = solar_system_assets.collector3.clone();
-    }else{
-        lvl=1.;
-        sprite= solar_system_assets.collector.clone();        
-    }
-    info!("Pay energy");
-    if score.energy_stored >= price.factor {
-        score.energy_stored -= price.factor*lvl;
-    } else {
+    let Some(touch) = er_touch
+        .read()
+        .find(|t| t.id == active_id && matches!(t.phase, TouchPhase::Ended | TouchPhase::Canceled))
+    else {
         return;
-    }
-    // Ensure only the newly launched satellite will be selected
-    for e in current_marked.iter() {
-        commands.entity(e).remove::<NavigationInstruments>();
-    }
-    let collector_id = commands.spawn((
-        Fuel { amount: 1.5 },
-        Level { level: lvl },
-        Attractee,
-        GravityForce::default(),
-        Velocity(launch_direction.xy() * Vec2::splat(force_multiplier as f32)),
-        Mass(1.0),
-        Transform::from_translation(launch_position + launch_direction)
-            .with_scale(Vec3::splat(0.015)),
-        Sprite::from(sprite),
-        TextColor(Color::from(GREEN)),
-        Thruster::new(ThrusterDirection::Retrograde, 2.0),
-        HitBox { radius: 8.0 },
-        NavigationInstruments,
-        Satellite,
-        CollectorStats {
-            energy_rate: 0.0,
-            _total_collected: 0.0,
-        },
-        Pickable::default(),
-    ))
-        .observe(on_hover_collector_over)
-        .id();
-
-    commands.spawn((
-        Text2d::new("0"),
-        Transform::default().with_translation(Vec3::new(0.0, -600.0, 0.0)).with_scale(Vec3::splat(10.0)),
-        TextFont {
-            font_size: 27.0,
-            ..default()
-        },
-        TextColor(Color::from(GREEN)),
-        ChildOf(collector_id),
-        EnergyRateLabel,
-        Pickable::IGNORE,
-    ));
-
-    commands.spawn((
-        Text2d::new("0"),
-        Transform::default().with_translation(Vec3::new(0.0, -1000.0, 0.0)).with_scale(Vec3::splat(10.0)),
-        TextFont {
-            font_size: 27.0,
-            ..default()
-        },
-        TextColor(Color::from(WHITE)),
-        ChildOf(collector_id),
-        FuelLabel,
-        Visibility::Visible,
-        Pickable::IGNORE,
-    ));
-
-    // disarm after launch
-    launch_armed.0 = false;
-
-    st.launched_at_time = None;
+    };
     st.active_touch = None;
+    let Some(power) = st.take_power(&time) else { return; };
+    if touch.phase == TouchPhase::Canceled { return; }
+    let Some(direction) = aim.towards(touch.position) else { return; };
+    commands.trigger(LaunchRequest { direction, power });
 }
 
 fn on_hover_collector_over(
     ev: On<Pointer<Over>>,
-    mut commands: Commands,
-    query: Query<Entity, With<NavigationInstruments>>,
+    collectors: Query<&CollectorId>,
+    mut inputs: ResMut<InputQueue>,
+    playback: Option<Res<ReplayPlayback>>,
 ) {
+    // the recorded selection is used while watching a replay
+    if playback.is_some() { return; }
     // Hover only indicates potential selection; do not modify thrusters here
-    //println!("hover over collector {:?}", ev.entity);
-    commands.entity(ev.entity).insert(NavigationInstruments);
-
-    // Remove selection marker from others
-    for entity in query.iter() {
-        if entity != ev.entity {
-            commands.entity(entity).remove::<NavigationInstruments>();
-        }
-    }
+    let Ok(id) = collectors.get(ev.entity) else { return; };
+    inputs.push(GameplayInput::Select(*id));
 }
 
 
 
-fn record_launch_time(time: Res<Time>, mut launch_state: ResMut<LaunchState>, score: Res<Score>,
-                      price: Res<SatellitePriceFactor>) {
-    if score.energy_stored < price.factor {
-        return;
-    }
-    if launch_state.launched_at_time.is_none() {
-        launch_state.launched_at_time = Some(time.elapsed_secs_f64());
-    }
-}
-
-fn deactivate_old_sats(
-    mut commands: Commands,
-    mut thruster_query: Query<(Entity, &mut Thruster), With<NavigationInstruments>>,
-) {
-    for (entity, mut thr) in thruster_query.iter_mut() {
-        // turn off, don't remove the thruster component
-        thr.active = false;
-        // remove navigation instruments marker
-        commands.entity(entity).remove::<NavigationInstruments>();
-    }
+fn deactivate_old_sats(mut inputs: ResMut<InputQueue>) {
+    inputs.push(GameplayInput::ReleaseSelection);
 }
 
 fn update_fuel_label(
@@ -424,12 +498,11 @@ fn select_satellite_on_touch(
     mut er_touch: EventReader<TouchInput>,
     window_q: Query<&Window, With<PrimaryWindow>>,
     camera_query: Query<(&Camera, &GlobalTransform, &Transform)>,
-    mut commands: Commands,
-    sats: Query<(Entity, &GlobalTransform, &HitBox), With<Satellite>>,
-    current_marked: Query<Entity, With<NavigationInstruments>>,
+    sats: Query<(&CollectorId, &GlobalTransform, &HitBox), With<Satellite>>,
+    mut inputs: ResMut<InputQueue>,
 ) {
     let Some((camera, cam_gt, cam_t)) = camera_query.iter().next() else { return; };
-    let _ = window_q.iter().next() else { return; };
+    if window_q.is_empty() { return; }
 
     // Determine zoom factor from camera transform scale (uniform scale expected)
     let zoom = cam_t.scale.x.max(0.0001);
@@ -438,7 +511,7 @@ fn select_satellite_on_touch(
         if t.phase != TouchPhase::Ended { continue; }
         let Ok(world_pos) = camera.viewport_to_world_2d(cam_gt, t.position) else { continue; };
 
-        let mut best: Option<(Entity, f32)> = None;
+        let mut best: Option<(CollectorId, f32)> = None;
         for (e, gt, hb) in sats.iter() {
             let sat_pos = gt.translation().truncate();
             let dist = sat_pos.distance(world_pos);
@@ -446,49 +519,38 @@ fn select_satellite_on_touch(
             let radius = hb.radius / zoom;
             if dist <= radius {
                 if let Some((_, best_dist)) = best {
-                    if dist < best_dist { best = Some((e, dist)); }
+                    if dist < best_dist { best = Some((*e, dist)); }
                 } else {
-                    best = Some((e, dist));
+                    best = Some((*e, dist));
                 }
             }
         }
 
         if let Some((target, _)) = best {
             // Single tap: select only (NavigationInstruments)
-            commands.entity(target).insert(NavigationInstruments);
-            // remove selection from others
-            for e in current_marked.iter() {
-                if e != target {
-                    commands.entity(e).remove::<NavigationInstruments>();
-                }
-            }
+            inputs.push(GameplayInput::Select(target));
         }
     }
 }
 
-fn arm_launch_on_earth_tap(
-    mut er_touch: EventReader<TouchInput>,
-    window_q: Query<&Window, With<PrimaryWindow>>,
-    camera_query: Query<(&Camera, &GlobalTransform)>,
-    earth_q: Query<(&GlobalTransform, Option<&HitBox>), With<Earth>>,
-    mut launch_armed: ResMut<LaunchArmed>,
+/// Selects the collector with the next or previous id, wrapping around
+fn cycle_selection<const STEP: i32>(
+    collectors: Query<&CollectorId, With<Satellite>>,
+    selected: Query<&CollectorId, With<NavigationInstruments>>,
+    mut inputs: ResMut<InputQueue>,
 ) {
-    let Some((camera, cam_gt)) = camera_query.iter().next() else { return; };
-    let _ = window_q.iter().next() else { return; };
-
-    for t in er_touch.read() {
-        if t.phase != TouchPhase::Started { continue; }
-        let Ok(world_pos) = camera.viewport_to_world_2d(cam_gt, t.position) else { continue; };
-        for (gt, hb_opt) in earth_q.iter() {
-            let earth_pos = gt.translation().truncate();
-            let dist = earth_pos.distance(world_pos);
-            let radius = hb_opt.map(|hb| hb.radius).unwrap_or(20.0);
-            if dist <= radius {
-                launch_armed.0 = true;
-                return;
-            }
-        }
+    let mut ids: Vec<u32> = collectors.iter().map(|id| id.0).collect();
+    if ids.is_empty() {
+        return;
     }
+    ids.sort_unstable();
+    let current = selected.iter().next().and_then(|current| ids.iter().position(|id| *id == current.0));
+    let index = match current {
+        Some(index) => (index as i32 + STEP).rem_euclid(ids.len() as i32) as usize,
+        None if STEP > 0 => 0,
+        None => ids.len() - 1,
+    };
+    inputs.push(GameplayInput::Select(CollectorId(ids[index])));
 }
 
 fn sun_thruster_touch(
@@ -496,11 +558,12 @@ fn sun_thruster_touch(
     window_q: Query<&Window, With<PrimaryWindow>>,
     camera_query: Query<(&Camera, &GlobalTransform)>,
     sun_q: Query<(&GlobalTransform, &HitBox), With<Sun>>,
-    mut selected_thruster: Query<(&mut Thruster, Entity), (With<NavigationInstruments>, With<Thruster>)>,
+    selected_thruster: Query<(), (With<NavigationInstruments>, With<Thruster>)>,
     mut thr_touch: ResMut<ThrusterTouch>,
+    mut inputs: ResMut<InputQueue>,
 ) {
     let Some((camera, cam_gt)) = camera_query.iter().next() else { return; };
-    let _ = window_q.iter().next() else { return; };
+    if window_q.is_empty() { return; }
 
     for t in er_touch.read() {
         match t.phase {
@@ -513,21 +576,17 @@ fn sun_thruster_touch(
                     let dist = sun_pos.distance(world_pos);
                     let radius = hb.radius;
                     if dist <= radius {
-                        if let Ok((mut thr, _)) = selected_thruster.single_mut() {
-                            thr.active = true;
+                        if selected_thruster.single().is_ok() {
+                            inputs.push(GameplayInput::SetSelectedThruster(true));
                             thr_touch.active_touch_id = Some(t.id);
                         }
                         break;
                     }
                 }
             }
-            TouchPhase::Ended | TouchPhase::Canceled => {
-                if thr_touch.active_touch_id == Some(t.id) {
-                    if let Ok((mut thr, _)) = selected_thruster.single_mut() {
-                        thr.active = false;
-                    }
-                    thr_touch.active_touch_id = None;
-                }
+            TouchPhase::Ended | TouchPhase::Canceled if thr_touch.active_touch_id == Some(t.id) => {
+                inputs.push(GameplayInput::SetSelectedThruster(false));
+                thr_touch.active_touch_id = None;
             }
             _ => {}
         }
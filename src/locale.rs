@@ -0,0 +1,304 @@
+//! Localization. Every player-facing string is looked up by key in the string table of the picked
+//! language, tables live in `assets/locales/*.locale.ron` and the language is picked in the
+//! settings menu. Static UI text uses [`LocalizedText`], systems that build strings every frame
+//! read [`Localization`] directly. Numbers are formatted with the separators of the language.
+
+use bevy::asset::io::Reader;
+use bevy::asset::{AssetLoader, LoadContext};
+use bevy::ecs::system::SystemParam;
+use bevy::prelude::*;
+use bevy::ui::UiSystems;
+use serde::{Deserialize, Serialize};
+use std::borrow::Cow;
+use std::collections::HashMap;
+
+use crate::asset_tracking::LoadResource;
+use crate::persistence;
+
+/// Languages offered in the settings menu, the first one is the fallback for missing strings.
+/// Listed explicitly because web builds can't load whole folders.
+const LOCALE_FILES: &[&str] = &["locales/en.locale.ron", "locales/de.locale.ron"];
+/// Stores the code of the picked language
+const LANGUAGE_KEY: &str = "language.ron";
+
+pub(super) fn plugin(app: &mut App) {
+    app.init_asset::<Locale>();
+    app.init_asset_loader::<LocaleLoader>();
+    app.init_resource::<Localization>();
+    app.load_resource::<LocaleAssets>();
+    app.add_systems(Update, apply_saved_language.run_if(resource_added::<LocaleAssets>));
+    app.add_systems(
+        PostUpdate,
+        apply_localized_text.in_set(ApplyLocale).before(UiSystems::Prepare),
+    );
+}
+
+/// String table of one language
+#[derive(Asset, TypePath, Debug, Clone, Default)]
+pub struct Locale {
+    /// Name of the language in the language itself
+    pub name: String,
+    /// ISO 639-1 code, e.g. `de`
+    pub code: String,
+    decimal_separator: String,
+    grouping_separator: String,
+    strings: HashMap<String, String>,
+}
+
+/// The picked language and the fallback for strings it doesn't translate
+#[derive(Resource, Debug, Clone, Default)]
+pub struct Localization {
+    current: Locale,
+    fallback: Locale,
+}
+
+impl Localization {
+    pub fn language(&self) -> &str {
+        &self.current.name
+    }
+
+    /// The string stored under `key`. Keys missing from every table are shown as they are, so
+    /// names from data files can be passed in without a table entry.
+    pub fn get<'a>(&'a self, key: &'a str) -> &'a str {
+        self.current
+            .strings
+            .get(key)
+            .or_else(|| self.fallback.strings.get(key))
+            .map_or(key, String::as_str)
+    }
+
+    /// The string stored under `key` with every `{name}` replaced by its argument
+    pub fn format(&self, key: &str, args: &[(impl AsRef<str>, LocaleArg)]) -> String {
+        let mut text = self.get(key).to_string();
+        for (name, arg) in args {
+            text = text.replace(&format!("{{{}}}", name.as_ref()), &self.arg(arg));
+        }
+        text
+    }
+
+    /// `value` rounded to `decimals` places, with the decimal and thousands separators of the
+    /// language
+    pub fn number(&self, value: f32, decimals: usize) -> String {
+        let formatted = format!("{:.*}", decimals, value.abs());
+        let (integer, fraction) = formatted.split_once('.').unwrap_or((&formatted, ""));
+        let mut grouped = String::new();
+        for (i, digit) in integer.chars().enumerate() {
+            if i > 0 && (integer.len() - i) % 3 == 0 {
+                grouped.push_str(&self.current.grouping_separator);
+            }
+            grouped.push(digit);
+        }
+        // no minus in front of values that round to zero
+        let negative = value < 0.0 && formatted.chars().any(|c| c.is_ascii_digit() && c != '0');
+        let sign = if negative { "-" } else { "" };
+        if fraction.is_empty() {
+            format!("{sign}{grouped}")
+        } else {
+            format!("{sign}{grouped}{}{fraction}", self.current.decimal_separator)
+        }
+    }
+
+    fn arg(&self, arg: &LocaleArg) -> String {
+        match arg {
+            LocaleArg::Text(text) => text.clone(),
+            LocaleArg::Key(key) => self.get(key).to_string(),
+            LocaleArg::Number { value, decimals } => self.number(*value, *decimals),
+        }
+    }
+}
+
+/// Value filled into a placeholder of a string
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub enum LocaleArg {
+    /// Shown as it is
+    Text(String),
+    /// Looked up in the string table
+    Key(String),
+    Number { value: f32, decimals: usize },
+}
+
+impl LocaleArg {
+    pub fn number(value: f32, decimals: usize) -> Self {
+        LocaleArg::Number { value, decimals }
+    }
+
+    pub fn key(key: impl Into<String>) -> Self {
+        LocaleArg::Key(key.into())
+    }
+}
+
+impl From<String> for LocaleArg {
+    fn from(text: String) -> Self {
+        LocaleArg::Text(text)
+    }
+}
+
+impl From<&str> for LocaleArg {
+    fn from(text: &str) -> Self {
+        LocaleArg::Text(text.to_string())
+    }
+}
+
+impl From<usize> for LocaleArg {
+    fn from(count: usize) -> Self {
+        LocaleArg::number(count as f32, 0)
+    }
+}
+
+impl From<u32> for LocaleArg {
+    fn from(count: u32) -> Self {
+        LocaleArg::number(count as f32, 0)
+    }
+}
+
+#[derive(Resource, Asset, Reflect, Clone)]
+#[reflect(Resource)]
+pub struct LocaleAssets {
+    #[dependency]
+    pub locales: Vec<Handle<Locale>>,
+}
+
+impl FromWorld for LocaleAssets {
+    fn from_world(world: &mut World) -> Self {
+        let assets = world.resource::<AssetServer>();
+        Self {
+            locales: LOCALE_FILES.iter().map(|path| assets.load(*path)).collect(),
+        }
+    }
+}
+
+/// Picks the language in the settings menu
+#[derive(SystemParam)]
+pub struct LanguagePicker<'w> {
+    localization: ResMut<'w, Localization>,
+    locale_assets: Res<'w, LocaleAssets>,
+    locales: Res<'w, Assets<Locale>>,
+}
+
+impl LanguagePicker<'_> {
+    /// Switches to the language after the current one and remembers the choice
+    pub fn cycle(&mut self) {
+        let loaded: Vec<&Locale> = self.locale_assets.locales.iter().filter_map(|handle| self.locales.get(handle)).collect();
+        let current = loaded.iter().position(|l| l.code == self.localization.current.code);
+        let Some(next) = loaded.get(current.map_or(0, |i| (i + 1) % loaded.len())) else { return; };
+        self.localization.current = (*next).clone();
+        if let Err(e) = persistence::write(LANGUAGE_KEY, &self.localization.current.code) {
+            warn!("Could not save the language: {e}");
+        }
+    }
+}
+
+/// Text looked up in the string table, updated when the language or the arguments change
+/// Serializable, e.g. for the event log of a saved run
+#[derive(Component, Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[require(Text)]
+pub struct LocalizedText {
+    pub key: String,
+    pub args: Vec<(Cow<'static, str>, LocaleArg)>,
+}
+
+impl LocalizedText {
+    pub fn new(key: impl Into<String>) -> Self {
+        Self {
+            key: key.into(),
+            args: Vec::new(),
+        }
+    }
+
+    pub fn with(mut self, name: &'static str, arg: impl Into<LocaleArg>) -> Self {
+        self.args.push((Cow::Borrowed(name), arg.into()));
+        self
+    }
+}
+
+impl From<&str> for LocalizedText {
+    fn from(key: &str) -> Self {
+        LocalizedText::new(key)
+    }
+}
+
+impl From<String> for LocalizedText {
+    fn from(key: String) -> Self {
+        LocalizedText::new(key)
+    }
+}
+
+/// Localized texts are updated in this set
+#[derive(SystemSet, Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct ApplyLocale;
+
+/// The saved language, or the one of the system on the first start
+fn preferred_language() -> Option<String> {
+    persistence::read(LANGUAGE_KEY).or_else(|| {
+        let lang = std::env::var("LANG").ok()?;
+        Some(lang.get(..2)?.to_string())
+    })
+}
+
+fn apply_saved_language(
+    mut localization: ResMut<Localization>,
+    locale_assets: Res<LocaleAssets>,
+    locales: Res<Assets<Locale>>,
+) {
+    let preferred = preferred_language();
+    let loaded: Vec<&Locale> = locale_assets.locales.iter().filter_map(|handle| locales.get(handle)).collect();
+    if let Some(fallback) = loaded.first() {
+        localization.fallback = (*fallback).clone();
+    }
+    let picked = loaded
+        .iter()
+        .find(|l| preferred.as_deref() == Some(l.code.as_str()))
+        .or(loaded.first());
+    if let Some(picked) = picked {
+        localization.current = (*picked).clone();
+    }
+}
+
+fn apply_localized_text(localization: Res<Localization>, mut texts: Query<(Ref<LocalizedText>, &mut Text)>) {
+    let changed = localization.is_changed();
+    for (localized, mut text) in texts.iter_mut() {
+        if changed || localized.is_changed() {
+            text.0 = localization.format(&localized.key, &localized.args);
+        }
+    }
+}
+
+#[derive(Deserialize)]
+struct LocaleFile {
+    name: String,
+    code: String,
+    decimal_separator: String,
+    grouping_separator: String,
+    strings: HashMap<String, String>,
+}
+
+#[derive(Default)]
+struct LocaleLoader;
+
+impl AssetLoader for LocaleLoader {
+    type Asset = Locale;
+    type Settings = ();
+    type Error = BevyError;
+
+    async fn load(
+        &self,
+        reader: &mut dyn Reader,
+        _settings: &(),
+        _load_context: &mut LoadContext<'_>,
+    ) -> Result<Locale, BevyError> {
+        let mut bytes = Vec::new();
+        reader.read_to_end(&mut bytes).await?;
+        let file: LocaleFile = ron::de::from_bytes(&bytes)?;
+        Ok(Locale {
+            name: file.name,
+            code: file.code,
+            decimal_separator: file.decimal_separator,
+            grouping_separator: file.grouping_separator,
+            strings: file.strings,
+        })
+    }
+
+    fn extensions(&self) -> &[&str] {
+        &["locale.ron"]
+    }
+}
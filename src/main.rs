@@ -9,6 +9,7 @@ mod collision;
 mod dev_tools;
 mod hud;
 mod launching;
+mod menus;
 mod physics;
 mod score;
 mod screens;
@@ -17,9 +18,23 @@ mod sound;
 mod trails;
 mod effects;
 mod achievements;
+mod campaign;
+mod persistence;
+mod replay;
+mod sandbox;
+mod save;
+mod scenario;
+mod seed;
+mod theme;
+mod locale;
+mod accessibility;
+mod camera;
+mod controls;
+mod gamepad;
 
 use std::ops::{Deref, DerefMut};
 use crate::screens::Screen;
+use bevy::ecs::schedule::ExecutorKind;
 use bevy::log::LogPlugin;
 use bevy::window::{WindowResolution};
 use bevy::{asset::AssetMetaCheck, prelude::*};
@@ -62,6 +77,9 @@ impl Plugin for AppPlugin {
         );
         app.insert_resource(ClearColor(Color::srgb(0.0, 0.0, 0.0)));
 
+        // Spawn the main camera, it is shared by the menus and gameplay
+        app.add_systems(Startup, spawn_camera);
+
         // add our own plugins
         app.add_plugins((
             asset_tracking::plugin,
@@ -69,6 +87,7 @@ impl Plugin for AppPlugin {
             #[cfg(feature = "dev")]
             dev_tools::plugin,
             screens::plugin,
+            menus::plugin,
             sun_system::plugin,
             effects::plugin,
             launching::plugin,
@@ -78,6 +97,20 @@ impl Plugin for AppPlugin {
             sound::SoundPlugin,
             trails::TrailsPlugin,
             achievements::AchievementsPlugin,
+            save::plugin,
+        ));
+        app.add_plugins((
+            scenario::plugin,
+            campaign::plugin,
+            sandbox::plugin,
+            seed::plugin,
+            replay::plugin,
+            theme::plugin,
+            locale::plugin,
+            accessibility::plugin,
+            camera::plugin,
+            controls::plugin,
+            gamepad::plugin,
         ));
         // Tell bevy that our AppSystems should always be executed in the below order
         app.configure_sets(
@@ -89,6 +122,25 @@ impl Plugin for AppPlugin {
             )
                 .chain(),
         );
+        app.configure_sets(
+            FixedUpdate,
+            (
+                AppSystems::RecordInput,
+                AppSystems::Physics,
+                AppSystems::Update,
+            )
+                .chain(),
+        );
+        // The simulation only advances on fixed ticks. Running them single threaded keeps the
+        // order of systems stable, so a replayed run plays out exactly like the recorded one.
+        app.edit_schedule(FixedUpdate, |schedule| {
+            schedule.set_executor_kind(ExecutorKind::SingleThreaded);
+        });
+        // Player input is ignored while a replay feeds in the recorded inputs
+        app.configure_sets(
+            Update,
+            AppSystems::RecordInput.run_if(not(resource_exists::<replay::ReplayPlayback>)),
+        );
 
         // Tell all of our used bevy schedules that they should only run Gameplay systems if we're in the gameplay screen
         app.configure_sets(PreUpdate, GameplaySystem.run_if(in_state(Screen::Gameplay)));
@@ -105,6 +157,11 @@ impl Plugin for AppPlugin {
         // Set up the `Pause` state.
         app.init_state::<Pause>();
         app.configure_sets(Update, PausableSystems.run_if(in_state(Pause(false))));
+        app.configure_sets(FixedUpdate, PausableSystems.run_if(in_state(Pause(false))));
+        app.configure_sets(PostUpdate, PausableSystems.run_if(in_state(Pause(false))));
+        // Freeze virtual time while paused so timers, animations and the countdown stop as well
+        app.add_systems(OnEnter(Pause(true)), pause_virtual_time);
+        app.add_systems(OnExit(Pause(true)), unpause_virtual_time);
 
         // Set up a randomness source
         let rng = ChaCha8Rng::try_from_os_rng().unwrap_or(ChaCha8Rng::seed_from_u64(42));
@@ -112,10 +169,10 @@ impl Plugin for AppPlugin {
     }
 }
 
-/// High-level groupings/tags of systems for the app in the `Update` schedule.
+/// High-level groupings/tags of systems for the app in the `Update` and `FixedUpdate` schedules.
 #[derive(SystemSet, Debug, Clone, Copy, Eq, PartialEq, Hash, PartialOrd, Ord)]
 enum AppSystems {
-    /// Record player input.
+    /// Record player input. Inputs are queued in `Update` and applied in `FixedUpdate`.
     RecordInput,
     /// Calculate physical forces based on entity components
     Physics,
@@ -136,7 +193,15 @@ struct PausableSystems;
 struct GameplaySystem;
 
 fn spawn_camera(mut commands: Commands) {
-    commands.spawn(Camera2d);
+    commands.spawn((Name::new("Camera"), Camera2d));
+}
+
+fn pause_virtual_time(mut time: ResMut<Time<Virtual>>) {
+    time.pause();
+}
+
+fn unpause_virtual_time(mut time: ResMut<Time<Virtual>>) {
+    time.unpause();
 }
 
 #[derive(Resource)]
@@ -0,0 +1,131 @@
+//! The accessibility menu, reached from the settings menu.
+
+use bevy::input::common_conditions::input_just_pressed;
+use bevy::prelude::*;
+
+use crate::accessibility::{Accessibility, UI_SCALE_STEP};
+use crate::locale::{LocaleArg, LocalizedText};
+use crate::menus::Menu;
+use crate::menus::widget;
+
+pub(super) fn plugin(app: &mut App) {
+    app.add_systems(OnEnter(Menu::Accessibility), spawn_accessibility_menu);
+    app.add_systems(
+        Update,
+        (
+            handle_accessibility_action,
+            update_accessibility_labels,
+            go_back.run_if(input_just_pressed(KeyCode::Escape)),
+        )
+            .run_if(in_state(Menu::Accessibility)),
+    );
+}
+
+#[derive(Component, Debug, Clone, Copy, PartialEq)]
+enum AccessibilityAction {
+    CyclePalette,
+    ChangeUiScale(f32),
+    ToggleReducedMotion,
+    ToggleHighContrastOrbits,
+    Back,
+}
+
+/// Text showing the current value of an option
+#[derive(Component, Debug, Clone, Copy, PartialEq)]
+enum AccessibilityLabel {
+    Palette,
+    UiScale,
+    ReducedMotion,
+    HighContrastOrbits,
+}
+
+fn spawn_accessibility_menu(mut commands: Commands) {
+    commands.spawn((
+        widget::ui_root("Accessibility Menu"),
+        GlobalZIndex(2),
+        DespawnOnExit(Menu::Accessibility),
+        children![
+            widget::header("accessibility.title"),
+            (widget::label("accessibility.palette"), AccessibilityLabel::Palette),
+            widget::button("accessibility.next_palette", AccessibilityAction::CyclePalette),
+            (widget::label("accessibility.ui_scale"), AccessibilityLabel::UiScale),
+            (
+                Node {
+                    column_gap: Val::Px(12.0),
+                    ..default()
+                },
+                children![
+                    widget::button("accessibility.ui_smaller", AccessibilityAction::ChangeUiScale(-UI_SCALE_STEP)),
+                    widget::button("accessibility.ui_larger", AccessibilityAction::ChangeUiScale(UI_SCALE_STEP)),
+                ],
+            ),
+            (
+                widget::button("", AccessibilityAction::ToggleReducedMotion),
+                AccessibilityLabel::ReducedMotion,
+            ),
+            (
+                widget::button("", AccessibilityAction::ToggleHighContrastOrbits),
+                AccessibilityLabel::HighContrastOrbits,
+            ),
+            widget::button("menu.back", AccessibilityAction::Back),
+        ],
+    ));
+}
+
+fn handle_accessibility_action(
+    query: Query<(&Interaction, &AccessibilityAction), Changed<Interaction>>,
+    mut accessibility: ResMut<Accessibility>,
+    next_menu: ResMut<NextState<Menu>>,
+) {
+    for (interaction, action) in query.iter() {
+        if *interaction != Interaction::Pressed {
+            continue;
+        }
+        match action {
+            AccessibilityAction::CyclePalette => accessibility.palette = accessibility.palette.next(),
+            AccessibilityAction::ChangeUiScale(delta) => accessibility.change_ui_scale(*delta),
+            AccessibilityAction::ToggleReducedMotion => {
+                accessibility.reduced_motion = !accessibility.reduced_motion;
+            }
+            AccessibilityAction::ToggleHighContrastOrbits => {
+                accessibility.high_contrast_orbits = !accessibility.high_contrast_orbits;
+            }
+            AccessibilityAction::Back => {
+                go_back(next_menu);
+                return;
+            }
+        }
+        accessibility.save();
+    }
+}
+
+/// Labels show the value themselves, toggle buttons through their text child
+fn update_accessibility_labels(
+    accessibility: Res<Accessibility>,
+    labels: Query<(Entity, &AccessibilityLabel, Option<&Children>)>,
+    mut texts: Query<&mut LocalizedText>,
+) {
+    let on_off = |on: bool| LocaleArg::key(if on { "common.on" } else { "common.off" });
+    for (entity, label, children) in labels.iter() {
+        let content = match label {
+            AccessibilityLabel::Palette => LocalizedText::new("accessibility.palette")
+                .with("palette", LocaleArg::key(accessibility.palette.name_key())),
+            AccessibilityLabel::UiScale => LocalizedText::new("accessibility.ui_scale")
+                .with("percent", LocaleArg::number(accessibility.ui_scale * 100.0, 0)),
+            AccessibilityLabel::ReducedMotion => LocalizedText::new("accessibility.reduced_motion")
+                .with("state", on_off(accessibility.reduced_motion)),
+            AccessibilityLabel::HighContrastOrbits => LocalizedText::new("accessibility.high_contrast_orbits")
+                .with("state", on_off(accessibility.high_contrast_orbits)),
+        };
+        let targets = std::iter::once(entity).chain(children.into_iter().flat_map(|c| c.iter()));
+        for target in targets {
+            if let Ok(mut text) = texts.get_mut(target) {
+                text.set_if_neq(content.clone());
+            }
+        }
+    }
+}
+
+fn go_back(mut next_menu: ResMut<NextState<Menu>>) {
+    next_menu.set(Menu::Settings);
+}
@@ -0,0 +1,104 @@
+//! The campaign menu, lists the missions and starts the unlocked ones.
+
+use bevy::input::common_conditions::input_just_pressed;
+use bevy::prelude::*;
+
+use crate::campaign::{ActiveMission, CampaignAssets, CampaignProgress};
+use crate::menus::Menu;
+use crate::locale::{LocaleArg, LocalizedText};
+use crate::menus::widget;
+use crate::theme::{TextSize, ThemeColor, ThemedText};
+use crate::scenario::{ActiveScenario, Scenario};
+use crate::screens::Screen;
+
+pub(super) fn plugin(app: &mut App) {
+    app.add_systems(OnEnter(Menu::Campaign), spawn_campaign_menu);
+    app.add_systems(
+        Update,
+        (
+            handle_campaign_action,
+            go_back.run_if(input_just_pressed(KeyCode::Escape)),
+        )
+            .run_if(in_state(Menu::Campaign)),
+    );
+}
+
+#[derive(Component, Debug, Clone, Copy, PartialEq, Eq)]
+enum CampaignAction {
+    Start(usize),
+    Back,
+}
+
+fn spawn_campaign_menu(
+    mut commands: Commands,
+    campaign: Res<CampaignAssets>,
+    scenarios: Res<Assets<Scenario>>,
+    progress: Res<CampaignProgress>,
+) {
+    commands
+        .spawn((
+            widget::ui_root("Campaign Menu"),
+            GlobalZIndex(2),
+            DespawnOnExit(Menu::Campaign),
+        ))
+        .with_children(|parent| {
+            parent.spawn(widget::header("menu.campaign"));
+            for (i, handle) in campaign.missions.iter().enumerate() {
+                let Some(mission) = scenarios.get(handle) else { continue; };
+                if !progress.is_unlocked(i) {
+                    parent.spawn((
+                        LocalizedText::new("campaign.locked").with("mission", LocaleArg::key(&mission.name)),
+                        ThemedText::new(ThemeColor::Dim, TextSize::Button),
+                    ));
+                    continue;
+                }
+                let name = if i < progress.completed {
+                    LocalizedText::new("campaign.done").with("mission", LocaleArg::key(&mission.name))
+                } else {
+                    LocalizedText::new(&mission.name)
+                };
+                parent.spawn(widget::button(name, CampaignAction::Start(i)));
+                parent.spawn((
+                    widget::label(mission.description.clone()),
+                    Node {
+                        max_width: Val::Px(420.0),
+                        margin: UiRect::bottom(Val::Px(8.0)),
+                        ..default()
+                    },
+                ));
+            }
+            parent.spawn(widget::button("menu.back", CampaignAction::Back));
+        });
+}
+
+fn handle_campaign_action(
+    query: Query<(&Interaction, &CampaignAction), Changed<Interaction>>,
+    mut commands: Commands,
+    campaign: Res<CampaignAssets>,
+    scenarios: Res<Assets<Scenario>>,
+    mut next_screen: ResMut<NextState<Screen>>,
+    next_menu: ResMut<NextState<Menu>>,
+) {
+    for (interaction, action) in query.iter() {
+        if *interaction != Interaction::Pressed {
+            continue;
+        }
+        match *action {
+            CampaignAction::Start(mission) => {
+                let Some(scenario) = campaign.missions.get(mission).and_then(|h| scenarios.get(h)) else { continue; };
+                info!("Starting mission {}", scenario.name);
+                commands.insert_resource(ActiveScenario(scenario.clone()));
+                commands.insert_resource(ActiveMission(Some(mission)));
+                next_screen.set(Screen::Gameplay);
+            }
+            CampaignAction::Back => {
+                go_back(next_menu);
+                return;
+            }
+        }
+    }
+}
+
+fn go_back(mut next_menu: ResMut<NextState<Menu>>) {
+    next_menu.set(Menu::Main);
+}
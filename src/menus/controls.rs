@@ -0,0 +1,164 @@
+//! The controls menu, reached from the settings menu. Clicking an action waits for the next key or
+//! mouse button and binds it, escape cancels.
+
+use bevy::input::common_conditions::input_just_pressed;
+use bevy::prelude::*;
+
+use crate::controls::{Action, Binding, InputBindings};
+use crate::locale::{LocaleArg, LocalizedText};
+use crate::menus::Menu;
+use crate::menus::widget;
+
+/// Width of the grid of actions, three buttons side by side
+const GRID_WIDTH: f32 = 3.0 * 240.0 + 2.0 * 12.0;
+
+pub(super) fn plugin(app: &mut App) {
+    app.init_resource::<Rebinding>();
+    app.add_systems(OnEnter(Menu::Controls), spawn_controls_menu);
+    app.add_systems(
+        Update,
+        (
+            go_back.run_if(input_just_pressed(KeyCode::Escape).and(not(is_rebinding))),
+            capture_binding,
+            handle_controls_action,
+            update_binding_labels,
+        )
+            .chain()
+            .run_if(in_state(Menu::Controls)),
+    );
+}
+
+/// The action waiting for a key or button
+#[derive(Resource, Default)]
+struct Rebinding(Option<Action>);
+
+#[derive(Component, Debug, Clone, Copy, PartialEq)]
+enum ControlsAction {
+    Rebind(Action),
+    Reset,
+    Back,
+}
+
+fn is_rebinding(rebinding: Res<Rebinding>) -> bool {
+    rebinding.0.is_some()
+}
+
+fn spawn_controls_menu(mut commands: Commands, mut rebinding: ResMut<Rebinding>) {
+    rebinding.0 = None;
+    commands
+        .spawn((
+            widget::ui_root("Controls Menu"),
+            GlobalZIndex(2),
+            DespawnOnExit(Menu::Controls),
+        ))
+        .with_children(|root| {
+            root.spawn(widget::header("controls.title"));
+            root.spawn((
+                widget::ScrollArea,
+                Node {
+                    width: Val::Px(GRID_WIDTH),
+                    max_height: Val::Percent(60.0),
+                    flex_wrap: FlexWrap::Wrap,
+                    column_gap: Val::Px(12.0),
+                    row_gap: Val::Px(12.0),
+                    overflow: Overflow::scroll_y(),
+                    ..default()
+                },
+            ))
+            .with_children(|grid| {
+                for action in Action::ALL.into_iter().filter(|action| action.is_available()) {
+                    grid.spawn(widget::button("", ControlsAction::Rebind(action)));
+                }
+            });
+            root.spawn((
+                Node {
+                    column_gap: Val::Px(12.0),
+                    ..default()
+                },
+                children![
+                    widget::button("controls.reset", ControlsAction::Reset),
+                    widget::button("menu.back", ControlsAction::Back),
+                ],
+            ));
+        });
+}
+
+/// Binds the next key or mouse button pressed to the waiting action
+fn capture_binding(
+    mut rebinding: ResMut<Rebinding>,
+    keys: Res<ButtonInput<KeyCode>>,
+    mouse: Res<ButtonInput<MouseButton>>,
+    mut bindings: ResMut<InputBindings>,
+) {
+    let Some(action) = rebinding.0 else { return; };
+    if keys.just_pressed(KeyCode::Escape) {
+        rebinding.0 = None;
+        return;
+    }
+    let binding = keys
+        .get_just_pressed()
+        .next()
+        .map(|key| Binding::Key(*key))
+        .or_else(|| mouse.get_just_pressed().next().map(|button| Binding::Mouse(*button)));
+    let Some(binding) = binding else { return; };
+    bindings.rebind(action, binding);
+    bindings.save();
+    rebinding.0 = None;
+}
+
+fn handle_controls_action(
+    query: Query<(&Interaction, &ControlsAction), Changed<Interaction>>,
+    mut rebinding: ResMut<Rebinding>,
+    mut bindings: ResMut<InputBindings>,
+    next_menu: ResMut<NextState<Menu>>,
+) {
+    // the click that was just bound doesn't press the button under the cursor
+    if rebinding.is_changed() {
+        return;
+    }
+    for (interaction, action) in query.iter() {
+        if *interaction != Interaction::Pressed {
+            continue;
+        }
+        match action {
+            ControlsAction::Rebind(action) => rebinding.0 = Some(*action),
+            ControlsAction::Reset => {
+                *bindings = InputBindings::default();
+                bindings.save();
+            }
+            ControlsAction::Back => {
+                go_back(next_menu);
+                return;
+            }
+        }
+    }
+}
+
+/// Each button names its action and binding, or asks for a key while waiting
+fn update_binding_labels(
+    rebinding: Res<Rebinding>,
+    bindings: Res<InputBindings>,
+    buttons: Query<(&ControlsAction, &Children)>,
+    mut texts: Query<&mut LocalizedText>,
+) {
+    for (action, children) in buttons.iter() {
+        let ControlsAction::Rebind(action) = *action else { continue; };
+        let name = LocaleArg::key(action.name_key());
+        let content = if rebinding.0 == Some(action) {
+            LocalizedText::new("controls.waiting").with("action", name)
+        } else {
+            LocalizedText::new("controls.binding")
+                .with("action", name)
+                .with("binding", bindings.get(action).label())
+        };
+        for child in children.iter() {
+            if let Ok(mut text) = texts.get_mut(child) {
+                text.set_if_neq(content.clone());
+            }
+        }
+    }
+}
+
+fn go_back(mut next_menu: ResMut<NextState<Menu>>) {
+    next_menu.set(Menu::Settings);
+}
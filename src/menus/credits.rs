@@ -0,0 +1,63 @@
+//! The credits menu.
+
+use bevy::input::common_conditions::input_just_pressed;
+use bevy::prelude::*;
+
+use crate::menus::Menu;
+use crate::locale::LocalizedText;
+use crate::menus::widget;
+use crate::theme::{TextSize, ThemeColor, ThemedText};
+
+pub(super) fn plugin(app: &mut App) {
+    app.add_systems(OnEnter(Menu::Credits), spawn_credits_menu);
+    app.add_systems(
+        Update,
+        (
+            handle_credits_action,
+            go_back.run_if(input_just_pressed(KeyCode::Escape)),
+        )
+            .run_if(in_state(Menu::Credits)),
+    );
+}
+
+#[derive(Component, Debug, Clone, Copy, PartialEq, Eq)]
+struct BackAction;
+
+fn spawn_credits_menu(mut commands: Commands) {
+    commands.spawn((
+        widget::ui_root("Credits Menu"),
+        GlobalZIndex(2),
+        DespawnOnExit(Menu::Credits),
+        children![
+            widget::header("menu.credits"),
+            (
+                LocalizedText::new("credits.created_by"),
+                ThemedText::new(ThemeColor::Accent, TextSize::Button),
+            ),
+            widget::label("LILLY\nJO\nJOHANNES\nALEX"),
+            (
+                LocalizedText::new("credits.made_with"),
+                Node {
+                    margin: UiRect::top(Val::Px(15.0)),
+                    ..default()
+                },
+                ThemedText::new(ThemeColor::Accent, TextSize::Button),
+            ),
+            widget::label("BEVY\nPHOTOSHOP\nABLETON LIVE\nRUSTROVER"),
+            widget::button("menu.back", BackAction),
+        ],
+    ));
+}
+
+fn handle_credits_action(
+    query: Query<&Interaction, (With<BackAction>, Changed<Interaction>)>,
+    next_menu: ResMut<NextState<Menu>>,
+) {
+    if query.iter().any(|interaction| *interaction == Interaction::Pressed) {
+        go_back(next_menu);
+    }
+}
+
+fn go_back(mut next_menu: ResMut<NextState<Menu>>) {
+    next_menu.set(Menu::Main);
+}
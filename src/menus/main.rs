@@ -0,0 +1,107 @@
+//! The main menu shown on the title screen.
+
+use bevy::prelude::*;
+
+use crate::campaign::ActiveMission;
+use crate::menus::Menu;
+use crate::locale::LocalizedText;
+use crate::menus::widget;
+use crate::theme::{TextSize, ThemeColor, ThemedText};
+use crate::save::{self, PendingLoad};
+use crate::scenario::{ActiveScenario, Scenario};
+use crate::seed::{SeedMode, SeedOverride};
+use crate::screens::Screen;
+
+pub(super) fn plugin(app: &mut App) {
+    app.add_systems(OnEnter(Menu::Main), spawn_main_menu);
+    app.add_systems(Update, handle_main_menu_action.run_if(in_state(Menu::Main)));
+}
+
+#[derive(Component, Debug, Clone, Copy, PartialEq, Eq)]
+enum MainMenuAction {
+    Continue,
+    Campaign,
+    Play,
+    DailyChallenge,
+    Sandbox,
+    Settings,
+    Credits,
+    #[cfg(not(target_family = "wasm"))]
+    Quit,
+}
+
+fn spawn_main_menu(mut commands: Commands) {
+    let has_save = save::load_save().is_some();
+    commands
+        .spawn((
+            widget::ui_root("Main Menu"),
+            GlobalZIndex(2),
+            DespawnOnExit(Menu::Main),
+        ))
+        .with_children(|parent| {
+            parent.spawn(widget::header("menu.title"));
+            parent.spawn((
+                LocalizedText::new("menu.tagline"),
+                Node {
+                    margin: UiRect::bottom(Val::Px(30.0)),
+                    ..default()
+                },
+                ThemedText::new(ThemeColor::Accent, TextSize::Body),
+            ));
+            if has_save {
+                parent.spawn(widget::button("menu.continue", MainMenuAction::Continue));
+            }
+            parent.spawn(widget::button("menu.campaign", MainMenuAction::Campaign));
+            parent.spawn(widget::button("menu.play", MainMenuAction::Play));
+            parent.spawn(widget::button("menu.daily_challenge", MainMenuAction::DailyChallenge));
+            parent.spawn(widget::button("menu.sandbox", MainMenuAction::Sandbox));
+            parent.spawn(widget::button("menu.settings", MainMenuAction::Settings));
+            parent.spawn(widget::button("menu.credits", MainMenuAction::Credits));
+            #[cfg(not(target_family = "wasm"))]
+            parent.spawn(widget::button("menu.quit", MainMenuAction::Quit));
+        });
+}
+
+fn handle_main_menu_action(
+    query: Query<(&Interaction, &MainMenuAction), Changed<Interaction>>,
+    mut commands: Commands,
+    mut next_screen: ResMut<NextState<Screen>>,
+    mut next_menu: ResMut<NextState<Menu>>,
+    #[cfg(not(target_family = "wasm"))] mut app_exit: MessageWriter<AppExit>,
+) {
+    for (interaction, action) in query.iter() {
+        if *interaction != Interaction::Pressed {
+            continue;
+        }
+        match action {
+            MainMenuAction::Continue => {
+                if let Some(save) = save::load_save() {
+                    commands.insert_resource(ActiveScenario(save.scenario().clone()));
+                    commands.insert_resource(ActiveMission(save.mission()));
+                    commands.insert_resource(PendingLoad(save));
+                }
+                next_screen.set(Screen::Gameplay);
+            }
+            MainMenuAction::Campaign => next_menu.set(Menu::Campaign),
+            MainMenuAction::Play => next_menu.set(Menu::Scenarios),
+            MainMenuAction::DailyChallenge => {
+                // everyone plays the classic scenario with the seed of the day
+                commands.insert_resource(ActiveScenario(Scenario::default()));
+                commands.insert_resource(ActiveMission(None));
+                commands.insert_resource(SeedOverride(SeedMode::Daily));
+                next_screen.set(Screen::Gameplay);
+            }
+            MainMenuAction::Sandbox => {
+                commands.insert_resource(ActiveScenario(Scenario::sandbox()));
+                commands.insert_resource(ActiveMission(None));
+                next_screen.set(Screen::Gameplay);
+            }
+            MainMenuAction::Settings => next_menu.set(Menu::Settings),
+            MainMenuAction::Credits => next_menu.set(Menu::Credits),
+            #[cfg(not(target_family = "wasm"))]
+            MainMenuAction::Quit => {
+                app_exit.write(AppExit::Success);
+            }
+        }
+    }
+}
@@ -0,0 +1,46 @@
+//! The game's menus and transitions between them.
+
+mod accessibility;
+mod campaign;
+mod controls;
+mod credits;
+mod main;
+mod pause;
+mod scenarios;
+mod seed;
+mod settings;
+pub(crate) mod widget;
+
+use bevy::prelude::*;
+
+pub(super) fn plugin(app: &mut App) {
+    app.init_state::<Menu>();
+    app.add_plugins((
+        accessibility::plugin,
+        campaign::plugin,
+        controls::plugin,
+        credits::plugin,
+        main::plugin,
+        pause::plugin,
+        scenarios::plugin,
+        seed::plugin,
+        settings::plugin,
+        widget::plugin,
+    ));
+}
+
+/// The game's menus, only one of them is open at a time.
+#[derive(States, Copy, Clone, Eq, PartialEq, Hash, Debug, Default)]
+pub enum Menu {
+    #[default]
+    None,
+    Main,
+    Settings,
+    Accessibility,
+    Controls,
+    Credits,
+    Pause,
+    Scenarios,
+    Campaign,
+    Seed,
+}
@@ -0,0 +1,98 @@
+//! The pause menu that is opened with Escape or the gamepad's start button during gameplay.
+
+use bevy::input::common_conditions::input_just_pressed;
+use bevy::prelude::*;
+
+use crate::Pause;
+use crate::controls::gamepad_just_pressed;
+use crate::menus::Menu;
+use crate::menus::widget;
+use crate::save::SaveRun;
+use crate::screens::Screen;
+
+pub(super) fn plugin(app: &mut App) {
+    app.add_systems(OnEnter(Menu::Pause), spawn_pause_menu);
+    app.add_systems(
+        Update,
+        (
+            handle_pause_action.run_if(in_state(Menu::Pause)),
+            toggle_pause.run_if(
+                in_state(Screen::Gameplay)
+                    .and(in_state(Menu::None).or(in_state(Menu::Pause)))
+                    .and(input_just_pressed(KeyCode::Escape).or(gamepad_just_pressed(GamepadButton::Start))),
+            ),
+        ),
+    );
+    app.add_systems(OnExit(Screen::Gameplay), close_pause_menu);
+}
+
+#[derive(Component, Debug, Clone, Copy, PartialEq, Eq)]
+enum PauseAction {
+    Resume,
+    Restart,
+    Settings,
+    Quit,
+}
+
+fn spawn_pause_menu(mut commands: Commands) {
+    commands.spawn((
+        widget::overlay_root("Pause Menu"),
+        GlobalZIndex(2),
+        DespawnOnExit(Menu::Pause),
+        children![
+            widget::header("pause.title"),
+            widget::button("pause.resume", PauseAction::Resume),
+            widget::button("menu.restart", PauseAction::Restart),
+            widget::button("menu.settings", PauseAction::Settings),
+            widget::button("pause.quit", PauseAction::Quit),
+        ],
+    ));
+}
+
+fn toggle_pause(
+    current_pause: Res<State<Pause>>,
+    mut next_pause: ResMut<NextState<Pause>>,
+    mut next_menu: ResMut<NextState<Menu>>,
+) {
+    if current_pause.0 {
+        debug!("Unpausing game");
+        next_pause.set(Pause(false));
+        next_menu.set(Menu::None);
+    } else {
+        debug!("Pausing game");
+        next_pause.set(Pause(true));
+        next_menu.set(Menu::Pause);
+    }
+}
+
+fn handle_pause_action(
+    mut commands: Commands,
+    query: Query<(&Interaction, &PauseAction), Changed<Interaction>>,
+    mut next_pause: ResMut<NextState<Pause>>,
+    mut next_menu: ResMut<NextState<Menu>>,
+    mut next_screen: ResMut<NextState<Screen>>,
+) {
+    for (interaction, action) in query.iter() {
+        if *interaction != Interaction::Pressed {
+            continue;
+        }
+        match action {
+            PauseAction::Resume => {
+                next_pause.set(Pause(false));
+                next_menu.set(Menu::None);
+            }
+            PauseAction::Restart => next_screen.set(Screen::Restart),
+            PauseAction::Settings => next_menu.set(Menu::Settings),
+            PauseAction::Quit => {
+                // the run can be continued from the title screen
+                commands.trigger(SaveRun);
+                next_screen.set(Screen::Title);
+            }
+        }
+    }
+}
+
+fn close_pause_menu(mut next_pause: ResMut<NextState<Pause>>, mut next_menu: ResMut<NextState<Menu>>) {
+    next_pause.set(Pause(false));
+    next_menu.set(Menu::None);
+}
@@ -0,0 +1,98 @@
+//! The scenario menu, picking a scenario starts a new run with it.
+
+use bevy::input::common_conditions::input_just_pressed;
+use bevy::prelude::*;
+
+use crate::campaign::ActiveMission;
+use crate::menus::Menu;
+use crate::menus::seed::seed_mode_text;
+use crate::menus::widget;
+use crate::seed::SeedMode;
+use crate::scenario::{ActiveScenario, Scenario, ScenarioAssets};
+use crate::screens::Screen;
+
+pub(super) fn plugin(app: &mut App) {
+    app.add_systems(OnEnter(Menu::Scenarios), spawn_scenario_menu);
+    app.add_systems(
+        Update,
+        (
+            handle_scenario_action,
+            go_back.run_if(input_just_pressed(KeyCode::Escape)),
+        )
+            .run_if(in_state(Menu::Scenarios)),
+    );
+}
+
+#[derive(Component, Debug, Clone, PartialEq, Eq)]
+enum ScenarioAction {
+    Start(Handle<Scenario>),
+    Seed,
+    Back,
+}
+
+fn spawn_scenario_menu(
+    mut commands: Commands,
+    scenario_assets: Res<ScenarioAssets>,
+    scenarios: Res<Assets<Scenario>>,
+    seed_mode: Res<SeedMode>,
+) {
+    commands
+        .spawn((
+            widget::ui_root("Scenario Menu"),
+            GlobalZIndex(2),
+            DespawnOnExit(Menu::Scenarios),
+        ))
+        .with_children(|parent| {
+            parent.spawn(widget::header("scenarios.title"));
+            for handle in scenario_assets.scenarios.iter() {
+                let Some(scenario) = scenarios.get(handle) else { continue; };
+                parent.spawn(widget::button(
+                    scenario.name.clone(),
+                    ScenarioAction::Start(handle.clone()),
+                ));
+                parent.spawn((
+                    widget::label(scenario.description.clone()),
+                    Node {
+                        max_width: Val::Px(420.0),
+                        margin: UiRect::bottom(Val::Px(8.0)),
+                        ..default()
+                    },
+                ));
+            }
+            parent.spawn(widget::button(seed_mode_text(*seed_mode), ScenarioAction::Seed));
+            parent.spawn(widget::button("menu.back", ScenarioAction::Back));
+        });
+}
+
+fn handle_scenario_action(
+    query: Query<(&Interaction, &ScenarioAction), Changed<Interaction>>,
+    scenarios: Res<Assets<Scenario>>,
+    mut active: ResMut<ActiveScenario>,
+    mut mission: ResMut<ActiveMission>,
+    mut next_screen: ResMut<NextState<Screen>>,
+    mut next_menu: ResMut<NextState<Menu>>,
+) {
+    for (interaction, action) in query.iter() {
+        if *interaction != Interaction::Pressed {
+            continue;
+        }
+        match action {
+            ScenarioAction::Start(handle) => {
+                let Some(scenario) = scenarios.get(handle) else { continue; };
+                info!("Starting scenario {}", scenario.name);
+                active.0 = scenario.clone();
+                mission.0 = None;
+                next_screen.set(Screen::Gameplay);
+            }
+            ScenarioAction::Seed => next_menu.set(Menu::Seed),
+            ScenarioAction::Back => {
+                go_back(next_menu);
+                return;
+            }
+        }
+    }
+}
+
+fn go_back(mut next_menu: ResMut<NextState<Menu>>) {
+    next_menu.set(Menu::Main);
+}
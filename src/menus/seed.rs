@@ -0,0 +1,133 @@
+//! The seed menu, type a number to play a fixed seed or pick a random or daily one.
+
+use bevy::input::common_conditions::input_just_pressed;
+use bevy::prelude::*;
+
+use crate::locale::LocalizedText;
+use crate::menus::Menu;
+use crate::menus::widget;
+use crate::seed::SeedMode;
+
+pub(super) fn plugin(app: &mut App) {
+    app.add_systems(OnEnter(Menu::Seed), spawn_seed_menu);
+    app.add_systems(
+        Update,
+        (
+            handle_seed_action,
+            type_seed,
+            update_seed_label,
+            go_back.run_if(input_just_pressed(KeyCode::Escape)),
+        )
+            .run_if(in_state(Menu::Seed)),
+    );
+}
+
+/// Seeds longer than this can't be typed in
+const MAX_SEED_DIGITS: usize = 18;
+
+#[derive(Component, Debug, Clone, Copy, PartialEq, Eq)]
+enum SeedAction {
+    Random,
+    Daily,
+    Back,
+}
+
+#[derive(Component)]
+struct SeedLabel;
+
+/// Short description of a seed mode, also used by the buttons leading to this menu
+pub(super) fn seed_mode_text(mode: SeedMode) -> LocalizedText {
+    match mode {
+        SeedMode::Random => LocalizedText::new("seed.mode_random"),
+        SeedMode::Fixed(seed) => LocalizedText::new("seed.mode_fixed").with("seed", seed.to_string()),
+        SeedMode::Daily => LocalizedText::new("seed.mode_daily"),
+    }
+}
+
+fn spawn_seed_menu(mut commands: Commands) {
+    commands.spawn((
+        widget::ui_root("Seed Menu"),
+        GlobalZIndex(2),
+        DespawnOnExit(Menu::Seed),
+        children![
+            widget::header("seed.title"),
+            (widget::label(""), SeedLabel),
+            widget::label("seed.hint"),
+            widget::button("seed.random", SeedAction::Random),
+            widget::button("seed.daily", SeedAction::Daily),
+            widget::button("menu.back", SeedAction::Back),
+        ],
+    ));
+}
+
+fn handle_seed_action(
+    query: Query<(&Interaction, &SeedAction), Changed<Interaction>>,
+    mut mode: ResMut<SeedMode>,
+    next_menu: ResMut<NextState<Menu>>,
+) {
+    for (interaction, action) in query.iter() {
+        if *interaction != Interaction::Pressed {
+            continue;
+        }
+        match action {
+            SeedAction::Random => *mode = SeedMode::Random,
+            SeedAction::Daily => *mode = SeedMode::Daily,
+            SeedAction::Back => {
+                go_back(next_menu);
+                return;
+            }
+        }
+    }
+}
+
+fn type_seed(keys: Res<ButtonInput<KeyCode>>, mut mode: ResMut<SeedMode>) {
+    let mut digits = match *mode {
+        SeedMode::Fixed(seed) => seed.to_string(),
+        _ => String::new(),
+    };
+    let before = digits.clone();
+
+    for key in keys.get_just_pressed() {
+        if let Some(digit) = key_digit(*key) {
+            if digits.len() < MAX_SEED_DIGITS {
+                digits.push(digit);
+            }
+        } else if *key == KeyCode::Backspace {
+            digits.pop();
+        }
+    }
+
+    if digits == before {
+        return;
+    }
+    *mode = match digits.parse() {
+        Ok(seed) => SeedMode::Fixed(seed),
+        Err(_) => SeedMode::Random,
+    };
+}
+
+fn key_digit(key: KeyCode) -> Option<char> {
+    let digit = match key {
+        KeyCode::Digit0 | KeyCode::Numpad0 => '0',
+        KeyCode::Digit1 | KeyCode::Numpad1 => '1',
+        KeyCode::Digit2 | KeyCode::Numpad2 => '2',
+        KeyCode::Digit3 | KeyCode::Numpad3 => '3',
+        KeyCode::Digit4 | KeyCode::Numpad4 => '4',
+        KeyCode::Digit5 | KeyCode::Numpad5 => '5',
+        KeyCode::Digit6 | KeyCode::Numpad6 => '6',
+        KeyCode::Digit7 | KeyCode::Numpad7 => '7',
+        KeyCode::Digit8 | KeyCode::Numpad8 => '8',
+        KeyCode::Digit9 | KeyCode::Numpad9 => '9',
+        _ => return None,
+    };
+    Some(digit)
+}
+
+fn update_seed_label(mode: Res<SeedMode>, mut label: Query<&mut LocalizedText, With<SeedLabel>>) {
+    let Ok(mut label) = label.single_mut() else { return; };
+    label.set_if_neq(seed_mode_text(*mode));
+}
+
+fn go_back(mut next_menu: ResMut<NextState<Menu>>) {
+    next_menu.set(Menu::Scenarios);
+}
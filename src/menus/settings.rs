@@ -0,0 +1,144 @@
+//! The settings menu.
+
+use bevy::audio::Volume;
+use bevy::input::common_conditions::input_just_pressed;
+use bevy::prelude::*;
+
+use crate::locale::{LanguagePicker, LocaleArg, Localization, LocalizedText};
+use crate::menus::Menu;
+use crate::menus::widget;
+use crate::screens::Screen;
+use crate::theme::{HudTheme, ThemePicker};
+
+pub(super) fn plugin(app: &mut App) {
+    app.add_systems(OnEnter(Menu::Settings), spawn_settings_menu);
+    app.add_systems(
+        Update,
+        (
+            handle_settings_action,
+            update_volume_label,
+            update_theme_label,
+            update_language_label,
+            go_back.run_if(input_just_pressed(KeyCode::Escape)),
+        )
+            .run_if(in_state(Menu::Settings)),
+    );
+}
+
+/// Step by which the volume buttons change the global volume
+const VOLUME_STEP: f32 = 0.05;
+
+#[derive(Component, Debug, Clone, Copy, PartialEq)]
+enum SettingsAction {
+    ChangeVolume(f32),
+    CycleTheme,
+    CycleLanguage,
+    Accessibility,
+    Controls,
+    Back,
+}
+
+#[derive(Component)]
+struct VolumeLabel;
+
+#[derive(Component)]
+struct ThemeLabel;
+
+#[derive(Component)]
+struct LanguageLabel;
+
+fn spawn_settings_menu(mut commands: Commands) {
+    commands.spawn((
+        widget::ui_root("Settings Menu"),
+        GlobalZIndex(2),
+        DespawnOnExit(Menu::Settings),
+        children![
+            widget::header("menu.settings"),
+            (widget::label("settings.volume"), VolumeLabel),
+            (
+                Node {
+                    column_gap: Val::Px(12.0),
+                    ..default()
+                },
+                children![
+                    widget::button("-", SettingsAction::ChangeVolume(-VOLUME_STEP)),
+                    widget::button("+", SettingsAction::ChangeVolume(VOLUME_STEP)),
+                ],
+            ),
+            (widget::label("settings.theme"), ThemeLabel),
+            widget::button("settings.next_theme", SettingsAction::CycleTheme),
+            (widget::label("settings.language"), LanguageLabel),
+            widget::button("settings.next_language", SettingsAction::CycleLanguage),
+            (
+                Node {
+                    column_gap: Val::Px(12.0),
+                    ..default()
+                },
+                children![
+                    widget::button("settings.accessibility", SettingsAction::Accessibility),
+                    widget::button("settings.controls", SettingsAction::Controls),
+                ],
+            ),
+            widget::button("menu.back", SettingsAction::Back),
+        ],
+    ));
+}
+
+fn handle_settings_action(
+    query: Query<(&Interaction, &SettingsAction), Changed<Interaction>>,
+    mut global_volume: ResMut<GlobalVolume>,
+    mut theme: ThemePicker,
+    mut language: LanguagePicker,
+    screen: Res<State<Screen>>,
+    mut next_menu: ResMut<NextState<Menu>>,
+) {
+    for (interaction, action) in query.iter() {
+        if *interaction != Interaction::Pressed {
+            continue;
+        }
+        match action {
+            SettingsAction::ChangeVolume(delta) => {
+                let linear = (global_volume.volume.to_linear() + delta).clamp(0.0, 1.0);
+                global_volume.volume = Volume::Linear(linear);
+            }
+            SettingsAction::CycleTheme => theme.cycle(),
+            SettingsAction::CycleLanguage => language.cycle(),
+            SettingsAction::Accessibility => next_menu.set(Menu::Accessibility),
+            SettingsAction::Controls => next_menu.set(Menu::Controls),
+            SettingsAction::Back => {
+                go_back(screen, next_menu);
+                return;
+            }
+        }
+    }
+}
+
+fn update_volume_label(
+    global_volume: Res<GlobalVolume>,
+    mut label: Query<&mut LocalizedText, With<VolumeLabel>>,
+) {
+    let Ok(mut label) = label.single_mut() else { return; };
+    let percent = LocaleArg::number(global_volume.volume.to_linear() * 100.0, 0);
+    label.set_if_neq(LocalizedText::new("settings.volume").with("percent", percent));
+}
+
+fn update_theme_label(theme: Res<HudTheme>, mut label: Query<&mut LocalizedText, With<ThemeLabel>>) {
+    let Ok(mut label) = label.single_mut() else { return; };
+    label.set_if_neq(LocalizedText::new("settings.theme").with("theme", theme.name.as_str()));
+}
+
+fn update_language_label(
+    localization: Res<Localization>,
+    mut label: Query<&mut LocalizedText, With<LanguageLabel>>,
+) {
+    let Ok(mut label) = label.single_mut() else { return; };
+    label.set_if_neq(LocalizedText::new("settings.language").with("language", localization.language()));
+}
+
+fn go_back(screen: Res<State<Screen>>, mut next_menu: ResMut<NextState<Menu>>) {
+    next_menu.set(if *screen.get() == Screen::Title {
+        Menu::Main
+    } else {
+        Menu::Pause
+    });
+}
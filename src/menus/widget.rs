@@ -0,0 +1,189 @@
+//! Building blocks for menus, styled with the [`HudTheme`]. Their texts are looked up in the
+//! string tables, see [`LocalizedText`].
+
+use bevy::ecs::system::SystemParam;
+use bevy::input::mouse::{MouseScrollUnit, MouseWheel};
+use bevy::picking::hover::HoverMap;
+use bevy::prelude::*;
+
+use crate::locale::LocalizedText;
+use crate::theme::{self, ApplyTheme, HudTheme, TextSize, ThemeColor, ThemedBackground, ThemedText};
+
+/// Pixels scrolled per line of a mouse wheel
+const SCROLL_LINE_HEIGHT: f32 = 20.0;
+
+pub(super) fn plugin(app: &mut App) {
+    app.add_systems(Update, scroll_scroll_areas);
+    app.add_systems(PostUpdate, update_menu_button_colors.after(ApplyTheme));
+}
+
+/// Marks a node with vertical overflow that is scrolled with the mouse wheel while hovered
+#[derive(Component)]
+pub struct ScrollArea;
+
+/// Marker for buttons whose outline and text react to hovering and pressing
+#[derive(Component)]
+pub struct MenuButton;
+
+/// A full screen, black container that centers its children in a column
+pub fn ui_root(name: impl Into<String>) -> impl Bundle {
+    (
+        Name::new(name.into()),
+        Node {
+            position_type: PositionType::Absolute,
+            width: Val::Percent(100.0),
+            height: Val::Percent(100.0),
+            flex_direction: FlexDirection::Column,
+            justify_content: JustifyContent::Center,
+            align_items: AlignItems::Center,
+            row_gap: Val::Px(12.0),
+            ..default()
+        },
+        ThemedBackground(ThemeColor::Background),
+    )
+}
+
+/// Like [`ui_root`] but only dims the screen so the scene stays visible underneath
+pub fn overlay_root(name: impl Into<String>) -> impl Bundle {
+    (
+        Name::new(name.into()),
+        Node {
+            position_type: PositionType::Absolute,
+            width: Val::Percent(100.0),
+            height: Val::Percent(100.0),
+            flex_direction: FlexDirection::Column,
+            justify_content: JustifyContent::Center,
+            align_items: AlignItems::Center,
+            row_gap: Val::Px(12.0),
+            ..default()
+        },
+        BackgroundColor(Color::srgba(0.0, 0.0, 0.0, 0.7)),
+    )
+}
+
+pub fn header(text: impl Into<LocalizedText>) -> impl Bundle {
+    (
+        text.into(),
+        Node {
+            margin: UiRect::bottom(Val::Px(20.0)),
+            ..default()
+        },
+        ThemedText::new(ThemeColor::Text, TextSize::Header),
+        TextLayout::new_with_justify(Justify::Center),
+    )
+}
+
+pub fn label(text: impl Into<LocalizedText>) -> impl Bundle {
+    (
+        text.into(),
+        ThemedText::new(ThemeColor::Highlight, TextSize::Body),
+        TextLayout::new_with_justify(Justify::Center),
+    )
+}
+
+/// A HUD styled button, `action` is inserted on the button entity so menus can react to it
+pub fn button<A: Component>(text: impl Into<LocalizedText>, action: A) -> impl Bundle {
+    sized_button(240.0, text, action)
+}
+
+/// A narrower [`button`] for rows of buttons
+pub fn small_button<A: Component>(text: impl Into<LocalizedText>, action: A) -> impl Bundle {
+    sized_button(110.0, text, action)
+}
+
+fn sized_button<A: Component>(width: f32, text: impl Into<LocalizedText>, action: A) -> impl Bundle {
+    (
+        Node {
+            width: Val::Px(width),
+            height: Val::Px(44.0),
+            justify_content: JustifyContent::Center,
+            align_items: AlignItems::Center,
+            ..default()
+        },
+        theme::panel(2.0),
+        Button,
+        MenuButton,
+        action,
+        children![(
+            text.into(),
+            ThemedText::new(ThemeColor::Text, TextSize::Button),
+            Pickable::IGNORE,
+        )],
+    )
+}
+
+fn update_menu_button_colors(
+    theme: Res<HudTheme>,
+    mut buttons: Query<(Ref<Interaction>, &mut Outline, &Children), With<MenuButton>>,
+    mut texts: Query<&mut TextColor>,
+) {
+    for (interaction, mut outline, children) in buttons.iter_mut() {
+        // a new theme recolours the buttons to their resting colour
+        if !interaction.is_changed() && !theme.is_changed() {
+            continue;
+        }
+        let color = theme.color(match *interaction {
+            Interaction::Pressed => ThemeColor::Dim,
+            Interaction::Hovered => ThemeColor::Highlight,
+            Interaction::None => ThemeColor::Text,
+        });
+        outline.color = color;
+        for child in children.iter() {
+            if let Ok(mut text_color) = texts.get_mut(child) {
+                text_color.0 = color;
+            }
+        }
+    }
+}
+
+/// The scroll areas the pointer is over, directly or through one of their children
+fn hovered_scroll_areas<'a>(
+    hover_map: &'a HoverMap,
+    parents: &'a Query<&ChildOf>,
+    is_area: impl Fn(Entity) -> bool + 'a,
+) -> impl Iterator<Item = Entity> + 'a {
+    hover_map
+        .values()
+        .flat_map(|hits| hits.keys())
+        .filter_map(move |entity| {
+            std::iter::once(*entity)
+                .chain(parents.iter_ancestors(*entity))
+                .find(|e| is_area(*e))
+        })
+}
+
+/// Tells whether the mouse wheel scrolls a [`ScrollArea`] instead of e.g. zooming the camera
+#[derive(SystemParam)]
+pub struct ScrollAreaHover<'w, 's> {
+    hover_map: Res<'w, HoverMap>,
+    parents: Query<'w, 's, &'static ChildOf>,
+    areas: Query<'w, 's, (), With<ScrollArea>>,
+}
+
+impl ScrollAreaHover<'_, '_> {
+    pub fn is_hovered(&self) -> bool {
+        hovered_scroll_areas(&self.hover_map, &self.parents, |e| self.areas.contains(e))
+            .next()
+            .is_some()
+    }
+}
+
+fn scroll_scroll_areas(
+    mut wheel: MessageReader<MouseWheel>,
+    hover_map: Res<HoverMap>,
+    parents: Query<&ChildOf>,
+    mut areas: Query<&mut ScrollPosition, With<ScrollArea>>,
+) {
+    for event in wheel.read() {
+        let dy = match event.unit {
+            MouseScrollUnit::Line => event.y * SCROLL_LINE_HEIGHT,
+            MouseScrollUnit::Pixel => event.y,
+        };
+        let hovered: Vec<Entity> = hovered_scroll_areas(&hover_map, &parents, |e| areas.contains(e)).collect();
+        for entity in hovered {
+            if let Ok(mut position) = areas.get_mut(entity) {
+                position.y = (position.y - dy).max(0.0);
+            }
+        }
+    }
+}
@@ -0,0 +1,52 @@
+//! Storage for data that should outlive a session, like save games.
+//! Native builds write files into a `saves` directory, web builds use the browser's local storage.
+
+#[cfg(not(target_family = "wasm"))]
+const SAVE_DIR: &str = "saves";
+
+/// Reads the entry stored under `key`, if there is one
+#[cfg(not(target_family = "wasm"))]
+pub fn read(key: &str) -> Option<String> {
+    std::fs::read_to_string(std::path::Path::new(SAVE_DIR).join(key)).ok()
+}
+
+/// Stores `contents` under `key`, replacing what was stored there before
+#[cfg(not(target_family = "wasm"))]
+pub fn write(key: &str, contents: &str) -> Result<(), String> {
+    std::fs::create_dir_all(SAVE_DIR).map_err(|e| e.to_string())?;
+    std::fs::write(std::path::Path::new(SAVE_DIR).join(key), contents).map_err(|e| e.to_string())
+}
+
+/// Removes the entry stored under `key`
+#[cfg(not(target_family = "wasm"))]
+pub fn remove(key: &str) {
+    let _ = std::fs::remove_file(std::path::Path::new(SAVE_DIR).join(key));
+}
+
+#[cfg(target_family = "wasm")]
+fn local_storage() -> Option<web_sys::Storage> {
+    web_sys::window()?.local_storage().ok()?
+}
+
+/// Reads the entry stored under `key`, if there is one
+#[cfg(target_family = "wasm")]
+pub fn read(key: &str) -> Option<String> {
+    local_storage()?.get_item(key).ok()?
+}
+
+/// Stores `contents` under `key`, replacing what was stored there before
+#[cfg(target_family = "wasm")]
+pub fn write(key: &str, contents: &str) -> Result<(), String> {
+    local_storage()
+        .ok_or_else(|| "local storage is not available".to_string())?
+        .set_item(key, contents)
+        .map_err(|e| format!("{e:?}"))
+}
+
+/// Removes the entry stored under `key`
+#[cfg(target_family = "wasm")]
+pub fn remove(key: &str) {
+    if let Some(storage) = local_storage() {
+        let _ = storage.remove_item(key);
+    }
+}
@@ -1,6 +1,10 @@
 use bevy::ecs::query::QuerySingleError;
 use crate::physics::directional_forces::{GravityForce, Mass};
 use bevy::prelude::*;
+use std::f32::consts::PI;
+
+/// Gravitational constant used for all bodies in the simulation
+pub const GRAVITATIONAL_CONSTANT: f32 = 6.674e-11;
 
 #[derive(Component, Debug)]
 pub struct Attractor;
@@ -34,8 +38,71 @@ pub fn calc_gravity_force(attractor_mass: &Mass, attractor_transform: &Transform
 }
 
 fn calc_gravity_force_magnitude(m1: f32, m2: f32, r: f32) -> f32 {
-    #[allow(non_snake_case)]
-    let G: f32 = 6.674 * (10.0f32.powi(-11));
-    
-    G * ((m1 * m2) / r.powi(2))
+    GRAVITATIONAL_CONSTANT * ((m1 * m2) / r.powi(2))
+}
+
+/// Velocity a body at `offset` from the attractor needs to follow a Kepler orbit with the given
+/// semi-major axis (vis-viva equation). The returned velocity is perpendicular to `offset` and
+/// orbits counter-clockwise, so it is only exact at the periapsis or apoapsis of the orbit.
+pub fn calc_orbital_velocity(attractor_mass: f32, offset: Vec2, semi_major_axis: f32) -> Vec2 {
+    let r = offset.length();
+    let mu = GRAVITATIONAL_CONSTANT * attractor_mass;
+    let speed = (mu * (2.0 / r - 1.0 / semi_major_axis)).max(0.0).sqrt();
+
+    offset.perp().normalize_or_zero() * speed
+}
+
+/// Shape and timing of a Kepler orbit around a single attractor
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Orbit {
+    pub eccentricity: f32,
+    /// Closest distance to the attractor
+    pub periapsis: f32,
+    /// Seconds for one revolution, `None` if the body escapes
+    pub period: Option<f32>,
+    /// Seconds until the body passes its periapsis next, `None` if it escapes or the orbit is
+    /// (nearly) circular
+    pub time_to_periapsis: Option<f32>,
+}
+
+impl Orbit {
+    /// Whether a body of `radius` on this orbit runs into an attractor of `attractor_radius`
+    pub fn hits_attractor(&self, attractor_radius: f32, radius: f32) -> bool {
+        self.periapsis <= attractor_radius + radius
+    }
+}
+
+/// Orbit of a body at `offset` from the attractor moving with `velocity` relative to it
+pub fn calc_orbit(attractor_mass: f32, offset: Vec2, velocity: Vec2) -> Orbit {
+    let mu = GRAVITATIONAL_CONSTANT * attractor_mass;
+    let r = offset.length();
+    let eccentricity_vector = ((velocity.length_squared() - mu / r) * offset - offset.dot(velocity) * velocity) / mu;
+    let eccentricity = eccentricity_vector.length();
+    let specific_energy = velocity.length_squared() / 2.0 - mu / r;
+    let angular_momentum = offset.perp_dot(velocity);
+    let periapsis = angular_momentum.powi(2) / (mu * (1.0 + eccentricity));
+
+    if specific_energy >= 0.0 || eccentricity >= 1.0 {
+        return Orbit { eccentricity, periapsis, period: None, time_to_periapsis: None };
+    }
+
+    let semi_major_axis = -mu / (2.0 * specific_energy);
+    let mean_motion = (mu / semi_major_axis.powi(3)).sqrt();
+    let period = 2.0 * PI / mean_motion;
+    if eccentricity < 1e-3 {
+        return Orbit { eccentricity, periapsis, period: Some(period), time_to_periapsis: None };
+    }
+
+    // true anomaly, measured in the direction the body travels
+    let direction = angular_momentum.signum();
+    let true_anomaly = eccentricity_vector.angle_to(offset) * direction;
+    let eccentric_anomaly = 2.0 * (((1.0 - eccentricity) / (1.0 + eccentricity)).sqrt() * (true_anomaly / 2.0).tan()).atan();
+    let mean_anomaly = (eccentric_anomaly - eccentricity * eccentric_anomaly.sin()).rem_euclid(2.0 * PI);
+
+    Orbit {
+        eccentricity,
+        periapsis,
+        period: Some(period),
+        time_to_periapsis: Some((2.0 * PI - mean_anomaly) / mean_motion),
+    }
 }
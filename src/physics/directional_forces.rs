@@ -26,8 +26,8 @@ pub(super) fn apply_directional_force(mut query: Query<(Option<&GravityForce>, O
 }
 
 pub fn calc_velocity_change(forces: Vec2, mass: &Mass, time_delta: f32) -> Vec2 {
-    let acceleration = forces / mass.0 as f32;
-    acceleration * time_delta as f32
+    let acceleration = forces / mass.0;
+    acceleration * time_delta
 }
 
 pub(super) fn clear_forces(mut gravity: Query<&mut GravityForce>, mut thrust: Query<&mut ThrustForce>) {
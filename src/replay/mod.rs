@@ -0,0 +1,195 @@
+//! Gameplay inputs and replays. Input systems don't change the run directly, they queue a
+//! [`GameplayInput`] that is applied on the next fixed tick. Every run records its inputs together
+//! with the tick they were applied on. As the simulation only advances on fixed ticks, a run can
+//! be re-simulated from its seed, scenario and recorded inputs.
+
+mod playback;
+
+use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+
+pub use playback::ReplayPlayback;
+
+use crate::launching::CollectorId;
+use crate::persistence;
+use crate::sandbox::SandboxToggle;
+use crate::scenario::{ActiveScenario, Scenario};
+use crate::screens::{InitGameResource, Screen};
+use crate::seed::RunSeed;
+use crate::sun_system::thruster::ThrusterDirection;
+use crate::{AppSystems, GameplaySystem, PausableSystems};
+
+const REPLAY_KEY: &str = "replay.ron";
+/// Replays of other versions are ignored, the simulation might have changed since
+const REPLAY_VERSION: u32 = 1;
+
+pub(super) fn plugin(app: &mut App) {
+    app.init_game_resource::<InputQueue>();
+    app.init_game_resource::<GameplayTick>();
+    app.init_game_resource::<ReplayRecorder>();
+    app.add_systems(
+        FixedUpdate,
+        apply_gameplay_inputs
+            .in_set(AppSystems::RecordInput)
+            .in_set(GameplaySystem)
+            .in_set(PausableSystems),
+    );
+    app.add_systems(OnEnter(Screen::Gameover), write_replay);
+    app.add_plugins(playback::plugin);
+}
+
+/// Something the player did that changes the run
+#[derive(Event, Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub enum GameplayInput {
+    /// Launch a collector from earth, `held` is how long the launch was held in seconds
+    Launch { direction: Vec2, held: f32 },
+    /// Place a collector anywhere, only in the sandbox
+    Place { position: Vec2, velocity: Vec2 },
+    Select(CollectorId),
+    /// Turn off the thrusters of the selected collectors and deselect them
+    ReleaseSelection,
+    /// Toggle the thrusters of all collectors with fuel left
+    ToggleThrusters,
+    /// Turn the thruster of the selected collector on or off
+    SetSelectedThruster(bool),
+    SetBurnDirection(CollectorId, ThrusterDirection),
+    Decommission(CollectorId),
+    TimeSpeed(f32),
+    Sandbox(SandboxToggle),
+}
+
+/// Inputs waiting for the next fixed tick
+#[derive(Resource, Default)]
+pub struct InputQueue(Vec<GameplayInput>);
+
+impl InputQueue {
+    pub fn push(&mut self, input: GameplayInput) {
+        self.0.push(input);
+    }
+}
+
+/// Number of fixed ticks the current run has been simulated for
+#[derive(Resource, Default, Debug, Clone, Copy)]
+pub struct GameplayTick(pub u64);
+
+impl GameplayTick {
+    /// Seconds the current run has been simulated for
+    pub fn elapsed_secs(&self, fixed: &Time<Fixed>) -> f32 {
+        self.0 as f32 * fixed.timestep().as_secs_f32()
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct RecordedInput {
+    tick: u64,
+    input: GameplayInput,
+}
+
+/// Inputs of the current run
+#[derive(Resource)]
+pub struct ReplayRecorder {
+    inputs: Vec<RecordedInput>,
+    enabled: bool,
+}
+
+impl Default for ReplayRecorder {
+    fn default() -> Self {
+        Self { inputs: Vec::new(), enabled: true }
+    }
+}
+
+impl ReplayRecorder {
+    pub fn is_enabled(&self) -> bool {
+        self.enabled
+    }
+
+    /// Stops recording, e.g. for replays or continued runs whose beginning wasn't recorded
+    pub fn stop(&mut self) {
+        self.enabled = false;
+        self.inputs.clear();
+    }
+}
+
+/// Everything needed to re-simulate a run
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Replay {
+    version: u32,
+    seed: RunSeed,
+    scenario: Scenario,
+    /// Tick the run ended on
+    end_tick: u64,
+    inputs: Vec<RecordedInput>,
+}
+
+impl Replay {
+    pub fn scenario(&self) -> &Scenario {
+        &self.scenario
+    }
+}
+
+/// The replay of the last finished run, if there is one
+pub fn load_replay() -> Option<Replay> {
+    let contents = persistence::read(REPLAY_KEY)?;
+    match ron::from_str::<Replay>(&contents) {
+        Ok(replay) if replay.version == REPLAY_VERSION => Some(replay),
+        Ok(replay) => {
+            warn!("Ignoring replay of version {}", replay.version);
+            None
+        }
+        Err(e) => {
+            warn!("Could not read replay: {e}");
+            None
+        }
+    }
+}
+
+fn apply_gameplay_inputs(
+    mut commands: Commands,
+    mut tick: ResMut<GameplayTick>,
+    mut queue: ResMut<InputQueue>,
+    mut recorder: ResMut<ReplayRecorder>,
+    playback: Option<ResMut<ReplayPlayback>>,
+) {
+    tick.0 += 1;
+    if let Some(mut playback) = playback {
+        queue.0.extend(playback.take_inputs(tick.0));
+    }
+    if recorder.enabled {
+        let tick = tick.0;
+        recorder.inputs.extend(queue.0.iter().map(|input| RecordedInput { tick, input: *input }));
+    }
+
+    for input in queue.0.drain(..) {
+        commands.trigger(input);
+    }
+}
+
+fn write_replay(
+    recorder: Res<ReplayRecorder>,
+    tick: Res<GameplayTick>,
+    seed: Res<RunSeed>,
+    scenario: Res<ActiveScenario>,
+) {
+    if !recorder.enabled {
+        return;
+    }
+
+    let replay = Replay {
+        version: REPLAY_VERSION,
+        seed: *seed,
+        scenario: scenario.0.clone(),
+        end_tick: tick.0,
+        inputs: recorder.inputs.clone(),
+    };
+    let contents = match ron::ser::to_string(&replay) {
+        Ok(contents) => contents,
+        Err(e) => {
+            error!("Could not serialize replay: {e}");
+            return;
+        }
+    };
+    match persistence::write(REPLAY_KEY, &contents) {
+        Ok(()) => info!("Replay with {} inputs saved", replay.inputs.len()),
+        Err(e) => error!("Could not write replay: {e}"),
+    }
+}
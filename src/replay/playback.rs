@@ -0,0 +1,277 @@
+//! Watching a replay. The run is re-simulated from its seed and scenario while the recorded inputs
+//! are fed back in on their ticks. Seeking forward fast-forwards the simulation, seeking backward
+//! restarts the run and fast-forwards to the target tick.
+
+use std::time::Duration;
+
+use bevy::prelude::*;
+use rand::SeedableRng;
+use rand_chacha::ChaCha8Rng;
+
+use super::{GameplayInput, GameplayTick, Replay, ReplayRecorder};
+use crate::controls::{Action, InputBindings, InputDevice, action_just_pressed};
+use crate::locale::{LocaleArg, LocalizedText};
+use crate::menus::widget;
+use crate::screens::{InGame, Screen};
+use crate::seed::{RunSeed, reseed_run};
+use crate::{GameplaySystem, PausableSystems, RandomSource};
+
+/// Speeds a replay can be watched at, on top of the recorded time speed
+const PLAYBACK_SPEEDS: [f32; 6] = [0.25, 0.5, 1.0, 2.0, 4.0, 8.0];
+/// Index of normal speed in [`PLAYBACK_SPEEDS`]
+const NORMAL_SPEED: usize = 2;
+/// Seconds skipped by one seek
+const SEEK_STEP: f32 = 10.0;
+/// Time speed while fast-forwarding to a seek target
+const SEEK_SPEED: f32 = 32.0;
+/// Longest frame virtual time advances by while seeking, allows more fixed ticks per frame
+const SEEK_MAX_DELTA: Duration = Duration::from_secs(1);
+/// Bevy's default for [`Time<Virtual>`]
+const DEFAULT_MAX_DELTA: Duration = Duration::from_millis(250);
+
+pub(super) fn plugin(app: &mut App) {
+    app.add_systems(
+        OnEnter(Screen::Gameplay),
+        (start_playback.after(reseed_run), spawn_playback_panel)
+            .run_if(resource_exists::<ReplayPlayback>),
+    );
+    app.add_systems(
+        OnEnter(Screen::Gameover),
+        end_seek.run_if(resource_exists::<ReplayPlayback>),
+    );
+    app.add_systems(OnEnter(Screen::Title), stop_playback);
+    app.add_systems(
+        Update,
+        (
+            handle_playback_action,
+            seek::<-1>.run_if(action_just_pressed(Action::SeekBack)),
+            seek::<1>.run_if(action_just_pressed(Action::SeekForward)),
+            change_playback_speed::<-1>.run_if(action_just_pressed(Action::SlowDown)),
+            change_playback_speed::<1>.run_if(action_just_pressed(Action::SpeedUp)),
+            update_playback_time,
+            update_playback_label,
+        )
+            .chain()
+            .run_if(resource_exists::<ReplayPlayback>)
+            .in_set(GameplaySystem)
+            .in_set(PausableSystems),
+    );
+}
+
+/// The replay being watched, the run is a replay as long as this resource exists
+#[derive(Resource, Debug)]
+pub struct ReplayPlayback {
+    replay: Replay,
+    /// Index of the next recorded input to feed in
+    cursor: usize,
+    /// Index into [`PLAYBACK_SPEEDS`]
+    speed: usize,
+    /// Time speed the recorded run was set to at the current tick
+    recorded_speed: f32,
+    /// Tick the simulation is fast-forwarded to
+    seek_target: Option<u64>,
+}
+
+impl ReplayPlayback {
+    pub fn new(replay: Replay) -> Self {
+        Self {
+            replay,
+            cursor: 0,
+            speed: NORMAL_SPEED,
+            recorded_speed: 1.0,
+            seek_target: None,
+        }
+    }
+
+    pub fn set_recorded_speed(&mut self, speed: f32) {
+        self.recorded_speed = speed;
+    }
+
+    /// Recorded inputs up to and including `tick` that haven't been fed in yet
+    pub(super) fn take_inputs(&mut self, tick: u64) -> Vec<GameplayInput> {
+        let start = self.cursor;
+        while self.replay.inputs.get(self.cursor).is_some_and(|recorded| recorded.tick <= tick) {
+            self.cursor += 1;
+        }
+        self.replay.inputs[start..self.cursor].iter().map(|recorded| recorded.input).collect()
+    }
+}
+
+#[derive(Component, Debug, Clone, Copy, PartialEq, Eq)]
+enum PlaybackAction {
+    SeekBack,
+    SeekForward,
+    Slower,
+    Faster,
+    Exit,
+}
+
+#[derive(Component)]
+struct PlaybackLabel;
+
+fn start_playback(
+    mut playback: ResMut<ReplayPlayback>,
+    mut recorder: ResMut<ReplayRecorder>,
+    mut run_seed: ResMut<RunSeed>,
+    mut random: ResMut<RandomSource>,
+) {
+    recorder.stop();
+    // the seek target survives restarts, that is how seeking backward works
+    playback.cursor = 0;
+    playback.recorded_speed = 1.0;
+    *run_seed = playback.replay.seed;
+    random.0 = ChaCha8Rng::seed_from_u64(run_seed.seed);
+    info!("Replaying run with {}", run_seed.label());
+}
+
+fn end_seek(mut playback: ResMut<ReplayPlayback>, mut time: ResMut<Time<Virtual>>) {
+    playback.seek_target = None;
+    time.set_max_delta(DEFAULT_MAX_DELTA);
+}
+
+fn stop_playback(mut commands: Commands, mut time: ResMut<Time<Virtual>>) {
+    commands.remove_resource::<ReplayPlayback>();
+    time.set_max_delta(DEFAULT_MAX_DELTA);
+}
+
+fn spawn_playback_panel(mut commands: Commands) {
+    commands.spawn((
+        Name::new("Replay Panel"),
+        DespawnOnExit(InGame),
+        Node {
+            position_type: PositionType::Absolute,
+            width: Val::Percent(100.0),
+            bottom: Val::Px(70.0),
+            flex_direction: FlexDirection::Column,
+            align_items: AlignItems::Center,
+            row_gap: Val::Px(8.0),
+            ..default()
+        },
+        Pickable::IGNORE,
+        children![
+            (widget::label(""), PlaybackLabel),
+            (
+                Node {
+                    column_gap: Val::Px(8.0),
+                    ..default()
+                },
+                Pickable::IGNORE,
+                children![
+                    widget::small_button("replay.seek_back", PlaybackAction::SeekBack),
+                    widget::small_button("replay.slower", PlaybackAction::Slower),
+                    widget::small_button("replay.faster", PlaybackAction::Faster),
+                    widget::small_button("replay.seek_forward", PlaybackAction::SeekForward),
+                    widget::small_button("replay.exit", PlaybackAction::Exit),
+                ],
+            ),
+        ],
+    ));
+}
+
+fn handle_playback_action(
+    query: Query<(&Interaction, &PlaybackAction), Changed<Interaction>>,
+    mut playback: ResMut<ReplayPlayback>,
+    tick: Res<GameplayTick>,
+    fixed: Res<Time<Fixed>>,
+    mut next_screen: ResMut<NextState<Screen>>,
+) {
+    for (interaction, action) in query.iter() {
+        if *interaction != Interaction::Pressed {
+            continue;
+        }
+        match action {
+            PlaybackAction::SeekBack => seek_by(&mut playback, &tick, &fixed, &mut next_screen, -1),
+            PlaybackAction::SeekForward => seek_by(&mut playback, &tick, &fixed, &mut next_screen, 1),
+            PlaybackAction::Slower => step_speed(&mut playback, -1),
+            PlaybackAction::Faster => step_speed(&mut playback, 1),
+            PlaybackAction::Exit => next_screen.set(Screen::Title),
+        }
+    }
+}
+
+fn seek<const DIRECTION: i8>(
+    mut playback: ResMut<ReplayPlayback>,
+    tick: Res<GameplayTick>,
+    fixed: Res<Time<Fixed>>,
+    mut next_screen: ResMut<NextState<Screen>>,
+) {
+    seek_by(&mut playback, &tick, &fixed, &mut next_screen, DIRECTION);
+}
+
+fn change_playback_speed<const STEP: i8>(mut playback: ResMut<ReplayPlayback>) {
+    step_speed(&mut playback, STEP);
+}
+
+/// Seeks [`SEEK_STEP`] seconds forward or backward, backward restarts the run
+fn seek_by(
+    playback: &mut ReplayPlayback,
+    tick: &GameplayTick,
+    fixed: &Time<Fixed>,
+    next_screen: &mut NextState<Screen>,
+    direction: i8,
+) {
+    let step = (SEEK_STEP / fixed.timestep().as_secs_f32()) as u64;
+    let from = playback.seek_target.unwrap_or(tick.0);
+    if direction < 0 {
+        let target = from.saturating_sub(step);
+        playback.seek_target = (target > 0).then_some(target);
+        next_screen.set(Screen::Restart);
+    } else {
+        playback.seek_target = Some((from + step).min(playback.replay.end_tick));
+    }
+}
+
+fn step_speed(playback: &mut ReplayPlayback, step: i8) {
+    playback.speed = playback
+        .speed
+        .saturating_add_signed(step as isize)
+        .min(PLAYBACK_SPEEDS.len() - 1);
+}
+
+fn update_playback_time(
+    mut playback: ResMut<ReplayPlayback>,
+    tick: Res<GameplayTick>,
+    mut time: ResMut<Time<Virtual>>,
+) {
+    if playback.seek_target.is_some_and(|target| tick.0 >= target) {
+        playback.seek_target = None;
+    }
+    let (speed, max_delta) = match playback.seek_target {
+        Some(_) => (SEEK_SPEED, SEEK_MAX_DELTA),
+        None => (playback.recorded_speed * PLAYBACK_SPEEDS[playback.speed], DEFAULT_MAX_DELTA),
+    };
+    if time.relative_speed() != speed {
+        time.set_relative_speed(speed);
+    }
+    if time.max_delta() != max_delta {
+        time.set_max_delta(max_delta);
+    }
+}
+
+fn update_playback_label(
+    playback: Res<ReplayPlayback>,
+    tick: Res<GameplayTick>,
+    fixed: Res<Time<Fixed>>,
+    (bindings, device): (Res<InputBindings>, Res<InputDevice>),
+    mut label: Query<&mut LocalizedText, With<PlaybackLabel>>,
+) {
+    let Ok(mut label) = label.single_mut() else { return; };
+    let clock = |ticks: u64| {
+        let secs = GameplayTick(ticks).elapsed_secs(&fixed) as u32;
+        format!("{:02}:{:02}", secs / 60, secs % 60)
+    };
+    let state = match playback.seek_target {
+        Some(_) => LocaleArg::key("replay.seeking"),
+        None => format!("x{}", PLAYBACK_SPEEDS[playback.speed]).into(),
+    };
+    label.set_if_neq(
+        LocalizedText::new("replay.status")
+            .with("time", clock(tick.0))
+            .with("end", clock(playback.replay.end_tick))
+            .with("state", state)
+            .with("back", bindings.prompt(Action::SeekBack, *device))
+            .with("forward", bindings.prompt(Action::SeekForward, *device))
+            .with("faster", bindings.prompt(Action::SpeedUp, *device))
+            .with("slower", bindings.prompt(Action::SlowDown, *device)),
+    );
+}
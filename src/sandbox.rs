@@ -0,0 +1,236 @@
+//! Sandbox mode for experimenting with orbits: no countdown or objectives, optionally infinite
+//! energy, toggles for asteroids and collisions, and collectors can be placed anywhere by dragging
+//! with the right mouse button (rebindable).
+
+use bevy::prelude::*;
+use bevy::window::PrimaryWindow;
+use serde::{Deserialize, Serialize};
+
+use crate::collision::CollisionsEnabled;
+use crate::controls::{Action, InputBindings, InputDevice, action_just_pressed, action_just_released};
+use crate::locale::{LocaleArg, LocalizedText};
+use crate::menus::widget;
+use crate::replay::{GameplayInput, InputQueue};
+use crate::scenario::ActiveScenario;
+use crate::score::Score;
+use crate::screens::{InGame, InitGameResource, Screen};
+use crate::sun_system::asteroids::AsteroidConfig;
+use crate::theme::{HudTheme, ThemeColor};
+use crate::{AppSystems, GameplaySystem, PausableSystems};
+
+/// Stored energy that is kept available while infinite energy is on
+const INFINITE_ENERGY: f32 = 1_000_000.0;
+/// Velocity of a spawned collector per world unit dragged
+const DRAG_VELOCITY_FACTOR: f32 = 0.1;
+
+pub(super) fn plugin(app: &mut App) {
+    app.init_game_resource::<SandboxSettings>();
+    app.init_game_resource::<SpawnDrag>();
+    app.add_systems(OnEnter(Screen::Gameplay), spawn_sandbox_panel.run_if(is_sandbox));
+    app.add_observer(apply_sandbox_toggle);
+    app.add_systems(
+        FixedUpdate,
+        refill_energy
+            .run_if(is_sandbox)
+            .in_set(AppSystems::Update)
+            .in_set(GameplaySystem)
+            .in_set(PausableSystems),
+    );
+    app.add_systems(
+        Update,
+        (
+            start_spawn_drag.run_if(action_just_pressed(Action::SandboxSpawn)),
+            finish_spawn_drag.run_if(action_just_released(Action::SandboxSpawn)),
+            draw_spawn_drag,
+        )
+            .chain()
+            .run_if(is_sandbox)
+            .in_set(AppSystems::RecordInput)
+            .in_set(GameplaySystem)
+            .in_set(PausableSystems),
+    );
+    app.add_systems(
+        Update,
+        (
+            handle_sandbox_action.in_set(AppSystems::RecordInput),
+            update_sandbox_labels,
+            update_sandbox_hint,
+        )
+            .run_if(is_sandbox)
+            .in_set(GameplaySystem),
+    );
+}
+
+pub fn is_sandbox(scenario: Res<ActiveScenario>) -> bool {
+    scenario.0.sandbox
+}
+
+#[derive(Resource, Debug)]
+pub(crate) struct SandboxSettings {
+    pub infinite_energy: bool,
+}
+
+impl Default for SandboxSettings {
+    fn default() -> Self {
+        Self { infinite_energy: true }
+    }
+}
+
+/// World position where the right mouse button was pressed to spawn a collector
+#[derive(Resource, Default)]
+struct SpawnDrag(Option<Vec2>);
+
+#[derive(Component, Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum SandboxToggle {
+    InfiniteEnergy,
+    Asteroids,
+    Collisions,
+}
+
+/// Marks a toggle button, its text shows the current state of the toggle
+#[derive(Component)]
+struct SandboxLabel(SandboxToggle);
+
+/// Explains placing collectors with the current binding
+#[derive(Component)]
+struct SandboxHint;
+
+fn spawn_sandbox_panel(mut commands: Commands) {
+    let toggle = |action: SandboxToggle| {
+        (
+            widget::button("", action),
+            SandboxLabel(action),
+        )
+    };
+    commands.spawn((
+        Name::new("Sandbox Panel"),
+        DespawnOnExit(InGame),
+        Node {
+            position_type: PositionType::Absolute,
+            right: Val::Px(15.0),
+            top: Val::Px(160.0),
+            flex_direction: FlexDirection::Column,
+            align_items: AlignItems::End,
+            row_gap: Val::Px(8.0),
+            ..default()
+        },
+        children![
+            (widget::label("sandbox.hint"), SandboxHint),
+            toggle(SandboxToggle::InfiniteEnergy),
+            toggle(SandboxToggle::Asteroids),
+            toggle(SandboxToggle::Collisions),
+        ],
+    ));
+}
+
+fn handle_sandbox_action(
+    query: Query<(&Interaction, &SandboxToggle), Changed<Interaction>>,
+    mut inputs: ResMut<InputQueue>,
+) {
+    for (interaction, action) in query.iter() {
+        if *interaction == Interaction::Pressed {
+            inputs.push(GameplayInput::Sandbox(*action));
+        }
+    }
+}
+
+fn apply_sandbox_toggle(
+    ev: On<GameplayInput>,
+    mut settings: ResMut<SandboxSettings>,
+    mut asteroids: ResMut<AsteroidConfig>,
+    mut collisions: ResMut<CollisionsEnabled>,
+) {
+    let GameplayInput::Sandbox(toggle) = *ev else { return; };
+    match toggle {
+        SandboxToggle::InfiniteEnergy => settings.infinite_energy = !settings.infinite_energy,
+        SandboxToggle::Asteroids => asteroids.enabled = !asteroids.enabled,
+        SandboxToggle::Collisions => collisions.0 = !collisions.0,
+    }
+}
+
+fn update_sandbox_labels(
+    settings: Res<SandboxSettings>,
+    asteroids: Res<AsteroidConfig>,
+    collisions: Res<CollisionsEnabled>,
+    buttons: Query<(&SandboxLabel, &Children)>,
+    mut texts: Query<&mut LocalizedText>,
+) {
+    let on_off = |on: bool| LocaleArg::key(if on { "common.on" } else { "common.off" });
+    for (label, children) in buttons.iter() {
+        let content = match label.0 {
+            SandboxToggle::InfiniteEnergy => {
+                LocalizedText::new("sandbox.infinite_energy").with("state", on_off(settings.infinite_energy))
+            }
+            SandboxToggle::Asteroids => LocalizedText::new("sandbox.asteroids").with("state", on_off(asteroids.enabled)),
+            SandboxToggle::Collisions => LocalizedText::new("sandbox.collisions").with("state", on_off(collisions.0)),
+        };
+        for child in children.iter() {
+            if let Ok(mut text) = texts.get_mut(child) {
+                text.set_if_neq(content.clone());
+            }
+        }
+    }
+}
+
+fn update_sandbox_hint(
+    bindings: Res<InputBindings>,
+    device: Res<InputDevice>,
+    mut hint: Query<&mut LocalizedText, With<SandboxHint>>,
+) {
+    let content = LocalizedText::new("sandbox.hint").with("key", bindings.prompt(Action::SandboxSpawn, *device));
+    for mut text in hint.iter_mut() {
+        text.set_if_neq(content.clone());
+    }
+}
+
+fn refill_energy(settings: Res<SandboxSettings>, mut score: ResMut<Score>) {
+    if settings.infinite_energy && score.energy_stored < INFINITE_ENERGY {
+        score.energy_stored = INFINITE_ENERGY;
+    }
+}
+
+fn cursor_world_position(
+    window_q: &Query<&Window, With<PrimaryWindow>>,
+    camera_query: &Query<(&Camera, &GlobalTransform)>,
+) -> Option<Vec2> {
+    let cursor = window_q.single().ok()?.cursor_position()?;
+    let (camera, camera_transform) = camera_query.single().ok()?;
+    camera.viewport_to_world_2d(camera_transform, cursor).ok()
+}
+
+fn start_spawn_drag(
+    mut drag: ResMut<SpawnDrag>,
+    window_q: Query<&Window, With<PrimaryWindow>>,
+    camera_query: Query<(&Camera, &GlobalTransform)>,
+) {
+    drag.0 = cursor_world_position(&window_q, &camera_query);
+}
+
+fn finish_spawn_drag(
+    mut drag: ResMut<SpawnDrag>,
+    window_q: Query<&Window, With<PrimaryWindow>>,
+    camera_query: Query<(&Camera, &GlobalTransform)>,
+    mut inputs: ResMut<InputQueue>,
+) {
+    let Some(start) = drag.0.take() else { return; };
+    let Some(end) = cursor_world_position(&window_q, &camera_query) else { return; };
+
+    inputs.push(GameplayInput::Place {
+        position: start,
+        velocity: (end - start) * DRAG_VELOCITY_FACTOR,
+    });
+}
+
+fn draw_spawn_drag(
+    mut gizmos: Gizmos,
+    drag: Res<SpawnDrag>,
+    window_q: Query<&Window, With<PrimaryWindow>>,
+    camera_query: Query<(&Camera, &GlobalTransform)>,
+    theme: Res<HudTheme>,
+) {
+    let Some(start) = drag.0 else { return; };
+    let Some(end) = cursor_world_position(&window_q, &camera_query) else { return; };
+    let color = theme.color(ThemeColor::Accent);
+    gizmos.circle_2d(Isometry2d::from_translation(start), 3.0, color);
+    gizmos.arrow_2d(start, end, color);
+}
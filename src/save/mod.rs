@@ -0,0 +1,380 @@
+//! Saving and loading of a whole run. The run is autosaved periodically and can be continued from
+//! the title screen.
+
+use bevy::ecs::system::SystemParam;
+use bevy::prelude::*;
+use rand_chacha::ChaCha8Rng;
+use serde::{Deserialize, Serialize};
+
+use crate::effects::red_star::{RedStarState, RestoreStarStage};
+use crate::launching::{spawn_collector, CollectorId, CollectorStats, Fuel, NextCollectorId};
+use crate::persistence;
+use crate::physics::velocity::Velocity;
+use crate::campaign::ActiveMission;
+use crate::collision::CollisionsEnabled;
+use crate::sandbox::SandboxSettings;
+use crate::replay::{GameplayTick, ReplayPlayback, ReplayRecorder};
+use crate::scenario::objectives::ObjectiveProgress;
+use crate::scenario::{ActiveScenario, Scenario};
+use crate::score::Score;
+use crate::score::kardashev::KardashevProgress;
+use crate::score::history::EnergyHistory;
+use crate::hud::feed::EventLog;
+use crate::seed::RunSeed;
+use crate::screens::gameover::GameEnd;
+use crate::screens::{InitGameResource, Screen};
+use crate::sun_system::asteroids::{spawn_asteroid, spawn_swarm, Asteroid, AsteroidAssets, AsteroidConfig, AsteroidSwarm, AsteroidTracker};
+use crate::sun_system::earth::Earth;
+use crate::sun_system::navigation_instruments::{NavigationInstruments, OrbitSweep};
+use crate::sun_system::thruster::{Thruster, ThrusterDirection};
+use crate::sun_system::{Level, Satellite, SolarSystemAssets};
+use crate::{GameplaySystem, PausableSystems, RandomSource};
+
+const SAVE_KEY: &str = "savegame.ron";
+/// Version of the [`SaveGame`] layout, bump it on every incompatible change. Saves of other
+/// versions are ignored.
+const SAVE_VERSION: u32 = 4;
+/// Seconds of gameplay between two autosaves
+const AUTOSAVE_INTERVAL: f32 = 30.0;
+
+pub(super) fn plugin(app: &mut App) {
+    app.init_game_resource::<AutosaveTimer>();
+    app.add_observer(save_run);
+    app.add_systems(
+        Update,
+        (
+            apply_pending_load.run_if(resource_exists::<PendingLoad>),
+            autosave,
+        )
+            .chain()
+            .run_if(not(resource_exists::<ReplayPlayback>))
+            .in_set(GameplaySystem)
+            .in_set(PausableSystems),
+    );
+    // a finished run can't be continued
+    app.add_systems(
+        OnEnter(Screen::Gameover),
+        delete_save.run_if(not(resource_exists::<ReplayPlayback>)),
+    );
+}
+
+/// Everything needed to continue a run
+#[derive(Serialize, Deserialize)]
+pub struct SaveGame {
+    version: u32,
+    scenario: Scenario,
+    /// Campaign mission the run belongs to
+    mission: Option<usize>,
+    objectives: ObjectiveProgress,
+    /// Sandbox toggles
+    infinite_energy: bool,
+    asteroids_enabled: bool,
+    collisions_enabled: bool,
+    seed: RunSeed,
+    satellites: Vec<SatelliteSave>,
+    asteroid_swarms: Vec<AsteroidSwarmSave>,
+    earth: Option<BodySave>,
+    /// Rate history timestamps are stored relative to the moment of saving
+    score: Score,
+    remaining_time: f32,
+    star_stage: usize,
+    rng: ChaCha8Rng,
+    /// Timers of the asteroid schedule
+    asteroid_tracker: AsteroidTracker,
+    /// Energy chart of the run, its timestamps are seconds into the run
+    history: EnergyHistory,
+    /// Event feed of the run, for the HUD and the game-over summary
+    log: EventLog,
+    /// Id of the next collector, so the ids in the log stay unique
+    next_collector_id: NextCollectorId,
+    /// Kardashev milestones already paid out
+    kardashev: KardashevProgress,
+}
+
+#[derive(Serialize, Deserialize)]
+struct SatelliteSave {
+    id: CollectorId,
+    translation: Vec3,
+    velocity: Vec2,
+    level: f32,
+    fuel: f32,
+    thruster_active: bool,
+    thruster_strength: f32,
+    thruster_direction: ThrusterDirection,
+    selected: bool,
+    /// How far the collector has flown towards its next full orbit
+    orbit: OrbitSweep,
+    /// Lifetime energy of the collector
+    collected: f32,
+}
+
+#[derive(Serialize, Deserialize)]
+struct AsteroidSwarmSave {
+    translation: Vec3,
+    rotation: Quat,
+    velocity: Vec2,
+    /// Positions of the asteroids relative to the swarm
+    asteroids: Vec<Vec2>,
+}
+
+#[derive(Serialize, Deserialize)]
+struct BodySave {
+    translation: Vec3,
+    velocity: Vec2,
+}
+
+impl SaveGame {
+    /// The scenario the saved run is played with, it has to be active before gameplay starts
+    pub fn scenario(&self) -> &Scenario {
+        &self.scenario
+    }
+
+    /// The campaign mission the saved run belongs to, if any
+    pub fn mission(&self) -> Option<usize> {
+        self.mission
+    }
+}
+
+/// A save that is applied to the world once gameplay has been set up
+#[derive(Resource)]
+pub struct PendingLoad(pub SaveGame);
+
+#[derive(Resource)]
+struct AutosaveTimer(Timer);
+
+impl Default for AutosaveTimer {
+    fn default() -> Self {
+        Self(Timer::from_seconds(AUTOSAVE_INTERVAL, TimerMode::Repeating))
+    }
+}
+
+/// Reads the stored save, if there is one with a compatible version
+pub fn load_save() -> Option<SaveGame> {
+    let contents = persistence::read(SAVE_KEY)?;
+    let save: SaveGame = match ron::from_str(&contents) {
+        Ok(save) => save,
+        Err(e) => {
+            warn!("Ignoring unreadable save game: {e}");
+            return None;
+        }
+    };
+    if save.version != SAVE_VERSION {
+        warn!("Ignoring save game of version {} (expected {})", save.version, SAVE_VERSION);
+        return None;
+    }
+    Some(save)
+}
+
+type SatelliteState = (
+    &'static CollectorId,
+    &'static Transform,
+    &'static Velocity,
+    &'static Level,
+    &'static Fuel,
+    &'static Thruster,
+    Has<NavigationInstruments>,
+    &'static OrbitSweep,
+    &'static CollectorStats,
+);
+
+#[derive(SystemParam)]
+struct RunSnapshot<'w, 's> {
+    satellites: Query<'w, 's, SatelliteState, With<Satellite>>,
+    swarms: Query<'w, 's, (&'static Transform, &'static Velocity, &'static Children), With<AsteroidSwarm>>,
+    asteroids: Query<'w, 's, &'static Transform, With<Asteroid>>,
+    earth: Query<'w, 's, (&'static Transform, &'static Velocity), With<Earth>>,
+    score: Res<'w, Score>,
+    game_end: Res<'w, GameEnd>,
+    red_star: Res<'w, RedStarState>,
+    rng: Res<'w, RandomSource>,
+    asteroid_tracker: Res<'w, AsteroidTracker>,
+    time: Res<'w, Time>,
+    tick: Res<'w, GameplayTick>,
+    fixed: Res<'w, Time<Fixed>>,
+    scenario: Res<'w, ActiveScenario>,
+    mission: Res<'w, ActiveMission>,
+    objectives: Res<'w, ObjectiveProgress>,
+    sandbox: (Res<'w, SandboxSettings>, Res<'w, AsteroidConfig>, Res<'w, CollisionsEnabled>),
+    history: Res<'w, EnergyHistory>,
+    log: Res<'w, EventLog>,
+    seed: Res<'w, RunSeed>,
+    next_collector_id: Res<'w, NextCollectorId>,
+    kardashev: Res<'w, KardashevProgress>,
+}
+
+impl RunSnapshot<'_, '_> {
+    fn to_save(&self) -> SaveGame {
+        let now = self.time.elapsed_secs();
+        let mut score = self.score.clone();
+        score.rebase_history(-now);
+
+        SaveGame {
+            version: SAVE_VERSION,
+            scenario: self.scenario.0.clone(),
+            mission: self.mission.0,
+            objectives: self.objectives.clone(),
+            infinite_energy: self.sandbox.0.infinite_energy,
+            asteroids_enabled: self.sandbox.1.enabled,
+            collisions_enabled: self.sandbox.2.0,
+            seed: *self.seed,
+            satellites: self
+                .satellites
+                .iter()
+                .map(|(id, transform, velocity, level, fuel, thruster, selected, orbit, stats)| SatelliteSave {
+                    id: *id,
+                    translation: transform.translation,
+                    velocity: velocity.0,
+                    level: level.level,
+                    fuel: fuel.amount,
+                    thruster_active: thruster.active,
+                    thruster_strength: thruster.strength,
+                    thruster_direction: thruster.direction,
+                    selected,
+                    orbit: *orbit,
+                    collected: stats.total_collected,
+                })
+                .collect(),
+            asteroid_swarms: self
+                .swarms
+                .iter()
+                .map(|(transform, velocity, children)| AsteroidSwarmSave {
+                    translation: transform.translation,
+                    rotation: transform.rotation,
+                    velocity: velocity.0,
+                    asteroids: children
+                        .iter()
+                        .filter_map(|child| self.asteroids.get(child).ok())
+                        .map(|t| t.translation.xy())
+                        .collect(),
+                })
+                .collect(),
+            earth: self.earth.iter().next().map(|(transform, velocity)| BodySave {
+                translation: transform.translation,
+                velocity: velocity.0,
+            }),
+            score,
+            remaining_time: (self.game_end.game_end_time - self.tick.elapsed_secs(&self.fixed)).max(0.0),
+            star_stage: self.red_star.stage,
+            rng: self.rng.0.clone(),
+            asteroid_tracker: self.asteroid_tracker.clone(),
+            history: self.history.clone(),
+            log: self.log.clone(),
+            next_collector_id: *self.next_collector_id,
+            kardashev: self.kardashev.clone(),
+        }
+    }
+}
+
+/// Saves the run right away, e.g. before leaving it for the title screen
+#[derive(Event, Debug)]
+pub struct SaveRun;
+
+fn save_run(_: On<SaveRun>, snapshot: RunSnapshot, playback: Option<Res<ReplayPlayback>>) {
+    // a replay is never saved, like it isn't autosaved
+    if playback.is_none() {
+        write_save(&snapshot);
+    }
+}
+
+fn autosave(mut timer: ResMut<AutosaveTimer>, time: Res<Time>, snapshot: RunSnapshot) {
+    timer.0.tick(time.delta());
+    if timer.0.just_finished() {
+        write_save(&snapshot);
+    }
+}
+
+fn write_save(snapshot: &RunSnapshot) {
+    let contents = match ron::ser::to_string_pretty(&snapshot.to_save(), ron::ser::PrettyConfig::default()) {
+        Ok(contents) => contents,
+        Err(e) => {
+            error!("Could not serialize save game: {e}");
+            return;
+        }
+    };
+    match persistence::write(SAVE_KEY, &contents) {
+        Ok(()) => info!("Game saved"),
+        Err(e) => error!("Could not write save game: {e}"),
+    }
+}
+
+fn apply_pending_load(world: &mut World) {
+    let Some(PendingLoad(save)) = world.remove_resource::<PendingLoad>() else { return; };
+    info!("Loading save game with {} satellites", save.satellites.len());
+    let now = world.resource::<Time>().elapsed_secs();
+
+    let mut score = save.score;
+    score.rebase_history(now);
+    world.insert_resource(score);
+    world.insert_resource(save.objectives);
+    world.resource_mut::<SandboxSettings>().infinite_energy = save.infinite_energy;
+    world.resource_mut::<AsteroidConfig>().enabled = save.asteroids_enabled;
+    world.resource_mut::<CollisionsEnabled>().0 = save.collisions_enabled;
+    world.insert_resource(save.history);
+    world.insert_resource(save.log);
+    world.insert_resource(save.kardashev);
+    {
+        // whether the countdown runs at all has already been set up from the saved scenario
+        let run_time = world.resource::<GameplayTick>().elapsed_secs(world.resource::<Time<Fixed>>());
+        let mut game_end = world.resource_mut::<GameEnd>();
+        game_end.game_end_time = run_time + save.remaining_time;
+    }
+    world.resource_mut::<RandomSource>().0 = save.rng;
+    world.insert_resource(save.asteroid_tracker);
+    world.insert_resource(save.seed);
+    // the run didn't start from its seed in this session, so it can't be replayed
+    world.resource_mut::<ReplayRecorder>().stop();
+
+    if let Some(earth) = save.earth {
+        let mut earth_q = world.query_filtered::<(&mut Transform, &mut Velocity), With<Earth>>();
+        for (mut transform, mut velocity) in earth_q.iter_mut(world) {
+            transform.translation = earth.translation;
+            velocity.0 = earth.velocity;
+        }
+    }
+
+    let solar_system_assets = world.resource::<SolarSystemAssets>().clone();
+    let asteroid_assets = world.resource::<AsteroidAssets>().clone();
+    let mut commands = world.commands();
+
+    for sat in save.satellites {
+        let id = spawn_collector(&mut commands, &solar_system_assets, sat.level, sat.translation, sat.velocity, Some(sat.id));
+        let mut entity = commands.entity(id);
+        entity.insert((
+            Fuel { amount: sat.fuel },
+            sat.orbit,
+            Thruster {
+                active: sat.thruster_active,
+                strength: sat.thruster_strength,
+                direction: sat.thruster_direction,
+            },
+            CollectorStats {
+                energy_rate: 0.0,
+                output: 0.0,
+                total_collected: sat.collected,
+            },
+        ));
+        if !sat.selected {
+            entity.remove::<NavigationInstruments>();
+        }
+    }
+
+    for swarm in save.asteroid_swarms {
+        let transform = Transform::from_translation(swarm.translation).with_rotation(swarm.rotation);
+        let swarm_id = spawn_swarm(&mut commands, transform, swarm.velocity);
+        for position in swarm.asteroids {
+            spawn_asteroid(&mut commands, &asteroid_assets, swarm_id, position);
+        }
+    }
+
+    // the first stage is already set up from the scenario
+    if save.star_stage > 0 {
+        commands.trigger(RestoreStarStage(save.star_stage));
+    }
+    world.flush();
+    // the loaded collectors have been handed ids of their own, the saved ones win
+    world.insert_resource(save.next_collector_id);
+}
+
+fn delete_save() {
+    persistence::remove(SAVE_KEY);
+}
@@ -0,0 +1,208 @@
+//! Scenarios describe the starting conditions and rules of a run. They are loaded from
+//! `assets/scenarios/*.scenario.ron` files and picked from the scenario menu.
+
+pub mod objectives;
+
+use bevy::asset::io::Reader;
+use bevy::asset::{AssetLoader, LoadContext};
+use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+
+use crate::asset_tracking::LoadResource;
+
+/// Scenario files offered in the scenario menu, in the order they are listed.
+/// Listed explicitly because web builds can't load whole folders.
+const SCENARIO_FILES: &[&str] = &[
+    "scenarios/classic.scenario.ron",
+    "scenarios/blue_dwarf.scenario.ron",
+    "scenarios/asteroid_belt.scenario.ron",
+    "scenarios/endless.scenario.ron",
+];
+
+pub(super) fn plugin(app: &mut App) {
+    app.init_asset::<Scenario>();
+    app.init_asset_loader::<ScenarioLoader>();
+    app.load_resource::<ScenarioAssets>();
+    app.init_resource::<ActiveScenario>();
+    app.add_plugins(objectives::plugin);
+}
+
+#[derive(Asset, TypePath, Serialize, Deserialize, Debug, Clone)]
+pub struct Scenario {
+    /// Key of the name in the string tables
+    pub name: String,
+    /// Key of the description in the string tables
+    pub description: String,
+    pub sun: SunSettings,
+    pub earth: OrbitSettings,
+    /// Energy in YTh stored at the start of the run
+    pub starting_energy: f32,
+    /// Energy a level 1 collector costs, higher levels cost a multiple of it
+    pub satellite_price: f32,
+    /// Seconds until the run ends, runs without a limit only end through their conditions
+    pub time_limit: Option<f32>,
+    /// The run is won as soon as any of these is met
+    pub win: Vec<WinCondition>,
+    /// The run is lost as soon as any of these is met
+    pub lose: Vec<LoseCondition>,
+    pub hazards: HazardSettings,
+    /// Sandbox runs come with tools for experimenting, see [`crate::sandbox`]
+    #[serde(default)]
+    pub sandbox: bool,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct SunSettings {
+    pub mass: f32,
+    /// Hitbox radius, everything inside gets swallowed
+    pub radius: f32,
+}
+
+impl SunSettings {
+    /// Scale of the sun sprite, the sprite grows with the hitbox
+    pub fn sprite_scale(&self) -> f32 {
+        0.001 * self.radius
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct OrbitSettings {
+    /// Closest distance to the sun, the body starts its orbit here
+    pub periapsis: f32,
+    /// 0 is a perfect circle
+    pub eccentricity: f32,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq)]
+pub enum WinCondition {
+    /// Energy rate in YW reaches the value
+    EnergyRate(f32),
+    /// Stored energy in YTh reaches the value
+    EnergyStored(f32),
+    /// Number of collectors in space reaches the value
+    Satellites(usize),
+    /// At least `count` collectors stay in space for `seconds` without interruption
+    KeepCollectors { count: usize, seconds: f32 },
+    /// For the given number of asteroid waves, a collector that was in space when the wave appeared
+    /// is still there some time later
+    SurviveAsteroidWaves(u32),
+    /// Collectors complete the given number of full orbits around the sun
+    FullOrbits(u32),
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq)]
+pub enum LoseCondition {
+    /// Stored energy drops below the value
+    EnergyBelow(f32),
+    /// No collector is left in space and there isn't enough energy to launch another one
+    Stranded,
+    /// More energy than the value has been spent on launching collectors
+    OverBudget(f32),
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct HazardSettings {
+    pub asteroids: bool,
+    /// chance (evaluated per frame) that an asteroid swarm will spawn, expressed as 1 / $this
+    pub asteroid_spawn_chance: usize,
+    /// Minimum time between asteroid swarms in seconds
+    pub asteroid_min_time_between: usize,
+    /// Minimum time that the game should be running before the first swarm appears
+    pub asteroid_min_initial_wait: usize,
+}
+
+impl Default for Scenario {
+    /// The original hard-coded game, used until a scenario is picked
+    fn default() -> Self {
+        Self {
+            name: "scenario.classic.name".to_string(),
+            description: "scenario.classic.description".to_string(),
+            sun: SunSettings {
+                mass: 100_000_000_000_000.0,
+                radius: 20.0,
+            },
+            earth: OrbitSettings {
+                periapsis: 100.0,
+                eccentricity: 0.0167,
+            },
+            starting_energy: 8000.0,
+            satellite_price: 500.0,
+            time_limit: Some(600.0),
+            // 400 Yottawatt are 4 x 10^26, Kardashev type two
+            win: vec![WinCondition::EnergyRate(400.0)],
+            lose: vec![],
+            hazards: HazardSettings {
+                asteroids: true,
+                asteroid_spawn_chance: 500,
+                asteroid_min_time_between: 60,
+                asteroid_min_initial_wait: 45,
+            },
+            sandbox: false,
+        }
+    }
+}
+
+impl Scenario {
+    /// Free play without countdown or objectives
+    pub fn sandbox() -> Self {
+        let classic = Self::default();
+        Self {
+            name: "scenario.sandbox.name".to_string(),
+            description: "scenario.sandbox.description".to_string(),
+            time_limit: None,
+            win: vec![],
+            lose: vec![],
+            hazards: HazardSettings {
+                asteroids: false,
+                ..classic.hazards
+            },
+            sandbox: true,
+            ..classic
+        }
+    }
+}
+
+/// The scenario the current (or next) run is played with
+#[derive(Resource, Default, Debug, Clone)]
+pub struct ActiveScenario(pub Scenario);
+
+/// All scenarios that can be picked in the scenario menu
+#[derive(Resource, Asset, Reflect, Clone)]
+#[reflect(Resource)]
+pub struct ScenarioAssets {
+    #[dependency]
+    pub scenarios: Vec<Handle<Scenario>>,
+}
+
+impl FromWorld for ScenarioAssets {
+    fn from_world(world: &mut World) -> Self {
+        let assets = world.resource::<AssetServer>();
+        Self {
+            scenarios: SCENARIO_FILES.iter().map(|path| assets.load(*path)).collect(),
+        }
+    }
+}
+
+#[derive(Default)]
+struct ScenarioLoader;
+
+impl AssetLoader for ScenarioLoader {
+    type Asset = Scenario;
+    type Settings = ();
+    type Error = BevyError;
+
+    async fn load(
+        &self,
+        reader: &mut dyn Reader,
+        _settings: &(),
+        _load_context: &mut LoadContext<'_>,
+    ) -> Result<Scenario, BevyError> {
+        let mut bytes = Vec::new();
+        reader.read_to_end(&mut bytes).await?;
+        Ok(ron::de::from_bytes(&bytes)?)
+    }
+
+    fn extensions(&self) -> &[&str] {
+        &["scenario.ron"]
+    }
+}
@@ -0,0 +1,217 @@
+//! Tracking of the win and lose conditions of the active scenario, including progress that can't be
+//! read off the current state of the run, like how long collectors have been kept alive.
+
+use bevy::ecs::system::SystemParam;
+use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+
+use crate::achievements::FullOrbitAchieved;
+use crate::launching::{CollectorId, SatellitePriceFactor};
+use crate::locale::{LocaleArg, Localization};
+use crate::scenario::{ActiveScenario, LoseCondition, WinCondition};
+use crate::score::Score;
+use crate::screens::{InGame, InitGameResource, Screen};
+use crate::sun_system::asteroids::AsteroidSwarmSpawned;
+use crate::sun_system::Satellite;
+use crate::theme::{TextSize, ThemeColor, ThemedText};
+use crate::{AppSystems, GameplaySystem, PausableSystems};
+
+/// Seconds a collector that was in space when an asteroid wave appeared has to stay there for the
+/// wave to count as survived
+const WAVE_SURVIVAL_TIME: f32 = 30.0;
+
+pub(super) fn plugin(app: &mut App) {
+    app.init_game_resource::<ObjectiveProgress>();
+    app.add_observer(count_full_orbit);
+    app.add_observer(track_asteroid_wave);
+    app.add_systems(OnEnter(Screen::Gameplay), spawn_objective_panel);
+    app.add_systems(
+        FixedUpdate,
+        update_objective_progress
+            .in_set(AppSystems::Update)
+            .in_set(GameplaySystem)
+            .in_set(PausableSystems),
+    );
+    app.add_systems(
+        Update,
+        update_objective_panel
+            .in_set(GameplaySystem)
+            .in_set(PausableSystems),
+    );
+}
+
+#[derive(Resource, Default, Debug, Clone, Serialize, Deserialize)]
+pub struct ObjectiveProgress {
+    /// Per win condition of the scenario, seconds its collector count has been held without
+    /// interruption. Only used by [`WinCondition::KeepCollectors`].
+    hold_timers: Vec<f32>,
+    pending_waves: Vec<PendingWave>,
+    pub waves_survived: u32,
+    pub full_orbits: u32,
+}
+
+/// An asteroid wave that appeared less than [`WAVE_SURVIVAL_TIME`] ago
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct PendingWave {
+    /// Seconds left until the wave is decided
+    remaining: f32,
+    /// Collectors in space when the wave appeared, the wave is survived if any of them is left
+    collectors: Vec<CollectorId>,
+}
+
+/// Everything needed to decide whether the run is won or lost
+#[derive(SystemParam)]
+pub struct RunStatus<'w, 's> {
+    scenario: Res<'w, ActiveScenario>,
+    score: Res<'w, Score>,
+    progress: Res<'w, ObjectiveProgress>,
+    price: Res<'w, SatellitePriceFactor>,
+    satellites: Query<'w, 's, (), With<Satellite>>,
+}
+
+impl RunStatus<'_, '_> {
+    /// Whether any win condition of the scenario is met
+    pub fn won(&self) -> bool {
+        let satellite_count = self.satellites.iter().count();
+        self.scenario.0.win.iter().enumerate().any(|(i, condition)| match *condition {
+            WinCondition::EnergyRate(rate) => self.score.energy_rate >= rate,
+            WinCondition::EnergyStored(energy) => self.score.energy_stored >= energy,
+            WinCondition::Satellites(count) => satellite_count >= count,
+            WinCondition::KeepCollectors { seconds, .. } => self.hold_time(i) >= seconds,
+            WinCondition::SurviveAsteroidWaves(waves) => self.progress.waves_survived >= waves,
+            WinCondition::FullOrbits(orbits) => self.progress.full_orbits >= orbits,
+        })
+    }
+
+    /// Whether any lose condition of the scenario is met
+    pub fn lost(&self) -> bool {
+        let satellite_count = self.satellites.iter().count();
+        self.scenario.0.lose.iter().any(|condition| match *condition {
+            LoseCondition::EnergyBelow(energy) => self.score.energy_stored < energy,
+            LoseCondition::Stranded => satellite_count == 0 && self.score.energy_stored < self.price.factor,
+            LoseCondition::OverBudget(budget) => self.score.energy_spent > budget,
+        })
+    }
+
+    /// One line per objective of the scenario, describing how far along it is
+    fn describe(&self, locale: &Localization) -> Vec<String> {
+        let satellite_count = self.satellites.iter().count();
+        let progress = |key: &str, goal: LocaleArg, current: LocaleArg| {
+            locale.format(key, &[("goal", goal.clone()), ("current", current), ("target", goal)])
+        };
+        let amount = |value: f32| LocaleArg::number(value, 0);
+        let mut lines: Vec<String> = self.scenario.0.win.iter().enumerate().map(|(i, condition)| match *condition {
+            WinCondition::EnergyRate(rate) => progress("objective.energy_rate", amount(rate), amount(self.score.energy_rate)),
+            WinCondition::EnergyStored(energy) => {
+                progress("objective.energy_stored", amount(energy), amount(self.score.energy_stored))
+            }
+            WinCondition::Satellites(count) => progress("objective.satellites", count.into(), satellite_count.into()),
+            WinCondition::KeepCollectors { count, seconds } => locale.format(
+                "objective.keep_collectors",
+                &[
+                    ("count", count.into()),
+                    ("current", amount(self.hold_time(i).min(seconds))),
+                    ("target", amount(seconds)),
+                ],
+            ),
+            WinCondition::SurviveAsteroidWaves(waves) => {
+                progress("objective.asteroid_waves", waves.into(), self.progress.waves_survived.into())
+            }
+            WinCondition::FullOrbits(orbits) => progress("objective.full_orbits", orbits.into(), self.progress.full_orbits.into()),
+        }).collect();
+        for condition in self.scenario.0.lose.iter() {
+            if let LoseCondition::OverBudget(budget) = *condition {
+                lines.push(progress("objective.budget", amount(budget), amount(self.score.energy_spent)));
+            }
+        }
+        lines
+    }
+
+    fn hold_time(&self, condition: usize) -> f32 {
+        self.progress.hold_timers.get(condition).copied().unwrap_or(0.0)
+    }
+}
+
+fn count_full_orbit(_ev: On<FullOrbitAchieved>, mut progress: ResMut<ObjectiveProgress>) {
+    progress.full_orbits += 1;
+}
+
+fn track_asteroid_wave(
+    _ev: On<AsteroidSwarmSpawned>,
+    mut progress: ResMut<ObjectiveProgress>,
+    collectors: Query<&CollectorId, With<Satellite>>,
+) {
+    progress.pending_waves.push(PendingWave {
+        remaining: WAVE_SURVIVAL_TIME,
+        collectors: collectors.iter().copied().collect(),
+    });
+}
+
+fn update_objective_progress(
+    mut progress: ResMut<ObjectiveProgress>,
+    scenario: Res<ActiveScenario>,
+    satellites: Query<&CollectorId, With<Satellite>>,
+    time: Res<Time>,
+) {
+    let satellite_count = satellites.iter().count();
+    let dt = time.delta_secs();
+
+    let conditions = &scenario.0.win;
+    progress.hold_timers.resize(conditions.len(), 0.0);
+    for (timer, condition) in progress.hold_timers.iter_mut().zip(conditions.iter()) {
+        if let WinCondition::KeepCollectors { count, .. } = *condition {
+            // losing a single collector restarts the objective
+            *timer = if satellite_count >= count { *timer + dt } else { 0.0 };
+        }
+    }
+
+    let mut survived = 0;
+    progress.pending_waves.retain_mut(|wave| {
+        wave.remaining -= dt;
+        if wave.remaining > 0.0 {
+            return true;
+        }
+        // collectors launched after the wave appeared don't count
+        if satellites.iter().any(|id| wave.collectors.contains(id)) {
+            survived += 1;
+        }
+        false
+    });
+    progress.waves_survived += survived;
+}
+
+#[derive(Component)]
+struct ObjectivePanelText;
+
+fn spawn_objective_panel(mut commands: Commands) {
+    commands.spawn((
+        Name::new("Objective Panel"),
+        DespawnOnExit(InGame),
+        Pickable::IGNORE,
+        Node {
+            position_type: PositionType::Absolute,
+            top: Val::Px(15.0),
+            width: Val::Percent(100.0),
+            justify_content: JustifyContent::Center,
+            ..default()
+        },
+        children![(
+            ObjectivePanelText,
+            Text::new(""),
+            ThemedText::new(ThemeColor::Text, TextSize::Small),
+            TextLayout::new_with_justify(Justify::Center),
+        )],
+    ));
+}
+
+fn update_objective_panel(
+    status: RunStatus,
+    locale: Res<Localization>,
+    mut text_q: Query<&mut Text, With<ObjectivePanelText>>,
+) {
+    let Ok(mut text) = text_q.single_mut() else { return; };
+    let content = format!("{}\n{}", locale.get(&status.scenario.0.name), status.describe(&locale).join("\n"));
+    if text.0 != content {
+        text.0 = content;
+    }
+}
@@ -0,0 +1,151 @@
+//! Energy history of the whole run for the charts, sampled once per second together with the
+//! moments that shaped it: launches, crashes, asteroid swarms and the sun changing stage.
+
+use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+
+use super::{Score, update_score};
+use crate::collision::FatalCollisionEvent;
+use crate::effects::red_star::StarStageReached;
+use crate::launching::CollectorLaunched;
+use crate::screens::InitGameResource;
+use crate::sun_system::Satellite;
+use crate::sun_system::asteroids::AsteroidSwarmSpawned;
+use crate::{AppSystems, GameplaySystem, PausableSystems};
+
+/// Seconds between two samples at the start of a run
+const SAMPLE_INTERVAL: f32 = 1.0;
+/// Samples kept before pairs of them are merged and the interval doubles, an hour at the start
+const MAX_SAMPLES: usize = 3600;
+
+pub(super) fn plugin(app: &mut App) {
+    app.init_game_resource::<EnergyHistory>();
+    app.add_systems(
+        FixedUpdate,
+        record_energy_sample
+            .after(update_score)
+            .in_set(AppSystems::Update)
+            .in_set(GameplaySystem)
+            .in_set(PausableSystems),
+    );
+    app.add_observer(record_launch);
+    app.add_observer(record_crash);
+    app.add_observer(record_swarm);
+    app.add_observer(record_star_stage);
+}
+
+/// Saved with the run, so a continued run keeps its chart and the times of its log
+#[derive(Resource, Debug, Clone, Serialize, Deserialize)]
+pub struct EnergyHistory {
+    pub samples: Vec<EnergySample>,
+    pub events: Vec<HistoryEvent>,
+    /// Seconds of gameplay recorded
+    elapsed: f32,
+    /// Seconds between two samples, doubles whenever the samples are merged
+    #[serde(default = "default_interval")]
+    interval: f32,
+}
+
+impl Default for EnergyHistory {
+    fn default() -> Self {
+        Self {
+            samples: Vec::new(),
+            events: Vec::new(),
+            elapsed: 0.0,
+            interval: SAMPLE_INTERVAL,
+        }
+    }
+}
+
+fn default_interval() -> f32 {
+    SAMPLE_INTERVAL
+}
+
+impl EnergyHistory {
+    pub fn duration(&self) -> f32 {
+        self.elapsed
+    }
+
+    /// Halves the samples by merging neighbours, so long runs keep evenly spaced samples
+    fn merge_samples(&mut self) {
+        self.samples = self
+            .samples
+            .chunks(2)
+            .map(|pair| {
+                let last = pair[pair.len() - 1];
+                EnergySample {
+                    rate: pair.iter().map(|sample| sample.rate).sum::<f32>() / pair.len() as f32,
+                    ..last
+                }
+            })
+            .collect();
+        self.interval *= 2.0;
+    }
+
+    fn record(&mut self, kind: HistoryEventKind) {
+        self.events.push(HistoryEvent { time: self.elapsed, kind });
+    }
+}
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+pub struct EnergySample {
+    pub time: f32,
+    pub rate: f32,
+    pub stored: f32,
+}
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+pub struct HistoryEvent {
+    pub time: f32,
+    pub kind: HistoryEventKind,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum HistoryEventKind {
+    Launch,
+    Crash,
+    Swarm,
+    /// The sun grew into a later stage, e.g. a red giant
+    StarStage,
+}
+
+fn record_energy_sample(mut history: ResMut<EnergyHistory>, score: Res<Score>, time: Res<Time>) {
+    history.elapsed += time.delta_secs();
+    let due = history
+        .samples
+        .last()
+        .is_none_or(|last| history.elapsed - last.time >= history.interval);
+    if due {
+        if history.samples.len() >= MAX_SAMPLES {
+            history.merge_samples();
+        }
+        let sample = EnergySample {
+            time: history.elapsed,
+            rate: score.energy_rate,
+            stored: score.energy_stored,
+        };
+        history.samples.push(sample);
+    }
+}
+
+fn record_launch(_ev: On<CollectorLaunched>, mut history: ResMut<EnergyHistory>) {
+    history.record(HistoryEventKind::Launch);
+}
+
+fn record_crash(
+    ev: On<FatalCollisionEvent>,
+    satellites: Query<(), With<Satellite>>,
+    mut history: ResMut<EnergyHistory>,
+) {
+    if satellites.contains(ev.destroyed) {
+        history.record(HistoryEventKind::Crash);
+    }
+}
+
+fn record_swarm(_ev: On<AsteroidSwarmSpawned>, mut history: ResMut<EnergyHistory>) {
+    history.record(HistoryEventKind::Swarm);
+}
+
+fn record_star_stage(_ev: On<StarStageReached>, mut history: ResMut<EnergyHistory>) {
+    history.record(HistoryEventKind::StarStage);
+}
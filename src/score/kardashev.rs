@@ -0,0 +1,98 @@
+//! Kardashev type of the civilisation, after Sagan's formula K = (log10(P) - 6) / 10 with P the
+//! power in watts. Crossing a milestone type during a run pays out a reward of stored energy.
+
+use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+
+use super::{Score, update_score};
+use crate::screens::InitGameResource;
+use crate::{AppSystems, GameplaySystem, PausableSystems};
+
+/// Watts in a yottawatt, the unit of the energy rate
+const WATTS_PER_YOTTAWATT: f32 = 1e24;
+
+pub(super) fn plugin(app: &mut App) {
+    app.init_resource::<KardashevMilestones>();
+    app.init_game_resource::<KardashevProgress>();
+    app.add_systems(
+        FixedUpdate,
+        check_milestones
+            .after(update_score)
+            .in_set(AppSystems::Update)
+            .in_set(GameplaySystem)
+            .in_set(PausableSystems),
+    );
+}
+
+/// Kardashev type of a civilisation harvesting `energy_rate` yottawatts, used by the HUD meter
+/// and for the final result
+pub fn kardashev_type(energy_rate: f32) -> f32 {
+    let watts = (energy_rate * WATTS_PER_YOTTAWATT).max(1.0);
+    (watts.log10() - 6.0) / 10.0
+}
+
+#[derive(Debug, Clone)]
+pub struct Milestone {
+    pub ktype: f32,
+    /// Energy added to the storage when the milestone is crossed
+    pub reward: f32,
+}
+
+/// Milestones in ascending order of their type
+#[derive(Resource, Debug, Clone)]
+pub struct KardashevMilestones(pub Vec<Milestone>);
+
+impl Default for KardashevMilestones {
+    fn default() -> Self {
+        Self(vec![
+            Milestone { ktype: 1.98, reward: 1_000.0 },
+            // 100 YW, Sagan's type II
+            Milestone { ktype: 2.0, reward: 3_000.0 },
+            Milestone { ktype: 2.03, reward: 6_000.0 },
+            // 400 YW, all the power of the sun
+            Milestone { ktype: 2.06, reward: 10_000.0 },
+        ])
+    }
+}
+
+/// Milestones crossed in the current run, saved with it so a continued run doesn't pay them again
+#[derive(Resource, Debug, Default, Clone, Serialize, Deserialize)]
+pub struct KardashevProgress {
+    /// Number of milestones crossed
+    reached: usize,
+}
+
+impl KardashevProgress {
+    pub fn reached(&self) -> usize {
+        self.reached
+    }
+}
+
+/// A milestone was crossed and its reward paid out
+#[derive(Event, Debug)]
+pub struct MilestoneReached {
+    pub ktype: f32,
+    pub reward: f32,
+}
+
+fn check_milestones(
+    mut commands: Commands,
+    mut score: ResMut<Score>,
+    milestones: Res<KardashevMilestones>,
+    mut progress: ResMut<KardashevProgress>,
+) {
+    let ktype = kardashev_type(score.energy_rate);
+    let crossed = milestones.0.iter().take_while(|m| m.ktype <= ktype).count();
+    if crossed <= progress.reached {
+        return;
+    }
+    for milestone in &milestones.0[progress.reached..crossed] {
+        info!("Kardashev type {} reached, rewarding {} YTh", milestone.ktype, milestone.reward);
+        score.energy_stored += milestone.reward;
+        commands.trigger(MilestoneReached {
+            ktype: milestone.ktype,
+            reward: milestone.reward,
+        });
+    }
+    progress.reached = crossed;
+}
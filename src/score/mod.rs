@@ -1,19 +1,35 @@
+pub mod history;
+pub mod kardashev;
+
 use bevy::ecs::relationship::Relationship;
 use bevy::prelude::*;
-use crate::GameplaySystem;
+use crate::{AppSystems, GameplaySystem, PausableSystems};
 use crate::launching::CollectorStats;
-use crate::sun_system::{Level, Satellite, Sun};
+use crate::scenario::ActiveScenario;
+use crate::screens::{InitGameResource, Screen};
+use crate::sun_system::{Level, Luminosity, Satellite, Sun};
 use std::collections::VecDeque;
+use serde::{Deserialize, Serialize};
 
 pub(crate) fn plugin(app: &mut App) {
-    app.add_systems(Update, update_score.in_set(GameplaySystem));
-    app.insert_resource(Score::default());
+    app.add_systems(
+        FixedUpdate,
+        update_score
+            .in_set(AppSystems::Update)
+            .in_set(GameplaySystem)
+            .in_set(PausableSystems),
+    );
+    app.init_game_resource::<Score>();
+    app.add_systems(OnEnter(Screen::Gameplay), apply_starting_energy);
+    app.add_plugins((history::plugin, kardashev::plugin));
 }
 
-#[derive(Resource)]
+#[derive(Resource, Clone, Serialize, Deserialize)]
 pub struct Score {
     pub energy_rate: f32,
     pub energy_stored: f32,
+    /// Energy spent on launching collectors during the run
+    pub energy_spent: f32,
     rate_history: VecDeque<(f32, f32)>, // (timestamp, rate)
     history_duration: f32,
 }
@@ -23,25 +39,40 @@ impl Default for Score {
         Self {
             energy_rate: 5.0,
             energy_stored: 8000.0,
+            energy_spent: 0.0,
             rate_history: VecDeque::new(),
             history_duration: 60.0,
         }
     }
 }
 
+impl Score {
+    /// Shifts the timestamps of the rate history, used to carry it over between sessions whose
+    /// clocks started at different times
+    pub fn rebase_history(&mut self, offset: f32) {
+        for (timestamp, _) in self.rate_history.iter_mut() {
+            *timestamp += offset;
+        }
+    }
+}
+
 #[derive(Component)]
 pub struct EnergyRateLabel;
 
+fn apply_starting_energy(mut score: ResMut<Score>, scenario: Res<ActiveScenario>) {
+    score.energy_stored = scenario.0.starting_energy;
+}
+
 fn update_score(
     mut score: ResMut<Score>,
     mut satellite_query: Query<(Entity, &Transform, &mut CollectorStats, &Level), With<Satellite>>,
-    sun_query: Query<&Transform, (With<Sun>, Without<Satellite>) >,
+    sun_query: Query<(&Transform, &Luminosity), With<Sun>>,
     mut label_query: Query<(&ChildOf, &mut Text2d), With<EnergyRateLabel>>,
 
     time: Res<Time>,
 ) {
-    let sun_transform = sun_query.single();
-    let sun_position = sun_transform.unwrap().translation;
+    let (sun_transform, luminosity) = sun_query.single().unwrap();
+    let sun_position = sun_transform.translation;
 
     let current_time = time.elapsed_secs();
     let mut instant_rate = 0.01;
@@ -49,10 +80,12 @@ fn update_score(
     for (entity, satellite_transform, mut collector_stats, level) in satellite_query.iter_mut() {
         let distance = satellite_transform.translation.distance(sun_position);
         if distance > 0.0 {
-            let mut individual_rate = 2.0 / distance;
+            let mut individual_rate = 2.0 / distance * luminosity.0;
             collector_stats.energy_rate = individual_rate;
             individual_rate = individual_rate*level.level*200.;
             instant_rate += individual_rate;
+            collector_stats.output = individual_rate;
+            collector_stats.total_collected += individual_rate * time.delta_secs();
 
             for (parent, mut text) in label_query.iter_mut() {
                 if parent.get() == entity {
@@ -1,74 +1,119 @@
-//! The screen state for the main gameplay.
+//! The game over screen that shows the result of the run.
 
 use bevy::prelude::*;
+use crate::menus::widget;
+use crate::{AppSystems, GameplaySystem, PausableSystems};
+use crate::campaign::{ActiveMission, CampaignAssets};
+use crate::hud::chart::spawn_energy_chart;
+use crate::hud::feed::{EventLog, LogIcons, spawn_event_log};
+use crate::locale::{LocaleArg, LocalizedText};
+use crate::replay::{self, GameplayTick, ReplayPlayback, ReplayRecorder};
+use crate::scenario::{ActiveScenario, Scenario};
+use crate::scenario::objectives::RunStatus;
 use crate::score::Score;
-use crate::screens::Screen;
-use crate::sun_system::SolarSystemAssets;
+use crate::score::history::EnergyHistory;
+use crate::score::kardashev::kardashev_type;
+use crate::screens::{InitGameResource, Screen};
+use crate::theme::{self, HudTheme, TextSize, ThemeColor, ThemedText};
 
 
-#[derive(Resource, Default)]
+/// Kardashev type of about 184 YW, twice what earth gets from the sun in the game
+const TWO_EARTHS_TYPE: f32 = 2.026;
+
+#[derive(Resource)]
 pub struct GameEnd{
+    /// Simulated seconds into the run at which it ends, see [`GameplayTick::elapsed_secs`]
     pub game_end_time: f32,
     pub ktype: f32,
     pub enabled: bool,
+    /// Whether the run ended by meeting a win condition of the scenario
+    pub won: bool,
 }
 
-pub(super) fn plugin(app: &mut App) {
-    app.insert_resource(GameEnd{game_end_time:600.0, ktype: 0.0, enabled: false});
-    app.add_systems(Update, enter_gameover_screen.run_if(in_state(Screen::Gameplay).and(is_gameover)));
-    app.add_systems(OnEnter(Screen::Gameover), show_game_over);
-    app.add_systems(OnEnter(Screen::Gameplay), reset_game_end_timer);
-}
-
-
-
-
-fn spawn_GameOver_screen(mut commands: Commands) {
-    commands.spawn(DespawnOnExit(Screen::Loading));
+impl Default for GameEnd {
+    fn default() -> Self {
+        Self { game_end_time: 600.0, ktype: 0.0, enabled: false, won: false }
+    }
 }
 
-fn enter_gameover_screen(mut next_screen: ResMut<NextState<Screen>>) {
-    next_screen.set(Screen::Gameover);
+pub(super) fn plugin(app: &mut App) {
+    app.init_game_resource::<GameEnd>();
+    // on fixed ticks, so a replayed run ends on the same tick as the recorded one
+    app.add_systems(
+        FixedUpdate,
+        check_game_over
+            .in_set(AppSystems::Update)
+            .in_set(GameplaySystem)
+            .in_set(PausableSystems),
+    );
+    app.add_systems(
+        OnEnter(Screen::Gameover),
+        (show_game_over, spawn_energy_chart_panel, spawn_event_log_panel).chain(),
+    );
+    app.add_systems(Update, handle_game_over_action.run_if(in_state(Screen::Gameover)));
+    app.add_systems(OnEnter(Screen::Gameplay), reset_game_end_timer);
 }
 
+fn check_game_over(
+    status: RunStatus,
+    tick: Res<GameplayTick>,
+    fixed: Res<Time<Fixed>>,
+    mut game_end: ResMut<GameEnd>,
+    mut next_screen: ResMut<NextState<Screen>>,
+) {
+    let won = status.won();
+    let lost = status.lost();
+    let time_up = game_end.enabled && tick.elapsed_secs(&fixed) - game_end.game_end_time > 0.;
 
-fn is_gameover( score: Res<Score>,
-                      time: Res<Time>,
-                      game_end: Res<GameEnd>) -> bool {
-    // 400 Yottawatt are 4 x 10^26, Kardashev type two,2.0 energy threshold
-   if (game_end.enabled && (time.elapsed_secs() - game_end.game_end_time > 0.)) || score.energy_rate >= 400. {
-       return true;
-   }
-    return false;
+    if won || lost || time_up {
+        game_end.won = won;
+        next_screen.set(Screen::Gameover);
+    }
 }
 
 
 #[derive(Component)]
 struct GameOverPopup;
 
+#[derive(Component, Debug, Clone, Copy, PartialEq, Eq)]
+enum GameOverAction {
+    NextMission,
+    Restart,
+    WatchReplay,
+    MainMenu,
+}
+
 fn show_game_over(mut commands: Commands, mut score: ResMut<Score>,
                   mut game_end: ResMut<GameEnd>,
-                  solar_system_assets: Res<SolarSystemAssets>) {
+                  mission: Res<ActiveMission>,
+                  campaign: Res<CampaignAssets>,
+                  recorder: Res<ReplayRecorder>) {
     if score.energy_rate >= 400. { score.energy_rate=400.;}
-    //let toYotta: f64=(score.energy_rate/100.) as f64* 1e24_f64; // multiplied by yotta
 
-    game_end.ktype = (1.0 + (score.energy_rate - 1.0) / 399.0).clamp(1.0, 2.0);//((toYotta.log10() - 6.0) / 10.0).abs();//((score.energy_rate.log10() + 9.0) / 10.0).max(0.0);
+    game_end.ktype = kardashev_type(score.energy_rate);
     info!("show Game Over {}", game_end.ktype);
     // Stop countdown immediately on game over (e.g., win by energy)
     game_end.enabled = false;
 
     let text_center = Justify::Center;
-    let mut better_earth = "";
-    if game_end.ktype > 1.46{
-        better_earth="You generate more Energy than 2 Earths!";
+    let mut better_earth = LocaleArg::from("");
+    if game_end.ktype > TWO_EARTHS_TYPE {
+        better_earth = LocaleArg::key("gameover.two_earths");
+    }
+    let mut game_end_string = "gameover.game_over";
+    if game_end.won {
+        game_end_string = "gameover.won";
     }
-    let mut game_end_string = "GAME OVER";
-    if score.energy_rate >= 400. {
-        game_end_string = "YOU WON!";
+    if mission.0.is_some() {
+        game_end_string = if game_end.won { "gameover.mission_complete" } else { "gameover.mission_failed" };
     }
+    let has_next_mission = game_end.won && mission.0.is_some_and(|m| m + 1 < campaign.missions.len());
+    // replays and continued runs aren't recorded
+    let has_replay = recorder.is_enabled();
     // Game-Over Popup
     commands.spawn((
         GameOverPopup,
+        DespawnOnExit(Screen::Gameover),
         Pickable::IGNORE,
         Node {
             position_type: PositionType::Absolute,
@@ -76,13 +121,15 @@ fn show_game_over(mut commands: Commands, mut score: ResMut<Score>,
             height: Val::Percent(100.0),
             justify_content: JustifyContent::Center,
             align_items: AlignItems::Center,
+            flex_wrap: FlexWrap::Wrap,
+            column_gap: Val::Px(20.0),
             ..default()
         },
         children![
             (
                 Node {
                     width: Val::Px(400.0),
-                    height: Val::Px(380.0),
+                    height: Val::Px(520.0),
                     border: UiRect::all(Val::Px(2.0)),
                     flex_direction: FlexDirection::Column,
                     justify_content: JustifyContent::Center,
@@ -90,26 +137,16 @@ fn show_game_over(mut commands: Commands, mut score: ResMut<Score>,
                     padding: UiRect::all(Val::Px(20.0)),
                     ..default()
                 },
-                BackgroundColor(Color::srgb(0.0, 0.0, 0.0)),
-                Outline {
-                    width: Val::Px(3.0),
-                    offset: Default::default(),
-                    color: Color::xyz(0.4811, 0.3064, 0.0253),
-                },
+                theme::panel(3.0),
                 children![
                     // Title
                     (
-                        Text::new(game_end_string),
+                        LocalizedText::new(game_end_string),
                         Node {
                             margin: UiRect::bottom(Val::Px(20.0)),
                             ..default()
                         },
-                        TextFont {
-                            font: solar_system_assets.font.clone(),
-                            font_size: 20.0,
-                            ..default()
-                        },
-                        TextColor(Color::xyz(0.4811, 0.3064, 0.0253)),
+                        ThemedText::new(ThemeColor::Text, TextSize::Title),
                         TextLayout::new_with_justify(text_center),
                     ),
                     // Energy bar decoration
@@ -119,68 +156,45 @@ fn show_game_over(mut commands: Commands, mut score: ResMut<Score>,
                             margin: UiRect::bottom(Val::Px(15.0)),
                             ..default()
                         },
-                        TextFont {
-                            font: solar_system_assets.font.clone(),
-                            font_size: 14.0,
-                            ..default()
-                        },
-                        TextColor(Color::xyz(0.4811, 0.3064, 0.0253)),
+                        ThemedText::new(ThemeColor::Text, TextSize::Body),
                     ),
                     // Energy Rate
                     (
-                        Text::new(format!("ENERGY RATE\n{:.3} YT", score.energy_rate)),
+                        LocalizedText::new("gameover.energy_rate").with("rate", LocaleArg::number(score.energy_rate, 3)),
                         Node {
                             margin: UiRect::bottom(Val::Px(20.0)),
                             ..default()
                         },
-                        TextFont {
-                            font: solar_system_assets.font.clone(),
-                            font_size: 16.0,
-                            ..default()
-                        },
-                        TextColor(Color::xyz(0.4811, 0.3064, 0.0253)),
+                        ThemedText::new(ThemeColor::Text, TextSize::Button),
                         TextLayout::new_with_justify(text_center),
                     ),
                     // Total Energy
                     (
-                        Text::new(format!("TOTAL ENERGY STORED\n{:.2} YTh", score.energy_stored)),
+                        LocalizedText::new("gameover.energy_stored").with("stored", LocaleArg::number(score.energy_stored, 2)),
                         Node {
                             margin: UiRect::bottom(Val::Px(20.0)),
                             ..default()
                         },
-                        TextFont {
-                            font: solar_system_assets.font.clone(),
-                            font_size: 16.0,
-                            ..default()
-                        },
-                        TextColor(Color::xyz(0.4811, 0.3064, 0.0253)),
+                        ThemedText::new(ThemeColor::Text, TextSize::Button),
                         TextLayout::new_with_justify(text_center),
                     ),
                     // Kardashev Scale
                     (
-                        Text::new(format!("Kardashev \nTYPE {:.3}\n {} ", game_end.ktype,better_earth)),
+                        LocalizedText::new("gameover.kardashev")
+                            .with("type", LocaleArg::number(game_end.ktype, 3))
+                            .with("note", better_earth),
                         Node {
                             margin: UiRect::bottom(Val::Px(25.0)),
                             ..default()
                         },
-                        TextFont {
-                            font: solar_system_assets.font.clone(),
-                            font_size: 18.0,
-                            ..default()
-                        },
-                        TextColor(Color::xyz(0.4811, 0.3064, 0.0253)),
+                        ThemedText::new(ThemeColor::Text, TextSize::Large),
                         TextLayout::new_with_justify(text_center),
                     ),
                     // Total Energy
                     (
-                        Text::new(format!("A Type 2 Civilization harnesses all power of a star")),
+                        LocalizedText::new("gameover.type_two"),
 
-                        TextFont {
-                            font: solar_system_assets.font.clone(),
-                            font_size: 16.0,
-                            ..default()
-                        },
-                        TextColor(Color::xyz(0.4811, 0.3064, 0.0253)),
+                        ThemedText::new(ThemeColor::Text, TextSize::Button),
                         TextLayout::new_with_justify(text_center),
                     ),
                     // Bottom bar decoration
@@ -190,23 +204,113 @@ fn show_game_over(mut commands: Commands, mut score: ResMut<Score>,
                             margin: UiRect::bottom(Val::Px(10.0)),
                             ..default()
                         },
-                        TextFont {
-                            font: solar_system_assets.font.clone(),
-                            font_size: 14.0,
+                        ThemedText::new(ThemeColor::Text, TextSize::Body),
+                    ),
+                    (
+                        Node {
+                            display: if has_next_mission { Display::Flex } else { Display::None },
+                            margin: UiRect::bottom(Val::Px(10.0)),
                             ..default()
                         },
-                        TextColor(Color::xyz(0.4811, 0.3064, 0.0253)),
+                        children![widget::button("gameover.next_mission", GameOverAction::NextMission)],
                     ),
-
+                    (
+                        Node {
+                            column_gap: Val::Px(20.0),
+                            margin: UiRect::bottom(Val::Px(10.0)),
+                            ..default()
+                        },
+                        children![
+                            widget::small_button("menu.restart", GameOverAction::Restart),
+                            (
+                                Node {
+                                    display: if has_replay { Display::Flex } else { Display::None },
+                                    ..default()
+                                },
+                                children![widget::small_button("gameover.replay", GameOverAction::WatchReplay)],
+                            ),
+                        ],
+                    ),
+                    widget::button("gameover.main_menu", GameOverAction::MainMenu),
                 ],
             )
         ],
     ));
 }
 
-fn reset_game_end_timer(mut game_end: ResMut<GameEnd>, time: Res<Time>) {
-    // Enable and start countdown whenever we enter Gameplay
-    game_end.enabled = true;
-    game_end.game_end_time = time.elapsed_secs() + game_end.game_end_time;
+/// Puts the energy history chart next to the popup
+fn spawn_energy_chart_panel(
+    mut commands: Commands,
+    popup: Single<Entity, With<GameOverPopup>>,
+    history: Res<EnergyHistory>,
+    theme: Res<HudTheme>,
+) {
+    commands.entity(*popup).with_children(|popup| {
+        spawn_energy_chart(popup, &history, &theme);
+    });
+}
+
+/// Puts the log of the run next to the chart
+fn spawn_event_log_panel(
+    mut commands: Commands,
+    popup: Single<Entity, With<GameOverPopup>>,
+    log: Res<EventLog>,
+    icons: LogIcons,
+) {
+    commands.entity(*popup).with_children(|popup| {
+        spawn_event_log(popup, &log, &icons);
+    });
+}
+
+fn handle_game_over_action(
+    query: Query<(&Interaction, &GameOverAction), Changed<Interaction>>,
+    mut commands: Commands,
+    mut mission: ResMut<ActiveMission>,
+    mut scenario: ResMut<ActiveScenario>,
+    campaign: Res<CampaignAssets>,
+    scenarios: Res<Assets<Scenario>>,
+    mut next_screen: ResMut<NextState<Screen>>,
+) {
+    for (interaction, action) in query.iter() {
+        if *interaction != Interaction::Pressed {
+            continue;
+        }
+        match action {
+            GameOverAction::NextMission => {
+                let Some(next) = mission.0.map(|m| m + 1) else { continue; };
+                let Some(next_scenario) = campaign.missions.get(next).and_then(|h| scenarios.get(h)) else { continue; };
+                scenario.0 = next_scenario.clone();
+                mission.0 = Some(next);
+                // after watching a replay the next run is played, not replayed
+                commands.remove_resource::<ReplayPlayback>();
+                next_screen.set(Screen::Restart);
+            }
+            GameOverAction::Restart => {
+                commands.remove_resource::<ReplayPlayback>();
+                next_screen.set(Screen::Restart);
+            }
+            GameOverAction::WatchReplay => {
+                let Some(replay) = replay::load_replay() else { continue; };
+                // a replay doesn't count towards the campaign
+                scenario.0 = replay.scenario().clone();
+                mission.0 = None;
+                commands.insert_resource(ReplayPlayback::new(replay));
+                next_screen.set(Screen::Restart);
+            }
+            GameOverAction::MainMenu => next_screen.set(Screen::Title),
+        }
+    }
+}
+
+fn reset_game_end_timer(
+    mut game_end: ResMut<GameEnd>,
+    tick: Res<GameplayTick>,
+    fixed: Res<Time<Fixed>>,
+    scenario: Res<ActiveScenario>,
+) {
+    // Start the countdown whenever we enter Gameplay, unless the scenario has no time limit
+    game_end.enabled = scenario.0.time_limit.is_some();
+    game_end.game_end_time = tick.elapsed_secs(&fixed) + scenario.0.time_limit.unwrap_or(0.0);
     game_end.ktype = 0.0;
+    game_end.won = false;
 }
\ No newline at end of file
@@ -1,145 +1,56 @@
 //! The screen state for the main gameplay.
 
-use bevy::input::common_conditions::input_just_pressed;
-use bevy::input::mouse::MouseWheel;
-use bevy::input::touch::{TouchInput, TouchPhase};
-use crate::sun_system::{init_sun_system, SolarSystemAssets};
+use crate::sun_system::init_sun_system;
 use bevy::prelude::*;
-use crate::GameplaySystem;
-use crate::screens::Screen;
-use std::collections::HashMap;
-
-#[derive(Component)]
-struct CameraZoom {
-    level: usize,
-}
-
-#[derive(Resource, Default)]
-struct PinchZoomState {
-    prev_dist: Option<f32>,
-    active: HashMap<u64, Vec2>,
-}
+use crate::{AppSystems, GameplaySystem, PausableSystems};
+use crate::controls::{Action, action_just_pressed};
+use crate::replay::{GameplayInput, InputQueue, ReplayPlayback};
+use crate::screens::{InGame, Screen};
 
 pub(super) fn plugin(app: &mut App) {
-    app.init_resource::<PinchZoomState>();
-    app.add_systems(OnEnter(Screen::Gameplay), setup_scene);
     app.add_systems(OnEnter(Screen::Gameplay), init_sun_system);
-    app.add_systems(Update, camera_zoom.in_set(GameplaySystem));
-    app.add_systems(Update, camera_pinch_zoom.in_set(GameplaySystem));
-    app.add_systems(Update, change_time_speed::<2>.run_if(input_just_pressed(KeyCode::ArrowUp)));
-    app.add_systems(Update, change_time_speed::<-2>.run_if(input_just_pressed(KeyCode::ArrowDown)));
+    app.add_systems(OnEnter(Screen::Restart), restart_run);
+    app.add_systems(OnExit(InGame), reset_time_speed);
+    app.add_observer(apply_time_speed);
+    app.add_systems(
+        Update,
+        (
+            change_time_speed::<2>.run_if(action_just_pressed(Action::SpeedUp)),
+            change_time_speed::<-2>.run_if(action_just_pressed(Action::SlowDown)),
+        )
+            .in_set(AppSystems::RecordInput)
+            .in_set(GameplaySystem)
+            .in_set(PausableSystems),
+    );
 }
 
-fn setup_scene(mut commands: Commands) {
-    commands.spawn((
-        Name::new("Camera"),
-        Camera2d,
-        Transform::from_translation(Vec3::new(0.0, 0.0, 0.0)),
-        CameraZoom { level: 2 },
-    ));
+/// The previous run has been torn down by leaving [`InGame`], start a fresh one
+fn restart_run(mut next_screen: ResMut<NextState<Screen>>) {
+    next_screen.set(Screen::Gameplay);
 }
 
+fn reset_time_speed(mut time: ResMut<Time<Virtual>>) {
+    time.set_relative_speed(1.0);
+}
 
-fn change_time_speed<const DELTA: i8>(mut time: ResMut<Time<Virtual>>) {
+fn change_time_speed<const DELTA: i8>(time: Res<Time<Virtual>>, mut inputs: ResMut<InputQueue>) {
     let time_speed = (time.relative_speed() + DELTA as f32)
         .round()
         .clamp(1., 20.);
-
-    info!("Time speed changed to {}", time_speed);
-    // set the speed of the virtual time to speed it up or slow it down
-    time.set_relative_speed(time_speed);
+    inputs.push(GameplayInput::TimeSpeed(time_speed));
 }
 
-fn camera_zoom(
-    mut scroll_evr: MessageReader<MouseWheel>,
-    mut query: Query<(&mut Transform, &mut CameraZoom), With<Camera>>,
-    mut commands: Commands,
-    assets: Res<SolarSystemAssets>,
+fn apply_time_speed(
+    ev: On<GameplayInput>,
+    mut time: ResMut<Time<Virtual>>,
+    playback: Option<ResMut<ReplayPlayback>>,
 ) {
-    //stepped zoom with predefined levels
-    let zoom_levels = [0.1, 0.15, 0.25, 0.5, 0.75];
-
-    if let Ok((mut transform, mut camera_zoom)) = query.single_mut() {
-        for ev in scroll_evr.read() {
-            let old_level = camera_zoom.level;
-            if ev.y > 0.0 && camera_zoom.level > 0 {
-                camera_zoom.level -= 1;
-            } else if ev.y < 0.0 && camera_zoom.level < zoom_levels.len() - 1 {
-                camera_zoom.level += 1;
-            }
-
-            // Play zoom sound if level changed
-            if old_level != camera_zoom.level {
-                commands.spawn((
-                    AudioPlayer::new(assets.warning_sound.clone()),
-                    PlaybackSettings::DESPAWN,
-                ));
-            }
-        }
-
-        let zoom_level = zoom_levels[camera_zoom.level];
-        transform.scale = Vec3::splat(zoom_level);
+    let GameplayInput::TimeSpeed(time_speed) = *ev else { return; };
+    info!("Time speed changed to {}", time_speed);
+    match playback {
+        // a replay runs at the recorded speed scaled by its playback speed
+        Some(mut playback) => playback.set_recorded_speed(time_speed),
+        // set the speed of the virtual time to speed it up or slow it down
+        None => time.set_relative_speed(time_speed),
     }
 }
-#[allow(deprecated)]
-fn camera_pinch_zoom(
-    mut pinch: ResMut<PinchZoomState>,
-    mut er_touch: EventReader<TouchInput>,
-    mut query: Query<(&mut Transform, &mut CameraZoom), With<Camera>>,
-    mut commands: Commands,
-    assets: Res<SolarSystemAssets>,
-) {
-    // Update active touches map
-    for ev in er_touch.read() {
-        match ev.phase {
-            TouchPhase::Started | TouchPhase::Moved => {
-                pinch.active.insert(ev.id, ev.position);
-            }
-            TouchPhase::Ended | TouchPhase::Canceled => {
-                pinch.active.remove(&ev.id);
-            }
-        }
-    }
-
-    // Only apply when two or more touches are active (use the first two)
-    if pinch.active.len() < 2 { 
-        pinch.prev_dist = None; 
-        return; 
-    }
-
-    let mut iter = pinch.active.values();
-    let p1 = *iter.next().unwrap();
-    let p2 = *iter.next().unwrap();
-    let dist = p1.distance(p2);
-
-    if let Some(prev) = pinch.prev_dist {
-        let delta = dist - prev;
-        // threshold in logical pixels to avoid jitter
-        let threshold = 5.0;
-        if delta.abs() > threshold {
-            if let Ok((mut transform, mut camera_zoom)) = query.single_mut() {
-                // same zoom levels as mouse
-                let zoom_levels = [0.1, 0.15, 0.25, 0.5, 0.75];
-                let old_level = camera_zoom.level;
-                if delta > 0.0 && camera_zoom.level > 0 {
-                    camera_zoom.level -= 1;
-                } else if delta < 0.0 && camera_zoom.level < zoom_levels.len() - 1 {
-                    camera_zoom.level += 1;
-                }
-
-                // Play zoom sound if level changed
-                if old_level != camera_zoom.level {
-                    commands.spawn((
-                        AudioPlayer::new(assets.warning_sound.clone()),
-                        PlaybackSettings::DESPAWN,
-                    ));
-                }
-
-                let zoom_level = zoom_levels[camera_zoom.level];
-                transform.scale = Vec3::splat(zoom_level);
-            }
-        }
-    }
-
-    pinch.prev_dist = Some(dist);
-}
\ No newline at end of file
@@ -3,19 +3,18 @@
 mod loading;
 mod gameplay;
 pub(crate) mod gameover;
-//mod splash;
-//mod title;
+mod title;
 
 use bevy::prelude::*;
 
 pub(super) fn plugin(app: &mut App) {
     app.init_state::<Screen>();
+    app.add_computed_state::<InGame>();
     app.add_plugins((
         gameplay::plugin,
         loading::plugin,
         gameover::plugin,
-        //splash::plugin,
-        //title::plugin,
+        title::plugin,
     ));
 }
 
@@ -26,7 +25,40 @@ pub enum Screen {
     Loading,
     Title,
     Gameplay,
-    Gameover
+    Gameover,
+    /// Passed through for a single frame to tear down the current run before starting a new one
+    Restart,
 }
 
+/// Active for as long as a run lasts, i.e. during gameplay and on the game over screen that is
+/// shown on top of the frozen scene.
+/// Entities belonging to a run should be spawned with `DespawnOnExit(InGame)`.
+#[derive(Copy, Clone, Eq, PartialEq, Hash, Debug)]
+pub struct InGame;
 
+impl ComputedStates for InGame {
+    type SourceStates = Screen;
+
+    fn compute(screen: Screen) -> Option<Self> {
+        matches!(screen, Screen::Gameplay | Screen::Gameover).then_some(InGame)
+    }
+}
+
+pub trait InitGameResource {
+    /// Initializes a resource that belongs to a run. It is reset to its initial value whenever a
+    /// run ends so the next one starts from a clean state.
+    fn init_game_resource<R: Resource + FromWorld>(&mut self) -> &mut Self;
+}
+
+impl InitGameResource for App {
+    fn init_game_resource<R: Resource + FromWorld>(&mut self) -> &mut Self {
+        self.init_resource::<R>();
+        self.add_systems(OnExit(InGame), reset_game_resource::<R>);
+        self
+    }
+}
+
+fn reset_game_resource<R: Resource + FromWorld>(world: &mut World) {
+    let value = R::from_world(world);
+    world.insert_resource(value);
+}
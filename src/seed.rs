@@ -0,0 +1,133 @@
+//! Run seeds. Every run reseeds the [`RandomSource`] so runs with the same seed get the same
+//! asteroid schedule. The seed is random by default, can be fixed in the seed menu or with
+//! `--seed <number>`, and the daily challenge derives it from the current date (`--daily`).
+
+use bevy::prelude::*;
+use rand::{Rng, SeedableRng};
+use rand_chacha::ChaCha8Rng;
+use serde::{Deserialize, Serialize};
+
+use crate::RandomSource;
+use crate::screens::Screen;
+
+pub(super) fn plugin(app: &mut App) {
+    app.insert_resource(SeedMode::from_args(std::env::args().skip(1)));
+    app.init_resource::<RunSeed>();
+    app.add_systems(OnEnter(Screen::Gameplay), reseed_run);
+    app.add_systems(OnEnter(Screen::Title), clear_seed_override);
+}
+
+/// How the seed of the next run is picked
+#[derive(Resource, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SeedMode {
+    Random,
+    Fixed(u64),
+    /// Derived from the current date, the same for every player on a day
+    Daily,
+}
+
+impl SeedMode {
+    /// Reads `--seed <number>` or `--daily` from the command line
+    fn from_args(mut args: impl Iterator<Item = String>) -> Self {
+        let mut mode = SeedMode::Random;
+        while let Some(arg) = args.next() {
+            match arg.as_str() {
+                "--seed" => match args.next().map(|value| value.parse()) {
+                    Some(Ok(seed)) => mode = SeedMode::Fixed(seed),
+                    _ => warn!("--seed expects a positive number"),
+                },
+                "--daily" => mode = SeedMode::Daily,
+                _ => {}
+            }
+        }
+        mode
+    }
+}
+
+/// Seed mode of runs started from a particular menu entry, e.g. the daily challenge, used instead
+/// of [`SeedMode`]. Restarting keeps it, it is dropped when going back to the title screen.
+#[derive(Resource, Debug, Clone, Copy)]
+pub struct SeedOverride(pub SeedMode);
+
+fn clear_seed_override(mut commands: Commands) {
+    commands.remove_resource::<SeedOverride>();
+}
+
+/// The seed of the current run
+#[derive(Resource, Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RunSeed {
+    pub seed: u64,
+    /// Set for daily challenge runs, days since 1970-01-01
+    pub day: Option<u64>,
+}
+
+impl RunSeed {
+    /// Short label for the log
+    pub fn label(&self) -> String {
+        match self.date() {
+            Some(date) => format!("DAILY {date}"),
+            None => format!("SEED {}", self.seed),
+        }
+    }
+
+    /// Date of a daily challenge run as `YYYY-MM-DD`
+    pub fn date(&self) -> Option<String> {
+        let (year, month, day) = civil_from_days(self.day?);
+        Some(format!("{year:04}-{month:02}-{day:02}"))
+    }
+}
+
+pub(crate) fn reseed_run(
+    mode: Res<SeedMode>,
+    seed_override: Option<Res<SeedOverride>>,
+    mut run_seed: ResMut<RunSeed>,
+    mut random: ResMut<RandomSource>,
+) {
+    let mode = seed_override.map_or(*mode, |seed_override| seed_override.0);
+    *run_seed = match mode {
+        SeedMode::Random => RunSeed {
+            // keep the seed short enough to be typed in again
+            seed: ChaCha8Rng::from_os_rng().random_range(0..1_000_000_000),
+            day: None,
+        },
+        SeedMode::Fixed(seed) => RunSeed { seed, day: None },
+        SeedMode::Daily => {
+            let day = days_since_epoch();
+            RunSeed {
+                // spread consecutive days over the seed space
+                seed: day.wrapping_mul(0x9E37_79B9_7F4A_7C15),
+                day: Some(day),
+            }
+        }
+    };
+    info!("Starting run with {}", run_seed.label());
+    random.0 = ChaCha8Rng::seed_from_u64(run_seed.seed);
+}
+
+#[cfg(not(target_family = "wasm"))]
+fn days_since_epoch() -> u64 {
+    std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .map(|d| d.as_secs() / 86_400)
+        .unwrap_or_default()
+}
+
+#[cfg(target_family = "wasm")]
+fn days_since_epoch() -> u64 {
+    (js_sys::Date::now() / 86_400_000.0) as u64
+}
+
+/// Converts days since 1970-01-01 to a (year, month, day) date in the proleptic Gregorian
+/// calendar, see <http://howardhinnant.github.io/date_algorithms.html#civil_from_days>
+fn civil_from_days(days: u64) -> (u64, u64, u64) {
+    let z = days + 719_468;
+    let era = z / 146_097;
+    let doe = z - era * 146_097;
+    let yoe = (doe - doe / 1460 + doe / 36_524 - doe / 146_096) / 365;
+    let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+    let mp = (5 * doy + 2) / 153;
+    let day = doy - (153 * mp + 2) / 5 + 1;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 };
+    let year = yoe + era * 400 + u64::from(month <= 2);
+    (year, month, day)
+}
@@ -1,7 +1,7 @@
 use bevy::audio::Volume;
 use bevy::prelude::*;
 use crate::collision::FatalCollisionEvent;
-use crate::screens::Screen;
+use crate::screens::{InGame, Screen};
 use crate::sun_system::{SolarSystemAssets, Sun};
 use crate::sun_system::asteroids::AsteroidSwarm;
 
@@ -31,6 +31,7 @@ fn setup_sound(mut commands: Commands, solar_system_assets: Res<SolarSystemAsset
         AudioPlayer::new(solar_system_assets.music_loop.clone()),
         PlaybackSettings::LOOP,
         Music,
+        DespawnOnExit(InGame),
     ));
 }
 
@@ -41,9 +42,7 @@ fn toggle_music(
     music_q: Query<Entity, With<Music>>,
     assets: Res<SolarSystemAssets>,
 ) {
-    let mut toggled = false;
-    for _ in ev_toggle.read() { toggled = true; break; }
-    if !toggled { return; }
+    if ev_toggle.read().count() == 0 { return; }
 
     if let Ok(e) = music_q.single() {
         commands.entity(e).despawn();
@@ -52,6 +51,7 @@ fn toggle_music(
             AudioPlayer::new(assets.music_loop.clone()),
             PlaybackSettings::LOOP,
             Music,
+            DespawnOnExit(InGame),
         ));
     }
 }
@@ -63,10 +63,10 @@ fn handle_fatal_collision_event_for_sound(
     asteroid_swarm_query: Query<Entity, With<AsteroidSwarm>>,
     sun_query: Query<(), With<Sun>>,
 ) {
-    if let Ok(asteroid_swarm_entity) = asteroid_swarm_query.single() {
-        if event.destroyed == asteroid_swarm_entity {
-            return;
-        }
+    if let Ok(asteroid_swarm_entity) = asteroid_swarm_query.single()
+        && event.destroyed == asteroid_swarm_entity
+    {
+        return;
     }
     // Mute crash SFX when swallowed by the sun
     if sun_query.get(event.other).is_ok() {
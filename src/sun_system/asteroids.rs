@@ -1,33 +1,38 @@
 use crate::asset_tracking::LoadResource;
 use crate::dev_tools::is_debug_enabled;
 use crate::physics::velocity::Velocity;
-use crate::{AppSystems, GameplaySystem, RandomSource};
+use crate::{GameplaySystem, PausableSystems, RandomSource};
 use bevy::color::palettes::basic::GREEN;
 use bevy::prelude::*;
 use rand::Rng;
+use serde::{Deserialize, Serialize};
 use std::f32::consts::PI;
 use std::ops::Range;
 use std::time::Duration;
 use crate::collision::HitBox;
 use crate::sun_system::Level;
+use crate::scenario::ActiveScenario;
+use crate::screens::{InGame, InitGameResource, Screen};
 
 pub fn plugin(app: &mut App) {
     app.load_resource::<AsteroidAssets>();
     app.init_resource::<AsteroidConfig>();
-    app.init_resource::<AsteroidTracker>();
+    app.init_game_resource::<AsteroidTracker>();
+    app.add_systems(OnEnter(Screen::Gameplay), apply_scenario_hazards);
+    // spawning runs on fixed ticks so runs with the same seed get the same asteroid schedule
     app.add_systems(
-        Update,
-        (asteroid_spawning_system)
+        FixedUpdate,
+        asteroid_spawning_system
             .in_set(GameplaySystem)
-            .in_set(AppSystems::Update),
+            .in_set(PausableSystems),
     );
     app.add_systems(PostUpdate, (draw_swarm_debug, draw_asteroid_debug).run_if(is_debug_enabled));
 }
 
 #[derive(Resource, Asset, Reflect, Debug, Clone)]
 #[reflect(Resource)]
-struct AsteroidAssets {
-    asteroid: Handle<Image>,
+pub(crate) struct AsteroidAssets {
+    pub(crate) asteroid: Handle<Image>,
 }
 
 impl FromWorld for AsteroidAssets {
@@ -41,7 +46,9 @@ impl FromWorld for AsteroidAssets {
 
 #[derive(Resource, Debug, PartialEq)]
 pub struct AsteroidConfig {
-    /// chance (evaluated per frame) that an asteroid swarm will spawn, expressed as 1 / $this
+    /// Whether asteroid swarms spawn at all
+    pub enabled: bool,
+    /// chance (evaluated per fixed tick) that an asteroid swarm will spawn, expressed as 1 / $this
     pub spawn_chance: usize,
     /// Minimum time between asteroid swarms in seconds
     pub min_time_between: usize,
@@ -60,6 +67,7 @@ pub struct AsteroidConfig {
 impl Default for AsteroidConfig {
     fn default() -> Self {
         Self {
+            enabled: true,
             spawn_chance: 500,
             min_time_between: 60,
             min_initial_wait: 45,
@@ -71,16 +79,21 @@ impl Default for AsteroidConfig {
     }
 }
 
-/// Helper for tracking state between asteroid system executions
-#[derive(Resource, Debug, Eq, PartialEq)]
-struct AsteroidTracker {
+/// Helper for tracking state between asteroid system executions, saved with the run
+#[derive(Resource, Debug, Clone, Eq, PartialEq, Serialize, Deserialize)]
+pub(crate) struct AsteroidTracker {
     start_timer: Timer,
     spawn_backoff_timer: Timer,
 }
 
 impl FromWorld for AsteroidTracker {
     fn from_world(world: &mut World) -> Self {
-        let cfg = world.resource::<AsteroidConfig>();
+        Self::new(world.resource::<AsteroidConfig>())
+    }
+}
+
+impl AsteroidTracker {
+    fn new(cfg: &AsteroidConfig) -> Self {
         Self {
             start_timer: Timer::new(
                 Duration::from_secs(cfg.min_initial_wait as u64),
@@ -94,6 +107,21 @@ impl FromWorld for AsteroidTracker {
     }
 }
 
+/// Configures asteroid spawning from the scenario of the run that is about to start
+fn apply_scenario_hazards(
+    mut cfg: ResMut<AsteroidConfig>,
+    mut tracker: ResMut<AsteroidTracker>,
+    scenario: Res<ActiveScenario>,
+) {
+    let hazards = &scenario.0.hazards;
+    cfg.enabled = hazards.asteroids;
+    cfg.spawn_chance = hazards.asteroid_spawn_chance;
+    cfg.min_time_between = hazards.asteroid_min_time_between;
+    cfg.min_initial_wait = hazards.asteroid_min_initial_wait;
+    // the timers were created from the previous config
+    *tracker = AsteroidTracker::new(&cfg);
+}
+
 /// Marker component to mark an asteroid swarm entity.
 /// It should have asteroids as children.
 #[derive(Component, Debug, Eq, PartialEq, Hash)]
@@ -106,7 +134,9 @@ pub struct AsteroidSwarm;
 pub struct Asteroid;
 
 #[derive(Event, Debug)]
-pub struct AsteroidSwarmSpawned;
+pub struct AsteroidSwarmSpawned {
+    pub swarm: Entity,
+}
 
 fn asteroid_spawning_system(
     mut commands: Commands,
@@ -116,6 +146,9 @@ fn asteroid_spawning_system(
     mut tracker: ResMut<AsteroidTracker>,
     time: Res<Time>,
 ) {
+    if !cfg.enabled {
+        return;
+    }
     tracker.start_timer.tick(time.delta());
     tracker.spawn_backoff_timer.tick(time.delta());
 
@@ -137,7 +170,7 @@ fn asteroid_spawning_system(
     if randomness.random_ratio(1, cfg.spawn_chance as u32) {
         tracker.spawn_backoff_timer.reset();
         let swarm = spawn_asteroids(&mut commands, &cfg, &assets, &mut randomness);
-        commands.trigger(AsteroidSwarmSpawned);
+        commands.trigger(AsteroidSwarmSpawned { swarm });
     }
 }
 
@@ -152,17 +185,12 @@ fn spawn_asteroids(
     let speed = random.random_range(10..20) as f32;
     info!("Spawning asteroid swarm with {num_asteroids} asteroids");
 
-    let swarm = commands
-        .spawn((
-            AsteroidSwarm,
-            Level{level:-1.},
-            Transform::from_translation(Vec3::new(-50.0, -150.0, 0.0))
-                .with_rotation(Quat::from_axis_angle(Vec3::Z, direction)),
-            InheritedVisibility::default(),
-            Velocity(Vec2::from_angle(direction + 0.5 * PI) * speed),
-            HitBox { radius: 14.0 },
-        ))
-        .id();
+    let swarm = spawn_swarm(
+        commands,
+        Transform::from_translation(Vec3::new(-50.0, -150.0, 0.0))
+            .with_rotation(Quat::from_axis_angle(Vec3::Z, direction)),
+        Vec2::from_angle(direction + 0.5 * PI) * speed,
+    );
 
 
     let mut positions = Vec::new();
@@ -183,22 +211,40 @@ fn spawn_asteroids(
 
         if let Some(pos) = position {
             positions.push(pos);
-
-            commands.spawn((
-                Asteroid,
-                ChildOf(swarm),
-                Transform::from_translation(Vec3::new(pos.x, pos.y, 0.0))
-                    .with_scale(Vec3::splat(0.01))
-                    .with_rotation(Quat::from_axis_angle(Vec3::X, PI)),
-                Sprite::from(assets.asteroid.clone()),
-
-            ));
+            spawn_asteroid(commands, assets, swarm, pos);
         }
     }
 
     swarm
 }
 
+/// Spawns an empty asteroid swarm, asteroids are added as its children with [`spawn_asteroid`]
+pub(crate) fn spawn_swarm(commands: &mut Commands, transform: Transform, velocity: Vec2) -> Entity {
+    commands
+        .spawn((
+            AsteroidSwarm,
+            DespawnOnExit(InGame),
+            Level{level:-1.},
+            transform,
+            InheritedVisibility::default(),
+            Velocity(velocity),
+            HitBox { radius: 14.0 },
+        ))
+        .id()
+}
+
+/// Spawns a single asteroid at `position` relative to its swarm
+pub(crate) fn spawn_asteroid(commands: &mut Commands, assets: &AsteroidAssets, swarm: Entity, position: Vec2) {
+    commands.spawn((
+        Asteroid,
+        ChildOf(swarm),
+        Transform::from_translation(position.extend(0.0))
+            .with_scale(Vec3::splat(0.01))
+            .with_rotation(Quat::from_axis_angle(Vec3::X, PI)),
+        Sprite::from(assets.asteroid.clone()),
+    ));
+}
+
 fn draw_swarm_debug(mut gizmos: Gizmos, query: Query<&GlobalTransform, With<AsteroidSwarm>>) {
     query.iter().for_each(|i_trans| {
         let isometry = Isometry2d::from_translation(i_trans.translation().xy());
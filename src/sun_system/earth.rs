@@ -1,16 +1,21 @@
 use crate::asset_tracking::LoadResource;
-use crate::screens::Screen;
+use crate::screens::{InGame, Screen};
 use bevy::prelude::*;
 use crate::GameplaySystem;
-use crate::launching::{make_launchpad, LaunchPad, LaunchArmed};
-use crate::sun_system::Sun;
+use crate::controls::gamepad_in_use;
+use crate::launching::{make_launchpad, LaunchState};
+use crate::physics::calc_gravity::{calc_orbital_velocity, Attractee};
+use crate::physics::directional_forces::{GravityForce, Mass};
+use crate::physics::velocity::Velocity;
+use crate::scenario::ActiveScenario;
 use crate::collision::HitBox;
+use crate::theme::{HudTheme, ThemeColor};
 
 pub(super) fn plugin(app: &mut App) {
     app.load_resource::<EarthAssets>();
     app.add_systems(OnEnter(Screen::Gameplay), init_earth);
-    app.add_systems(Update, move_earth_around_sun.in_set(GameplaySystem));
-    app.add_systems(Update, draw_arrow.in_set(GameplaySystem));
+    // the gamepad draws its own reticle
+    app.add_systems(Update, draw_arrow.run_if(not(gamepad_in_use)).in_set(GameplaySystem));
     app.add_systems(Update, draw_earth_hover.in_set(GameplaySystem));
 }
 
@@ -35,13 +40,23 @@ impl FromWorld for EarthAssets {
 #[require(Transform)]
 pub struct Earth;
 
-fn init_earth(mut commands: Commands, assets: Res<EarthAssets>) {
+fn init_earth(mut commands: Commands, assets: Res<EarthAssets>, scenario: Res<ActiveScenario>) {
     info!("Init earth");
+    let orbit = &scenario.0.earth;
+
+    // earth is launched from its periapsis, the sun sits at the origin
+    let offset = Vec2::new(orbit.periapsis, 0.0);
+    let semi_major_axis = orbit.periapsis / (1.0 - orbit.eccentricity);
 
     commands.spawn((
         Name::new("Earth"),
+        DespawnOnExit(InGame),
         Earth,
-        Transform::from_translation(Vec3::new(100.0, 0.0, 0.0)).with_scale(Vec3::splat(0.004)),
+        Attractee,
+        GravityForce::default(),
+        Velocity(calc_orbital_velocity(scenario.0.sun.mass, offset, semi_major_axis)),
+        Mass(1.0),
+        Transform::from_translation(offset.extend(0.0)).with_scale(Vec3::splat(0.004)),
         Sprite::from(assets.earth.clone()),
         HitBox { radius: 10.0 },
         children![ 
@@ -50,34 +65,12 @@ fn init_earth(mut commands: Commands, assets: Res<EarthAssets>) {
     ));
 }
 
-fn move_earth_around_sun(
-    mut earth_query: Query<&mut Transform, With<Earth>>,
-    sun_query: Query<&Transform, (With<Sun>, Without<Earth>)>,
-    mut launch_pad_query: Query<&mut Transform, (With<LaunchPad>, Without<Earth>, Without<Sun>)>,
-    time: Res<Time>
-) {
-    let sun_transform = sun_query.single();
-    let sun_position = sun_transform.unwrap().translation;
-
-    for mut earth_transform in earth_query.iter_mut() {
-        let angle_speed = 0.1;
-        let radius = earth_transform.translation.distance(sun_position);
-        let angle = time.elapsed_secs() * angle_speed;
-
-        let new_x = sun_position.x + radius * angle.cos();
-        let new_y = sun_position.y + radius * angle.sin();
-        earth_transform.translation = Vec3::new(new_x, new_y, earth_transform.translation.z);
-        // FUCK, I DONT KNOW WHY BUT TRANSFORM PROPAGATION IS BROKEN :(
-        let mut launch_pad_transform = launch_pad_query.single_mut().unwrap();
-        launch_pad_transform.translation = earth_transform.translation;
-    }
-}
-
 fn draw_arrow(
     mut gizmos: Gizmos,
     earth_query: Query<&Transform, With<Earth>>,
     camera_query: Query<(&Camera, &GlobalTransform)>,
     windows: Query<&Window>,
+    theme: Res<HudTheme>,
 ) {
     let earth_transform = earth_query.single().unwrap();
     let (camera, camera_transform) = camera_query.single().unwrap();
@@ -99,17 +92,19 @@ fn draw_arrow(
 
     let arrow_end = earth_pos + direction * arrow_length;
 
-    gizmos.arrow_2d(earth_pos, arrow_end, Color::xyz(0.1527, 0.0992, 0.0083));
+    gizmos.arrow_2d(earth_pos, arrow_end, theme.color(ThemeColor::Highlight));
 }
 fn draw_earth_hover(
     mut gizmos: Gizmos,
     earth_query: Query<(&Transform, &HitBox), With<Earth>>,
-    launch_armed: Res<LaunchArmed>,
+    launch_state: Res<LaunchState>,
+    theme: Res<HudTheme>,
 ) {
-    if !launch_armed.0 { return; }
+    // earth lights up while a launch is charging
+    if launch_state.launched_at_time.is_none() { return; }
     if let Ok((trans, hb)) = earth_query.single() {
         let center = trans.translation.truncate();
-        let color = Color::srgb(0.89, 0.647, 0.306);
+        let color = theme.color(ThemeColor::Highlight);
         gizmos.circle_2d(center, hb.radius + 0.05, color);
     }
 }
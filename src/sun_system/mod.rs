@@ -3,35 +3,38 @@ pub mod thruster;
 pub(crate) mod earth;
 pub(crate) mod asteroids;
 
-use crate::{AppSystems, GameplaySystem};
+use crate::{AppSystems, GameplaySystem, PausableSystems};
 use crate::asset_tracking::LoadResource;
+use crate::controls::{Action, action_just_pressed, action_just_released};
 use crate::physics::calc_gravity::Attractor;
 use crate::physics::directional_forces::Mass;
-use crate::screens::Screen;
+use crate::screens::{InGame, InitGameResource, Screen};
 use crate::sun_system::thruster::thruster_use_fuel;
-use bevy::input::common_conditions::{input_just_pressed, input_just_released};
 use bevy::prelude::*;
 use crate::collision::HitBox;
+use crate::scenario::ActiveScenario;
 
 
 pub(super) fn plugin(app: &mut App) {
     app.add_plugins((earth::plugin, asteroids::plugin));
     app.load_resource::<SolarSystemAssets>();
-    app.init_resource::<navigation_instruments::ShowAllOrbits>();
+    app.init_game_resource::<navigation_instruments::ShowAllOrbits>();
     app.add_systems(
         FixedUpdate,
         (thruster::apply_thrust_force)
             .in_set(AppSystems::Physics)
+            .in_set(PausableSystems)
             .run_if(in_state(Screen::Gameplay)),
     );
     app.add_systems(
         Update,
         thruster::toggle_thruster
             .run_if(
-                input_just_pressed(thruster::THRUSTER_KEY)
-                    .or(input_just_released(thruster::THRUSTER_KEY)),
+                action_just_pressed(Action::Thrust).or(action_just_released(Action::Thrust)),
             )
-            .in_set(AppSystems::RecordInput),
+            .in_set(AppSystems::RecordInput)
+            .in_set(GameplaySystem)
+            .in_set(PausableSystems),
     );
     app.add_systems(
         Update,
@@ -40,14 +43,21 @@ pub(super) fn plugin(app: &mut App) {
             .in_set(AppSystems::Update),
     );
 
-    app.add_systems(Update, thruster_use_fuel.in_set(GameplaySystem));
+    app.add_observer(thruster::apply_thruster_input);
+    app.add_systems(
+        FixedUpdate,
+        (thruster_use_fuel, navigation_instruments::track_flown_orbits)
+            .in_set(AppSystems::Update)
+            .in_set(GameplaySystem)
+            .in_set(PausableSystems),
+    );
 }
 
 #[derive(Resource, Asset, Clone, Reflect)]
 #[reflect(Resource)]
 pub struct SolarSystemAssets {
     #[dependency]
-    sun: Handle<Image>,
+    pub(crate) sun: Handle<Image>,
 
     #[dependency]
     pub(crate) redsun: Handle<Image>,
@@ -70,9 +80,6 @@ pub struct SolarSystemAssets {
     #[dependency]
     pub(crate) bg: Handle<Image>,
 
-    #[dependency]
-    pub(crate) font: Handle<Font>,
-
     #[dependency]
     pub crash_sound: Handle<AudioSource>,
 
@@ -96,6 +103,10 @@ pub struct Level{
 #[derive(Component)]
 pub struct Sun;
 
+/// How much energy the sun radiates relative to its main sequence output
+#[derive(Component, Debug, Copy, Clone)]
+pub struct Luminosity(pub f32);
+
 
 impl FromWorld for SolarSystemAssets {
     fn from_world(world: &mut World) -> Self {
@@ -109,7 +120,6 @@ impl FromWorld for SolarSystemAssets {
             collector2: assets.load("satellite_mk2.png"),
             collector3: assets.load("satellite_mk3.png"),
             bg: assets.load("retro_grid_bg.png"),
-            font: assets.load("fonts/lucon.ttf"),
             crash_sound: assets.load("sounds/collision.wav"),
             warning_sound: assets.load("sounds/zoom.wav"),
             music_loop: assets.load("sounds/music_loop.wav"),
@@ -117,18 +127,25 @@ impl FromWorld for SolarSystemAssets {
     }
 }
 
-pub fn init_sun_system(mut commands: Commands, solar_system_assets: Res<SolarSystemAssets>) {
+pub fn init_sun_system(
+    mut commands: Commands,
+    solar_system_assets: Res<SolarSystemAssets>,
+    scenario: Res<ActiveScenario>,
+) {
+    let sun = &scenario.0.sun;
     info!("Adding sun");
     commands.spawn((
         Attractor,
         Level { level: 0. }, // needed for easy collisions
         HitBox {
-            radius: 20.0
+            radius: sun.radius
         },
-        Mass(100_000_000_000_000.0),
+        Mass(sun.mass),
         Name::new("Sun"),
-        Transform::from_translation(Vec3::new(0.0, 0.0, 1.0)).with_scale(Vec3::splat(0.02)),
+        DespawnOnExit(InGame),
+        Transform::from_translation(Vec3::new(0.0, 0.0, 1.0)).with_scale(Vec3::splat(sun.sprite_scale())),
         Sprite::from(solar_system_assets.sun.clone()),
+        Luminosity(1.0),
         Sun
     ));
 }
\ No newline at end of file
@@ -1,14 +1,20 @@
+use crate::accessibility::Accessibility;
 use crate::collision::{HitBox, is_colliding};
 use crate::physics::calc_gravity::{Attractee, Attractor, calc_gravity_force};
 use crate::physics::directional_forces::{Mass, calc_velocity_change};
 use crate::physics::velocity::{Velocity, calc_position_change};
-use bevy::color::palettes::basic::GRAY;
 use bevy::prelude::*;
-use crate::achievements::{FullOrbitAchieved, FullOrbitAwarded};
-use std::f32::consts::PI;
+use serde::{Deserialize, Serialize};
+use crate::achievements::FullOrbitAchieved;
+use crate::sun_system::Satellite;
+use crate::sun_system::earth::Earth;
+use crate::theme::{HudTheme, ThemeColor};
+use std::f32::consts::{PI, TAU};
 
 const PROJECTION_DELTA: f32 = 0.5;
 const PROJECTION_MAX_COUNT: usize = 250;
+/// Size of a projection cross, and of one in high contrast mode
+const PROJECTION_CROSS_SIZE: (f32, f32) = (1.0, 2.0);
 
 #[derive(Component, Debug, Default, Copy, Clone)]
 #[require(Transform, Velocity, Mass, HitBox)]
@@ -17,53 +23,73 @@ pub struct NavigationInstruments;
 #[derive(Resource, Default)]
 pub struct ShowAllOrbits(pub bool);
 
+/// How far a collector has flown around the sun since its last full orbit
+#[derive(Component, Debug, Default, Copy, Clone, Serialize, Deserialize)]
+pub struct OrbitSweep {
+    /// Angle of the collector around the sun on the last tick, `None` before its first tick
+    last: Option<f32>,
+    /// Angle swept since the last full orbit, negative when flying clockwise
+    swept: f32,
+}
+
 pub fn draw_nav_projections(
     mut gizmos: Gizmos,
     attractor: Query<(&Transform, &Mass, &HitBox), With<Attractor>>,
-    query: Query<(Entity, &Transform, &Mass, &Velocity, &HitBox, Option<&FullOrbitAwarded>, Has<NavigationInstruments>), With<Attractee>>,
-    mut commands: Commands,
+    query: Query<(&Transform, &Mass, &Velocity, &HitBox, Has<NavigationInstruments>), (With<Attractee>, Without<Earth>)>,
     show_all: Res<ShowAllOrbits>,
+    accessibility: Res<Accessibility>,
+    theme: Res<HudTheme>,
 ) {
-    let (attractor_trans, attractor_mass, attractor_hitbox) = attractor
+    let (cross_size, cross_color) = if accessibility.high_contrast_orbits {
+        (PROJECTION_CROSS_SIZE.1, theme.color(ThemeColor::Highlight))
+    } else {
+        (PROJECTION_CROSS_SIZE.0, theme.color(ThemeColor::Neutral))
+    };
+    let attractor = attractor
         .single()
         .expect("Cannot draw orbital projections if there is no attractor in the world");
 
-    query.iter().for_each(|(entity, i_trans, i_mass, i_velocity, i_hitbox, awarded, has_nav)| {
+    query.iter().for_each(|(i_trans, i_mass, i_velocity, i_hitbox, has_nav)| {
         // Draw orbit if satellite is selected OR if show_all is enabled
         if has_nav || show_all.0 {
-            draw_orbit_projection(
-                &mut gizmos,
-                attractor_trans,
-                attractor_mass,
-                attractor_hitbox,
-                i_trans,
-                i_mass,
-                i_velocity,
-                i_hitbox,
-                &mut commands,
-                entity,
-                awarded.is_some(),
-                has_nav,
-            )
+            for point in project_orbit(attractor, i_trans, i_mass, i_velocity, i_hitbox) {
+                gizmos.cross_2d(Isometry2d::from_translation(point), cross_size, cross_color);
+            }
         }
     });
 }
 
-fn draw_orbit_projection(
-    gizmos: &mut Gizmos,
-    attractor_trans: &Transform,
-    attractor_mass: &Mass,
-    attractor_hitbox: &HitBox,
+/// Adds up the angle every collector sweeps around the sun, each full turn is a flown orbit. Runs
+/// on fixed ticks so replays count the same orbits.
+pub fn track_flown_orbits(
+    mut commands: Commands,
+    attractor: Query<&Transform, With<Attractor>>,
+    mut query: Query<(Entity, &Transform, &mut OrbitSweep), With<Satellite>>,
+) {
+    let Ok(attractor) = attractor.single() else { return; };
+    for (entity, transform, mut sweep) in query.iter_mut() {
+        let angle = (transform.translation - attractor.translation).xy().to_angle();
+        if let Some(last) = sweep.last {
+            sweep.swept += (angle - last + PI).rem_euclid(TAU) - PI;
+        }
+        sweep.last = Some(angle);
+        if sweep.swept.abs() >= TAU {
+            sweep.swept -= TAU.copysign(sweep.swept);
+            commands.trigger(FullOrbitAchieved { entity });
+        }
+    }
+}
+
+/// Where a body is headed, up to the attractor or once around it
+fn project_orbit(
+    (attractor_trans, attractor_mass, attractor_hitbox): (&Transform, &Mass, &HitBox),
     transform: &Transform,
     mass: &Mass,
     velocity: &Velocity,
     hitbox: &HitBox,
-    commands: &mut Commands,
-    entity: Entity,
-    already_awarded: bool,
-    has_nav: bool,
-) {
+) -> Vec<Vec2> {
     let mut degrees_covered = 0.0;
+    let mut points = Vec::new();
 
     let mut projected_trans = *transform;
     let mut projected_velocity = *velocity;
@@ -77,7 +103,7 @@ fn draw_orbit_projection(
         projected_trans.translation +=
             calc_position_change(&projected_velocity, PROJECTION_DELTA).extend(0.0);
 
-        // don't draw any more gizmos if they start colliding with the sun
+        // stop the projection once it collides with the sun
         if is_colliding(
             attractor_trans,
             attractor_hitbox,
@@ -87,22 +113,13 @@ fn draw_orbit_projection(
             break
         }
 
-        // draw the projection gizmo
-        gizmos.cross_2d(
-            Isometry2d::from_translation(projected_trans.translation.xy()),
-            1.0,
-            GRAY,
-        );
+        points.push(projected_trans.translation.xy());
 
-        // don't draw any more gizmos if we have covered 360°
+        // stop the projection once it has covered 360°
         degrees_covered += last_trans.angle_to(projected_trans.translation.xy()) * 180.0 / PI;
         if degrees_covered.abs() >= 355.0 {
-            if !already_awarded {
-                // Trigger achievement once per satellite
-                commands.entity(entity).insert(FullOrbitAwarded);
-                commands.trigger(FullOrbitAchieved { entity });
-            }
             break;
         }
     }
-}
\ No newline at end of file
+    points
+}
@@ -3,11 +3,12 @@ use crate::physics::directional_forces::ThrustForce;
 use crate::physics::velocity::Velocity;
 use bevy::prelude::*;
 use std::ops::Neg;
-use crate::launching::Fuel;
+use crate::launching::{CollectorId, Fuel};
+use crate::replay::{GameplayInput, InputQueue};
+use crate::sun_system::navigation_instruments::NavigationInstruments;
+use serde::{Deserialize, Serialize};
 
-pub const THRUSTER_KEY: KeyCode = KeyCode::Space;
-
-#[derive(Debug, Eq, PartialEq, Hash, Copy, Clone)]
+#[derive(Debug, Eq, PartialEq, Hash, Copy, Clone, Serialize, Deserialize)]
 #[allow(unused)]
 pub enum ThrusterDirection {
     /** Towards the velocity vector **/
@@ -38,14 +39,41 @@ impl Thruster {
     }
 }
 
-pub fn toggle_thruster(mut query: Query<(&mut Thruster, &Fuel)>) {
-    for (mut thruster, fuel) in query.iter_mut() {
-        if fuel.amount > 0.0 {
-            thruster.active = !thruster.active;
-        } else {
-            // Make sure it never flips on when empty
-            thruster.active = false;
+pub fn toggle_thruster(mut inputs: ResMut<InputQueue>) {
+    inputs.push(GameplayInput::ToggleThrusters);
+}
+
+/// Applies the gameplay inputs that switch thrusters
+pub fn apply_thruster_input(
+    ev: On<GameplayInput>,
+    mut query: Query<(&mut Thruster, &Fuel, &CollectorId, Has<NavigationInstruments>)>,
+) {
+    match *ev {
+        GameplayInput::ToggleThrusters => {
+            for (mut thruster, fuel, _, _) in query.iter_mut() {
+                if fuel.amount > 0.0 {
+                    thruster.active = !thruster.active;
+                } else {
+                    // Make sure it never flips on when empty
+                    thruster.active = false;
+                }
+            }
+        }
+        GameplayInput::SetSelectedThruster(active) => {
+            for (mut thruster, _, _, selected) in query.iter_mut() {
+                if selected {
+                    thruster.active = active;
+                }
+            }
+        }
+        GameplayInput::SetBurnDirection(target, direction) => {
+            for (mut thruster, _, id, _) in query.iter_mut() {
+                if *id == target {
+                    thruster.direction = direction;
+                }
+            }
         }
+        _ => {}
     }
 }
 
@@ -0,0 +1,357 @@
+//! HUD themes. A theme names the colours, the font and the text sizes used by the HUD, the menus
+//! and the effects. Themes are loaded from `assets/themes/*.theme.ron` and picked in the settings
+//! menu. UI elements opt in with [`ThemedText`], [`ThemedBackground`], [`ThemedOutline`] and
+//! [`ThemedBorder`], systems that draw or recolour things read [`HudTheme`] directly.
+
+use bevy::asset::io::Reader;
+use bevy::asset::{AssetLoader, LoadContext};
+use bevy::color::Srgba;
+use bevy::ecs::system::SystemParam;
+use bevy::prelude::*;
+use bevy::ui::UiSystems;
+use serde::{Deserialize, Deserializer};
+use std::collections::HashMap;
+
+use crate::asset_tracking::LoadResource;
+use crate::persistence;
+
+/// Theme files offered in the settings menu, the first one is the default.
+/// Listed explicitly because web builds can't load whole folders.
+const THEME_FILES: &[&str] = &[
+    "themes/amber.theme.ron",
+    "themes/teal.theme.ron",
+    "themes/mono.theme.ron",
+];
+/// Font of themes that don't name one
+const DEFAULT_FONT: &str = "fonts/lucon.ttf";
+/// Stores the name of the picked theme
+const THEME_KEY: &str = "theme.ron";
+
+pub(super) fn plugin(app: &mut App) {
+    app.init_asset::<Theme>();
+    app.init_asset_loader::<ThemeLoader>();
+    app.init_resource::<HudTheme>();
+    app.load_resource::<ThemeAssets>();
+    app.add_systems(Update, apply_saved_theme.run_if(resource_added::<ThemeAssets>));
+    app.add_systems(
+        PostUpdate,
+        apply_theme.in_set(ApplyTheme).before(UiSystems::Prepare),
+    );
+}
+
+/// Colours a theme names
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Deserialize)]
+pub enum ThemeColor {
+    /// Text and outlines
+    Text,
+    /// Pressed elements, dimmed text
+    Dim,
+    /// Hovered elements, the sun and emphasized values
+    Highlight,
+    /// Stored energy and other secondary values
+    Accent,
+    Background,
+    /// Blinking warnings, crashes and engulfment
+    Warning,
+    /// Asteroids and other things of no particular interest
+    Neutral,
+    /// The selected collector
+    Selected,
+    Earth,
+    /// Selection glow and achievement effects
+    Glow,
+    /// Gravity visualisation
+    Field,
+    /// Fire of collectors swallowed by the sun
+    Fire,
+    Trail,
+}
+
+impl ThemeColor {
+    /// Colours of the original HUD, used for everything a theme leaves out
+    fn fallback(self) -> Color {
+        match self {
+            ThemeColor::Text => Color::srgb_u8(0xff, 0x60, 0x00),
+            ThemeColor::Dim => Color::srgb_u8(0x98, 0x39, 0x00),
+            ThemeColor::Highlight => Color::srgb_u8(0xe3, 0xa5, 0x4e),
+            ThemeColor::Accent => Color::srgb_u8(0x08, 0x95, 0x97),
+            ThemeColor::Background => Color::srgb(0.0, 0.0, 0.0),
+            ThemeColor::Warning => Color::srgb(1.0, 0.1, 0.1),
+            ThemeColor::Neutral => Color::srgb(0.6, 0.6, 0.6),
+            ThemeColor::Selected => Color::WHITE,
+            ThemeColor::Earth => Color::srgb(0.3, 0.5, 1.0),
+            ThemeColor::Glow => Color::srgb(0.2, 0.8, 1.0),
+            ThemeColor::Field => Color::srgb(0.9, 0.8, 0.4),
+            ThemeColor::Fire => Color::srgb(1.0, 0.3, 0.05),
+            ThemeColor::Trail => Color::srgb(0.35, 0.9, 0.95),
+        }
+    }
+}
+
+/// Text sizes a theme names
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Deserialize)]
+pub enum TextSize {
+    Caption,
+    Small,
+    Body,
+    Button,
+    Large,
+    Title,
+    /// The vertical launch bar
+    Gauge,
+    Header,
+    /// Labels floating next to collectors
+    Label,
+}
+
+impl TextSize {
+    fn fallback(self) -> f32 {
+        match self {
+            TextSize::Caption => 10.0,
+            TextSize::Small => 12.0,
+            TextSize::Body => 14.0,
+            TextSize::Button => 16.0,
+            TextSize::Large => 18.0,
+            TextSize::Title => 20.0,
+            TextSize::Gauge => 25.0,
+            TextSize::Header => 32.0,
+            TextSize::Label => 27.0,
+        }
+    }
+}
+
+#[derive(Asset, TypePath, Debug, Clone)]
+pub struct Theme {
+    pub name: String,
+    pub font: Handle<Font>,
+    colors: HashMap<ThemeColor, Color>,
+    sizes: HashMap<TextSize, f32>,
+}
+
+impl Theme {
+    pub fn color(&self, color: ThemeColor) -> Color {
+        self.colors.get(&color).copied().unwrap_or_else(|| color.fallback())
+    }
+
+    pub fn size(&self, size: TextSize) -> f32 {
+        self.sizes.get(&size).copied().unwrap_or_else(|| size.fallback())
+    }
+}
+
+/// The theme in use, the original HUD colours until the theme files are loaded
+#[derive(Resource, Debug, Clone, Deref)]
+pub struct HudTheme {
+    #[deref]
+    pub theme: Theme,
+    /// Colours laid over the theme by the colour-blind palette picked in the accessibility menu
+    pub palette: HashMap<ThemeColor, Color>,
+}
+
+impl HudTheme {
+    pub fn color(&self, color: ThemeColor) -> Color {
+        self.palette.get(&color).copied().unwrap_or_else(|| self.theme.color(color))
+    }
+}
+
+impl FromWorld for HudTheme {
+    fn from_world(world: &mut World) -> Self {
+        let assets = world.resource::<AssetServer>();
+        Self {
+            theme: Theme {
+                name: String::new(),
+                font: assets.load(DEFAULT_FONT),
+                colors: HashMap::new(),
+                sizes: HashMap::new(),
+            },
+            palette: HashMap::new(),
+        }
+    }
+}
+
+#[derive(Resource, Asset, Reflect, Clone)]
+#[reflect(Resource)]
+pub struct ThemeAssets {
+    #[dependency]
+    pub themes: Vec<Handle<Theme>>,
+}
+
+impl FromWorld for ThemeAssets {
+    fn from_world(world: &mut World) -> Self {
+        let assets = world.resource::<AssetServer>();
+        Self {
+            themes: THEME_FILES.iter().map(|path| assets.load(*path)).collect(),
+        }
+    }
+}
+
+/// Picks the theme in the settings menu
+#[derive(SystemParam)]
+pub struct ThemePicker<'w> {
+    theme: ResMut<'w, HudTheme>,
+    theme_assets: Res<'w, ThemeAssets>,
+    themes: Res<'w, Assets<Theme>>,
+}
+
+impl ThemePicker<'_> {
+    /// Switches to the theme after the current one and remembers the choice
+    pub fn cycle(&mut self) {
+        let loaded: Vec<&Theme> = self.theme_assets.themes.iter().filter_map(|handle| self.themes.get(handle)).collect();
+        let current = loaded.iter().position(|t| t.name == self.theme.name);
+        let Some(next) = loaded.get(current.map_or(0, |i| (i + 1) % loaded.len())) else { return; };
+        self.theme.theme = (*next).clone();
+        if let Err(e) = persistence::write(THEME_KEY, &self.theme.name) {
+            warn!("Could not save the theme: {e}");
+        }
+    }
+}
+
+/// Text whose font, size and colour come from the theme
+#[derive(Component, Debug, Clone, Copy)]
+#[require(TextFont, TextColor)]
+pub struct ThemedText {
+    pub color: ThemeColor,
+    pub size: TextSize,
+}
+
+impl ThemedText {
+    pub fn new(color: ThemeColor, size: TextSize) -> Self {
+        Self { color, size }
+    }
+}
+
+#[derive(Component, Debug, Clone, Copy)]
+#[require(BackgroundColor)]
+pub struct ThemedBackground(pub ThemeColor);
+
+#[derive(Component, Debug, Clone, Copy)]
+pub struct ThemedOutline(pub ThemeColor);
+
+#[derive(Component, Debug, Clone, Copy)]
+#[require(BorderColor)]
+pub struct ThemedBorder(pub ThemeColor);
+
+/// The black, outlined box every HUD element sits in
+pub fn panel(outline_width: f32) -> impl Bundle {
+    (
+        ThemedBackground(ThemeColor::Background),
+        Outline {
+            width: Val::Px(outline_width),
+            offset: Default::default(),
+            color: Color::NONE,
+        },
+        ThemedOutline(ThemeColor::Text),
+    )
+}
+
+/// Themed components are updated in this set, systems that recolour themed elements on their
+/// own run after it
+#[derive(SystemSet, Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct ApplyTheme;
+
+fn apply_saved_theme(
+    mut theme: ResMut<HudTheme>,
+    theme_assets: Res<ThemeAssets>,
+    themes: Res<Assets<Theme>>,
+) {
+    let saved = persistence::read(THEME_KEY);
+    let loaded: Vec<&Theme> = theme_assets.themes.iter().filter_map(|handle| themes.get(handle)).collect();
+    let picked = loaded
+        .iter()
+        .find(|t| saved.as_deref() == Some(t.name.as_str()))
+        .or(loaded.first());
+    if let Some(picked) = picked {
+        theme.theme = (*picked).clone();
+    }
+}
+
+fn apply_theme(
+    theme: Res<HudTheme>,
+    mut texts: Query<(Ref<ThemedText>, &mut TextFont, &mut TextColor)>,
+    mut backgrounds: Query<(Ref<ThemedBackground>, &mut BackgroundColor)>,
+    mut outlines: Query<(Ref<ThemedOutline>, &mut Outline)>,
+    mut borders: Query<(Ref<ThemedBorder>, &mut BorderColor)>,
+) {
+    let changed = theme.is_changed();
+    for (themed, mut font, mut color) in texts.iter_mut() {
+        if changed || themed.is_changed() {
+            font.font = theme.font.clone();
+            font.font_size = theme.size(themed.size);
+            color.0 = theme.color(themed.color);
+        }
+    }
+    for (themed, mut background) in backgrounds.iter_mut() {
+        if changed || themed.is_changed() {
+            background.0 = theme.color(themed.0);
+        }
+    }
+    for (themed, mut outline) in outlines.iter_mut() {
+        if changed || themed.is_changed() {
+            outline.color = theme.color(themed.0);
+        }
+    }
+    for (themed, mut border) in borders.iter_mut() {
+        if changed || themed.is_changed() {
+            *border = BorderColor::all(theme.color(themed.0));
+        }
+    }
+}
+
+/// Colour written as `#rrggbb` or `#rrggbbaa`
+#[derive(Deserialize)]
+#[serde(try_from = "String")]
+struct HexColor(Color);
+
+/// Reads a colour written like the theme colours, for other RON files
+pub(crate) fn deserialize_hex_color<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Color, D::Error> {
+    HexColor::deserialize(deserializer).map(|color| color.0)
+}
+
+impl TryFrom<String> for HexColor {
+    type Error = String;
+
+    fn try_from(value: String) -> Result<Self, Self::Error> {
+        Srgba::hex(&value)
+            .map(|color| HexColor(color.into()))
+            .map_err(|e| format!("invalid colour {value}: {e}"))
+    }
+}
+
+#[derive(Deserialize)]
+struct ThemeFile {
+    name: String,
+    font: Option<String>,
+    #[serde(default)]
+    colors: HashMap<ThemeColor, HexColor>,
+    #[serde(default)]
+    sizes: HashMap<TextSize, f32>,
+}
+
+#[derive(Default)]
+struct ThemeLoader;
+
+impl AssetLoader for ThemeLoader {
+    type Asset = Theme;
+    type Settings = ();
+    type Error = BevyError;
+
+    async fn load(
+        &self,
+        reader: &mut dyn Reader,
+        _settings: &(),
+        load_context: &mut LoadContext<'_>,
+    ) -> Result<Theme, BevyError> {
+        let mut bytes = Vec::new();
+        reader.read_to_end(&mut bytes).await?;
+        let file: ThemeFile = ron::de::from_bytes(&bytes)?;
+        Ok(Theme {
+            name: file.name,
+            font: load_context.load(file.font.unwrap_or_else(|| DEFAULT_FONT.to_string())),
+            colors: file.colors.into_iter().map(|(name, color)| (name, color.0)).collect(),
+            sizes: file.sizes,
+        })
+    }
+
+    fn extensions(&self) -> &[&str] {
+        &["theme.ron"]
+    }
+}
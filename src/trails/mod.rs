@@ -1,13 +1,15 @@
 use bevy::prelude::*;
-use crate::GameplaySystem;
+use crate::{GameplaySystem, PausableSystems};
 use crate::sun_system::{Level, Satellite};
 use crate::physics::velocity::Velocity;
+use crate::theme::{HudTheme, ThemeColor};
 
 pub struct TrailsPlugin;
 
 impl Plugin for TrailsPlugin {
     fn build(&self, app: &mut App) {
-        app.add_systems(Update, (record_history, render_trails).in_set(GameplaySystem));
+        app.add_systems(Update, record_history.in_set(GameplaySystem).in_set(PausableSystems));
+        app.add_systems(Update, render_trails.in_set(GameplaySystem));
         app.add_systems(PostUpdate, attach_trails_to_satellites);
     }
 }
@@ -21,7 +23,6 @@ pub enum TrailStyle {
 #[derive(Component)]
 pub struct Trail {
     pub style: TrailStyle,
-    pub color: Color,
     pub max_points: usize,   // stored tail length
     pub spacing: f32,        // world units between samples
 }
@@ -30,8 +31,6 @@ impl Default for Trail {
     fn default() -> Self {
         Self {
             style: TrailStyle::Ribbon,
-            // muted teal fits retro NASA; tweak per style if desired
-            color: Color::srgb(0.35, 0.9, 0.95),
             max_points: 17,
             spacing: 4.0,
         }
@@ -81,9 +80,11 @@ fn record_history(
 fn render_trails(
     mut gizmos: Gizmos,
     q: Query<( &Trail, &TrailHistory)>,
+    theme: Res<HudTheme>,
 ) {
+    // read every frame so trails follow theme changes
+    let c = theme.color(ThemeColor::Trail);
     for ( trail, hist) in q.iter() {
-        let c = trail.color;
         match trail.style {
 
             TrailStyle::Ribbon => {